@@ -2,6 +2,7 @@ use crate::models::*;
 use anyhow::{Context, Result};
 use base64::{engine::general_purpose, Engine};
 use reqwest::Client;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -35,7 +36,8 @@ impl SpotifyClient {
         }
     }
 
-    pub fn get_auth_url(&self, state: &str) -> String {
+    /// Build the authorization URL; `show_dialog` forces the consent screen so another account can be chosen
+    pub fn get_auth_url(&self, state: &str, show_dialog: bool) -> String {
         let scopes = vec![
             "user-read-private",
             "user-read-email",
@@ -45,15 +47,21 @@ impl SpotifyClient {
             "playlist-read-collaborative",
             "playlist-modify-public",
             "playlist-modify-private",
+            "user-library-read",
+            "user-library-modify",
+            "user-follow-read",
+            "user-follow-modify",
+            "ugc-image-upload",
         ]
         .join(" ");
 
         format!(
-            "https://accounts.spotify.com/authorize?client_id={}&response_type=code&redirect_uri={}&scope={}&state={}",
+            "https://accounts.spotify.com/authorize?client_id={}&response_type=code&redirect_uri={}&scope={}&state={}&show_dialog={}",
             self.client_id,
             urlencoding::encode(&self.redirect_uri),
             urlencoding::encode(&scopes),
-            state
+            state,
+            show_dialog
         )
     }
 
@@ -96,6 +104,42 @@ impl SpotifyClient {
         self.token.read().await.clone()
     }
 
+    /// Fetch a paged endpoint and follow its `next` links until every item is collected
+    async fn get_all_pages<T: DeserializeOwned>(&self, url: String, what: &str) -> Result<Vec<T>> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let mut all_items = Vec::new();
+        let mut next_url = Some(url);
+
+        while let Some(url) = next_url {
+            let response = self
+                .client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", token))
+                .send()
+                .await
+                .context(format!("Failed to fetch {}", what))?;
+
+            let status = response.status();
+            let response_text = response.text().await?;
+
+            if !status.is_success() {
+                return Err(anyhow::anyhow!("Failed to fetch {}: {} - {}", what, status, response_text));
+            }
+
+            let page: Paging<T> = serde_json::from_str(&response_text)
+                .context(format!("Failed to parse {} response: {}", what, response_text))?;
+
+            all_items.extend(page.items);
+            next_url = page.next;
+        }
+
+        Ok(all_items)
+    }
+
     pub async fn get_current_user(&self) -> Result<User> {
         let token = self
             .get_token()
@@ -113,6 +157,32 @@ impl SpotifyClient {
         response.json().await.context("Failed to parse user")
     }
 
+    /// Fetch the public profile of any user, e.g. a playlist contributor
+    pub async fn get_user(&self, user_id: &UserId) -> Result<PublicUser> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let url = format!("https://api.spotify.com/v1/users/{}", urlencoding::encode(user_id.as_str()));
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .send()
+            .await
+            .context("Failed to fetch user profile")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Failed to fetch user profile: {} - {}", status, error_text));
+        }
+
+        response.json().await.context("Failed to parse user profile")
+    }
+
     pub async fn get_top_tracks(&self, limit: u32, time_range: &str) -> Result<Vec<Track>> {
         let token = self
             .get_token()
@@ -195,7 +265,7 @@ impl SpotifyClient {
         Ok(playlists_response.items)
     }
 
-    pub async fn get_playlist(&self, playlist_id: &str) -> Result<Playlist> {
+    pub async fn get_playlist(&self, playlist_id: &PlaylistId) -> Result<Playlist> {
         let token = self
             .get_token()
             .await
@@ -219,7 +289,13 @@ impl SpotifyClient {
         Ok(playlist)
     }
 
-    pub async fn create_playlist(&self, name: &str, description: &str, public: bool) -> Result<Playlist> {
+    pub async fn create_playlist(
+        &self,
+        name: &str,
+        description: &str,
+        public: bool,
+        collaborative: bool,
+    ) -> Result<Playlist> {
         let token = self
             .get_token()
             .await
@@ -233,7 +309,8 @@ impl SpotifyClient {
         let body = serde_json::json!({
             "name": name,
             "description": description,
-            "public": public
+            "public": public,
+            "collaborative": collaborative
         });
 
         let response = self
@@ -254,7 +331,70 @@ impl SpotifyClient {
         Ok(playlist)
     }
 
-    pub async fn add_tracks_to_playlist(&self, playlist_id: &str, track_uris: Vec<String>) -> Result<()> {
+    /// Change the name, description and visibility of a playlist
+    pub async fn update_playlist_details(&self, playlist_id: &PlaylistId, details: &PlaylistDetailsUpdate) -> Result<()> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let url = format!("https://api.spotify.com/v1/playlists/{}", playlist_id);
+
+        let response = self
+            .client
+            .put(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Content-Type", "application/json")
+            .json(details)
+            .send()
+            .await
+            .context("Failed to update playlist details")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Failed to update playlist details: {} - {}", status, error_text));
+        }
+
+        Ok(())
+    }
+
+    /// Replace the cover of a playlist with a base64 encoded JPEG
+    pub async fn upload_playlist_cover(&self, playlist_id: &PlaylistId, image_base64: String) -> Result<()> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let url = format!("https://api.spotify.com/v1/playlists/{}/images", playlist_id);
+
+        let response = self
+            .client
+            .put(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Content-Type", "image/jpeg")
+            .body(image_base64)
+            .send()
+            .await
+            .context("Failed to upload playlist cover")?;
+
+        let status = response.status();
+        // Logins from before cover uploads were added lack the ugc-image-upload scope
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            return Err(anyhow::anyhow!(
+                "Spotify refused the cover upload ({}). Log out and log in again to allow image uploads.",
+                status
+            ));
+        }
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Failed to upload playlist cover: {} - {}", status, error_text));
+        }
+
+        Ok(())
+    }
+
+    pub async fn add_tracks_to_playlist(&self, playlist_id: &PlaylistId, track_uris: Vec<PlayableUri>) -> Result<()> {
         let token = self
             .get_token()
             .await
@@ -265,7 +405,7 @@ impl SpotifyClient {
         // Spotify API limits to 100 tracks per request
         for chunk in track_uris.chunks(100) {
             let body = serde_json::json!({
-                "uris": chunk
+                "uris": chunk.iter().map(PlayableUri::uri).collect::<Vec<_>>()
             });
 
             let response = self
@@ -288,40 +428,152 @@ impl SpotifyClient {
         Ok(())
     }
 
-    pub async fn get_recently_played(&self, limit: u32) -> Result<Vec<RecentlyPlayedItem>> {
+    /// Insert tracks starting at `position`, returning the new snapshot ID
+    pub async fn insert_tracks_at(
+        &self,
+        playlist_id: &PlaylistId,
+        track_uris: Vec<PlayableUri>,
+        position: usize,
+    ) -> Result<String> {
         let token = self
             .get_token()
             .await
             .context("No access token available")?;
 
-        let url = format!("https://api.spotify.com/v1/me/player/recently-played?limit={}", limit);
+        let url = format!("https://api.spotify.com/v1/playlists/{}/tracks", playlist_id);
+        let mut snapshot_id = String::new();
+
+        // Spotify API limits to 100 tracks per request, later chunks follow the earlier ones
+        for (index, chunk) in track_uris.chunks(100).enumerate() {
+            let body = serde_json::json!({
+                "uris": chunk.iter().map(PlayableUri::uri).collect::<Vec<_>>(),
+                "position": position + index * 100
+            });
+
+            let response = self
+                .client
+                .post(&url)
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .json(&body)
+                .send()
+                .await
+                .context("Failed to insert tracks into playlist")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let text = response.text().await?;
+                return Err(anyhow::anyhow!("Failed to insert tracks: {} - {}", status, text));
+            }
+
+            let snapshot: SnapshotResponse = response
+                .json()
+                .await
+                .context("Failed to parse insert tracks response")?;
+            snapshot_id = snapshot.snapshot_id;
+        }
+
+        Ok(snapshot_id)
+    }
+
+    /// Move `range_length` tracks starting at `range_start` in front of `insert_before`
+    pub async fn reorder_playlist_items(
+        &self,
+        playlist_id: &PlaylistId,
+        range_start: usize,
+        range_length: usize,
+        insert_before: usize,
+        snapshot_id: Option<&str>,
+    ) -> Result<String> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let url = format!("https://api.spotify.com/v1/playlists/{}/tracks", playlist_id);
+
+        let mut body = serde_json::json!({
+            "range_start": range_start,
+            "range_length": range_length,
+            "insert_before": insert_before
+        });
+        if let Some(snapshot_id) = snapshot_id {
+            body["snapshot_id"] = serde_json::json!(snapshot_id);
+        }
 
         let response = self
             .client
-            .get(&url)
+            .put(&url)
             .header("Authorization", format!("Bearer {}", token))
+            .header("Content-Type", "application/json")
+            .json(&body)
             .send()
             .await
-            .context("Failed to fetch recently played tracks")?;
+            .context("Failed to reorder playlist")?;
 
-        let status = response.status();
-        let response_text = response.text().await?;
-
-        println!("DEBUG Recently Played - Status: {}", status);
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await?;
+            return Err(anyhow::anyhow!("Failed to reorder playlist: {} - {}", status, text));
+        }
 
-        let recent_response: RecentlyPlayedResponse = serde_json::from_str(&response_text)
-            .context(format!("Failed to parse recently played response: {}", response_text))?;
+        let snapshot: SnapshotResponse = response
+            .json()
+            .await
+            .context("Failed to parse reorder response")?;
 
-        Ok(recent_response.items)
+        Ok(snapshot.snapshot_id)
     }
 
-    pub async fn get_playlist_tracks(&self, playlist_id: &str) -> Result<Vec<PlaylistTrackItem>> {
+    /// Replace all items of a playlist. The replace endpoint accepts 100 URIs, the rest is appended.
+    pub async fn replace_playlist_items(
+        &self,
+        playlist_id: &PlaylistId,
+        track_uris: Vec<PlayableUri>,
+    ) -> Result<String> {
         let token = self
             .get_token()
             .await
             .context("No access token available")?;
 
         let url = format!("https://api.spotify.com/v1/playlists/{}/tracks", playlist_id);
+        let first_chunk: Vec<String> = track_uris.iter().take(100).map(PlayableUri::uri).collect();
+
+        let response = self
+            .client
+            .put(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Content-Type", "application/json")
+            .json(&serde_json::json!({ "uris": first_chunk }))
+            .send()
+            .await
+            .context("Failed to replace playlist items")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await?;
+            return Err(anyhow::anyhow!("Failed to replace playlist items: {} - {}", status, text));
+        }
+
+        let snapshot: SnapshotResponse = response
+            .json()
+            .await
+            .context("Failed to parse replace response")?;
+
+        if track_uris.len() > 100 {
+            return self.insert_tracks_at(playlist_id, track_uris[100..].to_vec(), 100).await;
+        }
+
+        Ok(snapshot.snapshot_id)
+    }
+
+    pub async fn get_recently_played(&self, limit: u32) -> Result<Vec<RecentlyPlayedItem>> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let url = format!("https://api.spotify.com/v1/me/player/recently-played?limit={}", limit);
 
         let response = self
             .client
@@ -329,20 +581,43 @@ impl SpotifyClient {
             .header("Authorization", format!("Bearer {}", token))
             .send()
             .await
-            .context("Failed to fetch playlist tracks")?;
+            .context("Failed to fetch recently played tracks")?;
 
         let status = response.status();
         let response_text = response.text().await?;
 
-        println!("DEBUG Playlist Tracks - Status: {}", status);
+        println!("DEBUG Recently Played - Status: {}", status);
 
-        let tracks_response: PlaylistTracksResponse = serde_json::from_str(&response_text)
-            .context(format!("Failed to parse playlist tracks response: {}", response_text))?;
+        let recent_response: RecentlyPlayedResponse = serde_json::from_str(&response_text)
+            .context(format!("Failed to parse recently played response: {}", response_text))?;
 
-        Ok(tracks_response.items)
+        Ok(recent_response.items)
     }
 
-    pub async fn remove_tracks_from_playlist(&self, playlist_id: &str, tracks_with_positions: Vec<(String, usize)>) -> Result<()> {
+    /// Fetch every entry of a playlist. With a `market` Spotify relinks tracks to versions available
+    /// there and fills in `is_playable`, `linked_from` and `restrictions`
+    pub async fn get_playlist_tracks(&self, playlist_id: &PlaylistId, market: Option<&str>) -> Result<Vec<PlaylistTrackItem>> {
+        // Without `additional_types` episodes come back as track objects
+        let mut url = format!(
+            "https://api.spotify.com/v1/playlists/{}/tracks?limit=100&additional_types=track,episode",
+            playlist_id
+        );
+        if let Some(market) = market {
+            url.push_str(&format!("&market={}", market));
+        }
+
+        self.get_all_pages(url, "playlist tracks").await
+    }
+
+    /// Remove tracks at the given positions. Positions are resolved against `snapshot_id`
+    /// (the current snapshot when `None`), so every chunk refers to the same playlist state.
+    /// Returns the snapshot ID of the playlist after the removal.
+    pub async fn remove_tracks_from_playlist(
+        &self,
+        playlist_id: &PlaylistId,
+        tracks_with_positions: Vec<(PlayableUri, usize)>,
+        snapshot_id: Option<&str>,
+    ) -> Result<String> {
         let token = self
             .get_token()
             .await
@@ -350,24 +625,36 @@ impl SpotifyClient {
 
         let url = format!("https://api.spotify.com/v1/playlists/{}/tracks", playlist_id);
 
+        let snapshot_id = match snapshot_id {
+            Some(snapshot_id) => snapshot_id.to_string(),
+            None => self.get_playlist(playlist_id).await?.snapshot_id.unwrap_or_default(),
+        };
+        let mut new_snapshot_id = snapshot_id.clone();
+
         // Group positions by URI
-        let mut uri_positions: std::collections::HashMap<String, Vec<usize>> = std::collections::HashMap::new();
+        let mut uri_positions: Vec<(PlayableUri, Vec<usize>)> = Vec::new();
         for (uri, position) in tracks_with_positions {
-            uri_positions.entry(uri).or_insert_with(Vec::new).push(position);
+            match uri_positions.iter_mut().find(|(existing, _)| *existing == uri) {
+                Some((_, positions)) => positions.push(position),
+                None => uri_positions.push((uri, vec![position])),
+            }
         }
 
-        // For each unique URI, remove all its positions in one request
-        for (uri, positions) in uri_positions {
-            let tracks_to_remove = vec![serde_json::json!({
-                "uri": uri,
-                "positions": positions
-            })];
+        // Spotify API limits to 100 tracks per request
+        for chunk in uri_positions.chunks(100) {
+            let tracks_to_remove: Vec<serde_json::Value> = chunk
+                .iter()
+                .map(|(uri, positions)| serde_json::json!({ "uri": uri.uri(), "positions": positions }))
+                .collect();
 
-            let body = serde_json::json!({
+            let mut body = serde_json::json!({
                 "tracks": tracks_to_remove
             });
+            if !snapshot_id.is_empty() {
+                body["snapshot_id"] = serde_json::json!(snapshot_id);
+            }
 
-            println!("DEBUG: Removing track with body: {}", serde_json::to_string_pretty(&body).unwrap());
+            println!("DEBUG: Removing tracks with body: {}", serde_json::to_string_pretty(&body).unwrap());
 
             let response = self
                 .client
@@ -384,12 +671,32 @@ impl SpotifyClient {
                 let text = response.text().await?;
                 return Err(anyhow::anyhow!("Failed to remove tracks: {} - {}", status, text));
             }
+
+            let snapshot: SnapshotResponse = response
+                .json()
+                .await
+                .context("Failed to parse remove tracks response")?;
+            new_snapshot_id = snapshot.snapshot_id;
         }
 
-        Ok(())
+        Ok(new_snapshot_id)
+    }
+
+    /// Fail if the playlist was modified since `expected_snapshot_id` was read
+    pub async fn check_snapshot(&self, playlist_id: &PlaylistId, expected_snapshot_id: &str) -> Result<()> {
+        let playlist = self.get_playlist(playlist_id).await?;
+
+        match playlist.snapshot_id {
+            Some(current) if current != expected_snapshot_id => Err(anyhow::anyhow!(
+                "'{}' was changed since it was loaded, reload it and try again",
+                playlist.name
+            )),
+            _ => Ok(()),
+        }
     }
 
-    pub async fn get_tracks(&self, track_ids: Vec<String>) -> Result<Vec<Track>> {
+    /// Fetch full track objects; passing a `market` makes Spotify fill in `is_playable`
+    pub async fn get_tracks(&self, track_ids: Vec<TrackId>, market: Option<&str>) -> Result<Vec<Track>> {
         let token = self
             .get_token()
             .await
@@ -399,8 +706,10 @@ impl SpotifyClient {
 
         // Spotify API allows up to 50 tracks per request
         for chunk in track_ids.chunks(50) {
-            let ids = chunk.join(",");
-            let url = format!("https://api.spotify.com/v1/tracks?ids={}", ids);
+            let mut url = format!("https://api.spotify.com/v1/tracks?ids={}", join_ids(chunk));
+            if let Some(market) = market {
+                url.push_str(&format!("&market={}", market));
+            }
 
             let response = self
                 .client
@@ -431,4 +740,390 @@ impl SpotifyClient {
 
         Ok(all_tracks)
     }
+
+    /// Fetch full artist objects (with genres), 50 per request
+    pub async fn get_artists(&self, artist_ids: Vec<ArtistId>) -> Result<Vec<Artist>> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let mut all_artists = Vec::new();
+
+        for chunk in artist_ids.chunks(50) {
+            let url = format!("https://api.spotify.com/v1/artists?ids={}", join_ids(chunk));
+
+            let response = self
+                .client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", token))
+                .send()
+                .await
+                .context("Failed to fetch artists")?;
+
+            let status = response.status();
+            let response_text = response.text().await?;
+
+            if !status.is_success() {
+                return Err(anyhow::anyhow!("Failed to fetch artists: {} - {}", status, response_text));
+            }
+
+            #[derive(Deserialize)]
+            struct ArtistsResponse {
+                artists: Vec<Option<Artist>>,
+            }
+
+            let artists_response: ArtistsResponse = serde_json::from_str(&response_text)
+                .context("Failed to parse artists response")?;
+
+            all_artists.extend(artists_response.artists.into_iter().flatten());
+        }
+
+        Ok(all_artists)
+    }
+
+    /// Fetch an artist's most popular tracks in the given market (up to 10)
+    pub async fn get_artist_top_tracks(&self, artist_id: &ArtistId, market: &str) -> Result<Vec<Track>> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let url = format!(
+            "https://api.spotify.com/v1/artists/{}/top-tracks?market={}",
+            artist_id, market
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .send()
+            .await
+            .context("Failed to fetch artist top tracks")?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("Failed to fetch artist top tracks: {} - {}", status, response_text));
+        }
+
+        #[derive(Deserialize)]
+        struct ArtistTopTracksResponse {
+            tracks: Vec<Track>,
+        }
+
+        let top_tracks_response: ArtistTopTracksResponse = serde_json::from_str(&response_text)
+            .context("Failed to parse artist top tracks")?;
+
+        Ok(top_tracks_response.tracks)
+    }
+
+    /// Search the catalog for tracks, e.g. with `isrc:` or `track:`/`artist:` filters
+    pub async fn search_tracks(&self, query: &str, market: Option<&str>, limit: u32) -> Result<Vec<Track>> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let mut url = format!(
+            "https://api.spotify.com/v1/search?type=track&limit={}&q={}",
+            limit,
+            urlencoding::encode(query)
+        );
+        if let Some(market) = market {
+            url.push_str(&format!("&market={}", market));
+        }
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .send()
+            .await
+            .context("Failed to search tracks")?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("Failed to search tracks: {} - {}", status, response_text));
+        }
+
+        #[derive(Deserialize)]
+        struct SearchResponse {
+            tracks: Paging<Track>,
+        }
+
+        let search_response: SearchResponse = serde_json::from_str(&response_text)
+            .context(format!("Failed to parse search response: {}", response_text))?;
+
+        Ok(search_response.tracks.items)
+    }
+
+    /// Check which of the given tracks are in the user's library, 50 per request
+    pub async fn check_saved_tracks(&self, track_ids: Vec<TrackId>) -> Result<Vec<bool>> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let mut saved = Vec::new();
+
+        for chunk in track_ids.chunks(50) {
+            let url = format!("https://api.spotify.com/v1/me/tracks/contains?ids={}", join_ids(chunk));
+
+            let response = self
+                .client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", token))
+                .send()
+                .await
+                .context("Failed to check saved tracks")?;
+
+            let status = response.status();
+            let response_text = response.text().await?;
+
+            if !status.is_success() {
+                return Err(anyhow::anyhow!("Failed to check saved tracks: {} - {}", status, response_text));
+            }
+
+            let flags: Vec<bool> = serde_json::from_str(&response_text)
+                .context("Failed to parse saved tracks check")?;
+            saved.extend(flags);
+        }
+
+        Ok(saved)
+    }
+
+    /// Fetch every playlist the user owns or follows, following pagination
+    pub async fn get_all_playlists(&self) -> Result<Vec<Playlist>> {
+        let url = "https://api.spotify.com/v1/me/playlists?limit=50".to_string();
+
+        self.get_all_pages(url, "playlists").await
+    }
+
+    pub async fn get_saved_tracks(&self) -> Result<Vec<SavedTrack>> {
+        let url = "https://api.spotify.com/v1/me/tracks?limit=50".to_string();
+
+        self.get_all_pages(url, "saved tracks").await
+    }
+
+    pub async fn get_saved_albums(&self) -> Result<Vec<SavedAlbum>> {
+        let url = "https://api.spotify.com/v1/me/albums?limit=50".to_string();
+
+        self.get_all_pages(url, "saved albums").await
+    }
+
+    pub async fn get_followed_artists(&self) -> Result<Vec<Artist>> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let mut all_artists = Vec::new();
+        let mut next_url = Some("https://api.spotify.com/v1/me/following?type=artist&limit=50".to_string());
+
+        // Followed artists use cursor paging wrapped in an "artists" object
+        while let Some(url) = next_url {
+            let response = self
+                .client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", token))
+                .send()
+                .await
+                .context("Failed to fetch followed artists")?;
+
+            let status = response.status();
+            let response_text = response.text().await?;
+
+            if !status.is_success() {
+                return Err(anyhow::anyhow!("Failed to fetch followed artists: {} - {}", status, response_text));
+            }
+
+            let followed: FollowedArtistsResponse = serde_json::from_str(&response_text)
+                .context(format!("Failed to parse followed artists response: {}", response_text))?;
+
+            all_artists.extend(followed.artists.items);
+            next_url = followed.artists.next;
+        }
+
+        Ok(all_artists)
+    }
+
+    pub async fn follow_artists(&self, artist_ids: Vec<ArtistId>) -> Result<()> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let url = "https://api.spotify.com/v1/me/following?type=artist";
+
+        // Spotify API limits to 50 artists per request
+        for chunk in artist_ids.chunks(50) {
+            let body = serde_json::json!({
+                "ids": chunk.iter().map(|id| id.as_str()).collect::<Vec<_>>()
+            });
+
+            let response = self
+                .client
+                .put(url)
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .json(&body)
+                .send()
+                .await
+                .context("Failed to follow artists")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let text = response.text().await?;
+                return Err(anyhow::anyhow!("Failed to follow artists: {} - {}", status, text));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Follow a playlist; `public` decides whether it shows on the user's profile
+    pub async fn follow_playlist(&self, playlist_id: &PlaylistId, public: bool) -> Result<()> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let url = format!("https://api.spotify.com/v1/playlists/{}/followers", playlist_id);
+
+        let response = self
+            .client
+            .put(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Content-Type", "application/json")
+            .json(&serde_json::json!({ "public": public }))
+            .send()
+            .await
+            .context("Failed to follow playlist")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await?;
+            return Err(anyhow::anyhow!("Failed to follow playlist: {} - {}", status, text));
+        }
+
+        Ok(())
+    }
+
+    /// Whether a user still follows a playlist. Deleting a playlist only unfollows it, so this is
+    /// how to tell a deleted playlist from an existing one.
+    pub async fn is_following_playlist(&self, playlist_id: &PlaylistId, user_id: &UserId) -> Result<bool> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let url = format!(
+            "https://api.spotify.com/v1/playlists/{}/followers/contains?ids={}",
+            playlist_id,
+            urlencoding::encode(user_id.as_str())
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .send()
+            .await
+            .context("Failed to check playlist followers")?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("Failed to check playlist followers: {} - {}", status, response_text));
+        }
+
+        let flags: Vec<bool> = serde_json::from_str(&response_text)
+            .context("Failed to parse playlist followers check")?;
+        Ok(flags.first().copied().unwrap_or(false))
+    }
+
+    /// Save tracks to the library, keeping their original `added_at` timestamps
+    pub async fn save_tracks(&self, tracks_with_dates: Vec<(TrackId, String)>) -> Result<()> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let url = "https://api.spotify.com/v1/me/tracks";
+
+        // Spotify API limits to 50 tracks per request
+        for chunk in tracks_with_dates.chunks(50) {
+            let timestamped_ids: Vec<serde_json::Value> = chunk
+                .iter()
+                .map(|(id, added_at)| serde_json::json!({ "id": id.as_str(), "added_at": added_at }))
+                .collect();
+
+            let body = serde_json::json!({
+                "timestamped_ids": timestamped_ids
+            });
+
+            let response = self
+                .client
+                .put(url)
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .json(&body)
+                .send()
+                .await
+                .context("Failed to save tracks")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let text = response.text().await?;
+                return Err(anyhow::anyhow!("Failed to save tracks: {} - {}", status, text));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn save_albums(&self, album_ids: Vec<AlbumId>) -> Result<()> {
+        let token = self
+            .get_token()
+            .await
+            .context("No access token available")?;
+
+        let url = "https://api.spotify.com/v1/me/albums";
+
+        // Spotify API limits to 20 albums per request
+        for chunk in album_ids.chunks(20) {
+            let body = serde_json::json!({
+                "ids": chunk.iter().map(|id| id.as_str()).collect::<Vec<_>>()
+            });
+
+            let response = self
+                .client
+                .put(url)
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .json(&body)
+                .send()
+                .await
+                .context("Failed to save albums")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let text = response.text().await?;
+                return Err(anyhow::anyhow!("Failed to save albums: {} - {}", status, text));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Comma separated IDs for the `ids` query parameter
+fn join_ids<T: IdType>(ids: &[SpotifyId<T>]) -> String {
+    ids.iter().map(|id| id.as_str()).collect::<Vec<_>>().join(",")
 }
\ No newline at end of file
@@ -0,0 +1,228 @@
+use crate::models::{Playlist, PlaylistTrackItem};
+use crate::utils::*;
+use crate::AppContext;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::{FaArrowDownWideShort, FaArrowUpShortWide, FaXmark};
+use dioxus_free_icons::Icon;
+
+/// Sort a playlist with one or more keys and write the new order to Spotify
+#[component]
+pub fn ApplySortModal(
+    playlist: Playlist,
+    items: Vec<PlaylistTrackItem>,
+    initial_keys: Vec<SortKey>,
+    on_close: EventHandler<()>,
+) -> Element {
+    let context = use_context::<AppContext>();
+    let mut keys = use_signal(move || initial_keys);
+    let mut method = use_signal(|| ApplyMethod::Reorder);
+    let mut applying = use_signal(|| false);
+
+    let order = sorted_order(&items, &keys());
+    let moved = order.iter().enumerate().filter(|(new, old)| *new != **old).count();
+    let move_requests = reorder_moves(&order).len();
+
+    let apply = {
+        let context = context.clone();
+        let playlist = playlist.clone();
+        let items = items.clone();
+        let order = order.clone();
+        move |_| {
+            let Some(client) = context.spotify_client.read().clone() else {
+                return;
+            };
+            let context = context.clone();
+            let playlist = playlist.clone();
+            let items = items.clone();
+            let order = order.clone();
+            let method = method();
+            let description = format!(
+                "Sorted by {}",
+                keys().iter().map(|key| key.field.label().to_lowercase()).collect::<Vec<_>>().join(", ")
+            );
+
+            applying.set(true);
+
+            spawn(async move {
+                // The order was computed from the loaded tracks, make sure nobody changed them since
+                if let Some(snapshot_id) = &playlist.snapshot_id {
+                    if let Err(e) = client.check_snapshot(&playlist.playlist_id(), snapshot_id).await {
+                        show_error(&context, format!("{}", e));
+                        applying.set(false);
+                        return;
+                    }
+                }
+
+                match apply_order(&client, &playlist.playlist_id(), &items, &order, playlist.snapshot_id.clone(), method).await {
+                    Ok(snapshot_id) => {
+                        let operation_ids = record_operations(
+                            &context,
+                            vec![reorder_entry(&description, &playlist, &items, Some(snapshot_id))],
+                        );
+                        show_success_with_undo(&context, format!("Saved new order of {}", playlist.name), operation_ids);
+                        on_close.call(());
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to apply sort: {}", e);
+                        show_error(&context, format!("Failed to save the new order: {}", e));
+                    }
+                }
+                applying.set(false);
+            });
+        }
+    };
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/sorting.css") }
+		div { class: "modal-overlay", onclick: move |_| on_close.call(()),
+			div {
+				class: "modal-content sort-modal",
+				onclick: move |e| e.stop_propagation(),
+				div { class: "modal-header",
+					h2 { "Save Sort Order to Spotify" }
+					button {
+						class: "modal-close",
+						onclick: move |_| on_close.call(()),
+						Icon { icon: FaXmark, width: 20, height: 20 }
+					}
+				}
+
+				div { class: "modal-body",
+					// Sort keys, the first one has the highest priority
+					div { class: "sort-keys",
+						for (index , key) in keys().into_iter().enumerate() {
+							div { class: "sort-key-row", key: "{index}",
+								span { class: "sort-key-label",
+									if index == 0 {
+										"Sort by"
+									} else {
+										"then by"
+									}
+								}
+								select {
+									class: "sort-key-select",
+									value: "{key.field.key()}",
+									onchange: move |e| {
+									    if let Some(field) = SortField::from_key(&e.value()) {
+									        keys.write()[index].field = field;
+									    }
+									},
+									for field in SortField::all() {
+										option { key: "{field.key()}", value: "{field.key()}", "{field.label()}" }
+									}
+								}
+								button {
+									class: "sort-button",
+									title: if key.descending { "Descending" } else { "Ascending" },
+									onclick: move |_| {
+									    let mut keys = keys.write();
+									    keys[index].descending = !keys[index].descending;
+									},
+									if key.descending {
+										Icon { icon: FaArrowDownWideShort, width: 14, height: 14 }
+									} else {
+										Icon { icon: FaArrowUpShortWide, width: 14, height: 14 }
+									}
+								}
+								if keys().len() > 1 {
+									button {
+										class: "sort-button",
+										onclick: move |_| {
+										    keys.write().remove(index);
+										},
+										Icon { icon: FaXmark, width: 14, height: 14 }
+									}
+								}
+							}
+						}
+						if keys().len() < SortField::all().len() {
+							button {
+								class: "sort-button",
+								onclick: move |_| {
+								    keys.write()
+								        .push(SortKey {
+								            field: SortField::Title,
+								            descending: false,
+								        })
+								},
+								"+ Add sort key"
+							}
+						}
+					}
+
+					div { class: "sort-method",
+						label { class: "sort-method-option",
+							input {
+								r#type: "radio",
+								name: "sort-method",
+								checked: method() == ApplyMethod::Reorder,
+								onchange: move |_| method.set(ApplyMethod::Reorder),
+							}
+							"Move tracks ({move_requests} request(s), keeps \"date added\")"
+						}
+						label { class: "sort-method-option",
+							input {
+								r#type: "radio",
+								name: "sort-method",
+								checked: method() == ApplyMethod::Replace,
+								onchange: move |_| method.set(ApplyMethod::Replace),
+							}
+							"Rewrite playlist ({items.len().div_ceil(100).max(1)} request(s), resets \"date added\")"
+						}
+					}
+
+					p { class: "sort-summary", "{moved} of {items.len()} tracks change position" }
+
+					// Before and after, tracks that move are highlighted
+					div { class: "sort-preview",
+						div { class: "sort-preview-column",
+							h3 { "Before" }
+							for (position , item) in items.iter().enumerate() {
+								div {
+									class: if order[position] != position { "sort-preview-row moved" } else { "sort-preview-row" },
+									key: "before-{position}",
+									span { class: "sort-preview-position", "{position + 1}" }
+									span { class: "sort-preview-name", "{item.track.name()}" }
+								}
+							}
+						}
+						div { class: "sort-preview-column",
+							h3 { "After" }
+							for (position , & index) in order.iter().enumerate() {
+								div {
+									class: if index != position { "sort-preview-row moved" } else { "sort-preview-row" },
+									key: "after-{position}",
+									span { class: "sort-preview-position", "{position + 1}" }
+									span { class: "sort-preview-name",
+										"{items[index].track.name()} – {items[index].track.first_artist_name()}"
+									}
+									if index != position {
+										span { class: "sort-preview-from", "was {index + 1}" }
+									}
+								}
+							}
+						}
+					}
+				}
+
+				div { class: "modal-footer",
+					button {
+						class: "modal-button cancel-button",
+						onclick: move |_| on_close.call(()),
+						"Cancel"
+					}
+					button {
+						class: "modal-button remove-button",
+						disabled: applying() || moved == 0,
+						onclick: apply,
+						if applying() {
+							"Saving..."
+						} else {
+							"Save to Spotify"
+						}
+					}
+				}
+			}
+		}
+	}
+}
\ No newline at end of file
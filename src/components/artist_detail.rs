@@ -1,4 +1,6 @@
+use crate::components::RankTimelineChart;
 use crate::models::Artist;
+use crate::utils::TopListKind;
 use dioxus::prelude::*;
 use dioxus_free_icons::icons::fa_solid_icons::FaXmark;
 use dioxus_free_icons::icons::fa_brands_icons::FaSpotify;
@@ -7,7 +9,7 @@ use dioxus_free_icons::Icon;
 #[component]
 pub fn ArtistDetail(artist: Artist, on_close: EventHandler<()>) -> Element {
     let spotify_url = artist.external_urls.spotify.clone();
-    let artist_uri = format!("spotify:artist:{}", artist.id);
+    let artist_uri = artist.artist_id().uri();
     let genres = if let Some(g) = &artist.genres {
         if g.is_empty() {
             "No genres listed".to_string()
@@ -84,6 +86,8 @@ pub fn ArtistDetail(artist: Artist, on_close: EventHandler<()>) -> Element {
 						}
 					}
 
+					RankTimelineChart { kind: TopListKind::Artists, id: artist.id.clone() }
+
 					// Spotify button
 					a {
 						class: "detail-spotify-button",
@@ -0,0 +1,139 @@
+use crate::models::{Playlist, PlaylistTrackItem};
+use crate::utils::*;
+use crate::AppContext;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::FaXmark;
+use dioxus_free_icons::Icon;
+use std::collections::HashMap;
+
+/// Who added what to a (collaborative) playlist
+#[component]
+pub fn ContributorsModal(playlist: Playlist, items: Vec<PlaylistTrackItem>, on_close: EventHandler<()>) -> Element {
+    let context = use_context::<AppContext>();
+    let spotify_client = context.spotify_client;
+
+    let mut names = use_signal(cached_user_names);
+    let mut genres = use_signal(HashMap::<String, Vec<String>>::new);
+    let mut loading = use_signal(|| true);
+
+    {
+        let items = items.clone();
+        use_hook(move || {
+            let Some(client) = spotify_client.read().clone() else {
+                loading.set(false);
+                return;
+            };
+            spawn(async move {
+                names.set(resolve_user_names(&client, &contributor_ids(&items)).await);
+                match fetch_artist_genres(&client, items.iter().filter_map(|item| item.as_track())).await {
+                    Ok(fetched) => genres.set(fetched),
+                    Err(e) => eprintln!("Failed to load artist genres: {}", e),
+                }
+                loading.set(false);
+            });
+        });
+    }
+
+    let contributors = contributor_stats(&items, &names.read(), &genres.read());
+    let months = timeline_months(&contributors);
+    let total = items.len().max(1);
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/playlists.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		div { class: "modal-overlay", onclick: move |_| on_close.call(()),
+			div {
+				class: "modal-content shared-modal",
+				onclick: move |e| e.stop_propagation(),
+
+				div { class: "modal-header",
+					h2 { "Contributors of \"{playlist.name}\"" }
+					button {
+						class: "modal-close",
+						onclick: move |_| on_close.call(()),
+						Icon { icon: FaXmark, width: 20, height: 20 }
+					}
+				}
+
+				div { class: "modal-body",
+					if loading() {
+						p { style: "text-align: center; padding: 20px;", "Loading contributors..." }
+					} else {
+						p { class: "duplicate-preview-summary",
+							"{contributors.len()} contributor(s), {items.len()} track(s)"
+							if let (Some(first), Some(last)) = (months.first(), months.last()) {
+								", added between {first} and {last}"
+							}
+						}
+
+						for contributor in contributors.iter() {
+							{
+							    let share = contributor.track_count * 100 / total;
+							    let hours = contributor.duration_ms / 3_600_000;
+							    let minutes = contributor.duration_ms / 60_000 % 60;
+							    let per_month: HashMap<&str, usize> = contributor
+							        .timeline
+							        .iter()
+							        .map(|(month, count)| (month.as_str(), *count))
+							        .collect();
+							    let busiest = contributor.timeline.iter().map(|(_, count)| *count).max().unwrap_or(1);
+							    rsx! {
+								div { class: "contributor-card", key: "{contributor.display_name}",
+									div { class: "contributor-header",
+										span { class: "contributor-name", "{contributor.display_name}" }
+										span { class: "contributor-meta",
+											"{contributor.track_count} track(s) · {share}% · {hours} h {minutes} min"
+										}
+									}
+									div { class: "contributor-share",
+										div { class: "contributor-share-fill", style: "width: {share}%;" }
+									}
+
+									// Tracks added per month on the playlist's shared axis
+									div { class: "contributor-timeline",
+										for month in months.iter() {
+											{
+											    let count = per_month.get(month.as_str()).copied().unwrap_or(0);
+											    let height = count * 100 / busiest;
+											    rsx! {
+												div {
+													class: "contributor-timeline-bar",
+													key: "{month}",
+													title: "{month}: {count} track(s)",
+													style: "height: {height}%;",
+												}
+											}
+											}
+										}
+									}
+									if let (Some(first), Some(last)) = (contributor.first_added(), contributor.last_added()) {
+										div { class: "contributor-meta", "Active {first} – {last}" }
+									}
+
+									if !contributor.genres.is_empty() {
+										div { class: "contributor-genres",
+											for (genre , count) in contributor.genres.iter() {
+												span { class: "duplicate-reason", key: "{genre}",
+													"{genre} {count * 100 / contributor.track_count}%"
+												}
+											}
+										}
+									}
+								}
+							}
+							}
+						}
+					}
+				}
+
+				div { class: "modal-footer",
+					button {
+						class: "modal-button cancel-button",
+						onclick: move |_| on_close.call(()),
+						"Close"
+					}
+				}
+			}
+		}
+	}
+}
\ No newline at end of file
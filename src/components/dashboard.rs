@@ -1,4 +1,6 @@
-use crate::components::{UserProfile, TopTracks, TopArtists, Playlists, RecentlyPlayed};
+use crate::components::{
+    UserProfile, TopTracks, TopArtists, Playlists, RecentlyPlayed, TopPlaylistModal, RangeComparisonModal,
+};
 use crate::models::*;
 use crate::{Route, AppContext};
 use crate::utils::*;
@@ -25,11 +27,23 @@ pub fn Dashboard() -> Element {
     let mut import_name = use_signal(|| String::new());
     let mut import_description = use_signal(|| String::new());
     let mut import_author = use_signal(|| String::new());
-    let mut import_track_uris = use_signal(|| Vec::<String>::new());
+    let mut import_track_uris = use_signal(|| Vec::<PlayableUri>::new());
     let mut import_tracks = use_signal(|| Vec::<Track>::new());
     let mut importing = use_signal(|| false);
     let mut loading_tracks = use_signal(|| false);
 
+    // Library backup / restore state
+    let mut backing_up = use_signal(|| false);
+    let mut restore_backup_data = use_signal(|| None::<LibraryBackup>);
+    let mut restore_options = use_signal(RestoreOptions::default);
+    let mut restoring = use_signal(|| false);
+
+    // Save top tracks as playlist modal
+    let mut show_top_playlist_modal = use_signal(|| false);
+
+    // List the time range comparison opens on
+    let mut range_comparison = use_signal(|| None::<TopListKind>);
+
     let is_demo_mode = context.demo_mode.read().clone();
 
     // Check if we have a Spotify client with token or if we're in demo mode
@@ -122,6 +136,7 @@ pub fn Dashboard() -> Element {
                     match client_clone2.get_recently_played(50).await {
                         Ok(recent_tracks) => {
                             println!("DEBUG: Fetched {} recently played tracks", recent_tracks.len());
+                            record_plays(&recent_tracks);
                             recently_played.set(recent_tracks);
                         }
                         Err(e) => {
@@ -133,6 +148,13 @@ pub fn Dashboard() -> Element {
                 }
 
                 loading.set(false);
+
+                // Snapshot all top lists, at most once per day
+                match record_top_snapshot(&client_clone2).await {
+                    Ok(true) => println!("DEBUG: Recorded today's top list snapshot"),
+                    Ok(false) => {}
+                    Err(e) => eprintln!("Failed to record top list snapshot: {}", e),
+                }
             });
         });
     }
@@ -246,15 +268,20 @@ pub fn Dashboard() -> Element {
                                     let description = json_data["info"]["description"].as_str().unwrap_or("").to_string();
                                     let author = json_data["info"]["author"].as_str().unwrap_or("Unknown").to_string();
 
-                                    // Extract track URIs
-                                    let track_uris: Vec<String> = json_data["tracks"]
-                                        .as_array()
-                                        .map(|arr| {
-                                            arr.iter()
-                                                .filter_map(|t| t.as_str().map(String::from))
-                                                .collect()
-                                        })
-                                        .unwrap_or_default();
+                                    // Accept track and episode URIs, links or IDs, nothing else can be added
+                                    let mut track_uris = Vec::new();
+                                    let mut track_ids = Vec::new();
+                                    let entries = json_data["tracks"].as_array().into_iter().flatten().filter_map(|t| t.as_str());
+                                    for entry in entries {
+                                        if let Ok(track_id) = TrackId::parse(entry) {
+                                            track_uris.push(PlayableUri::Track(track_id.clone()));
+                                            track_ids.push(track_id);
+                                        } else if let Ok(episode_id) = EpisodeId::parse(entry) {
+                                            track_uris.push(PlayableUri::Episode(episode_id));
+                                        } else {
+                                            eprintln!("Skipping import entry {}: not a track or episode", entry);
+                                        }
+                                    }
 
                                     if track_uris.is_empty() {
                                         eprintln!("No tracks found in JSON file");
@@ -272,14 +299,7 @@ pub fn Dashboard() -> Element {
 
                                     // Fetch track details from Spotify
                                     if let Some(client) = client_opt {
-                                        // Extract track IDs from URIs (spotify:track:ID)
-                                        let track_ids: Vec<String> = track_uris.iter()
-                                            .filter_map(|uri| {
-                                                uri.split(':').nth(2).map(String::from)
-                                            })
-                                            .collect();
-
-                                        match client.get_tracks(track_ids).await {
+                                        match client.get_tracks(track_ids, None).await {
                                             Ok(tracks) => {
                                                 import_tracks.set(tracks);
                                                 loading_tracks.set(false);
@@ -322,12 +342,12 @@ pub fn Dashboard() -> Element {
 
                 spawn(async move {
                     // Create the playlist
-                    match client.create_playlist(&name, &description, false).await {
+                    match client.create_playlist(&name, &description, false, false).await {
                         Ok(playlist) => {
                             println!("✓ Created playlist: {}", playlist.name);
 
                             // Add tracks to the playlist
-                            match client.add_tracks_to_playlist(&playlist.id, track_uris).await {
+                            match client.add_tracks_to_playlist(&playlist.playlist_id(), track_uris).await {
                                 Ok(_) => {
                                     println!("✓ Successfully imported playlist!");
 
@@ -366,6 +386,144 @@ pub fn Dashboard() -> Element {
         }
     };
 
+    // Backup the whole library into a ZIP archive
+    let on_backup_library = {
+        let demo = is_demo_mode;
+        let client_opt = spotify_client_option.clone();
+        let ctx = use_context::<AppContext>();
+        move |_| {
+            if demo {
+                println!("Backup not available in demo mode");
+                return;
+            }
+            if backing_up() {
+                return;
+            }
+
+            if let Some(client) = client_opt.clone() {
+                let context = ctx.clone();
+                backing_up.set(true);
+                show_info(&context, "Creating library backup, this may take a while...".to_string());
+
+                spawn(async move {
+                    match create_backup(&client).await {
+                        Ok(backup) => {
+                            let default_filename = format!(
+                                "InfoSpot_Backup_{}.zip",
+                                chrono::Local::now().format("%Y-%m-%d")
+                            );
+                            if let Some(save_path) = save_json_file(&default_filename) {
+                                let final_path = if save_path.ends_with(".zip") {
+                                    save_path
+                                } else {
+                                    format!("{}.zip", save_path)
+                                };
+
+                                match write_backup_archive(&backup, &final_path) {
+                                    Ok(_) => {
+                                        println!("✓ Library backed up to: {}", final_path);
+                                        show_success(&context, format!(
+                                            "Backed up {} playlists, {} artists, {} tracks and {} albums",
+                                            backup.playlists.len(),
+                                            backup.followed_artists.len(),
+                                            backup.saved_tracks.len(),
+                                            backup.saved_albums.len()
+                                        ));
+                                    }
+                                    Err(e) => {
+                                        eprintln!("Failed to write backup: {}", e);
+                                        show_error(&context, format!("Failed to write backup: {}", e));
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to create backup: {}", e);
+                            show_error(&context, format!("Failed to create backup: {}", e));
+                        }
+                    }
+                    backing_up.set(false);
+                });
+            }
+        }
+    };
+
+    // Pick a backup archive and show the restore modal
+    let on_restore_backup = {
+        let demo = is_demo_mode;
+        let ctx = use_context::<AppContext>();
+        move |_| {
+            if demo {
+                println!("Restore not available in demo mode");
+                return;
+            }
+
+            let context = ctx.clone();
+            spawn(async move {
+                if let Some(file_path) = pick_file("Select an InfoSpot backup", &["zip"]) {
+                    match read_backup_archive(&file_path) {
+                        Ok(backup) => {
+                            restore_options.set(RestoreOptions::default());
+                            restore_backup_data.set(Some(backup));
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to read backup: {}", e);
+                            show_error(&context, format!("Failed to read backup: {}", e));
+                        }
+                    }
+                }
+            });
+        }
+    };
+
+    // Restore the selected parts of the backup into the logged in account
+    let confirm_restore = {
+        let client_opt = spotify_client_option.clone();
+        let ctx = use_context::<AppContext>();
+        move |_| {
+            let Some(backup) = restore_backup_data() else {
+                return;
+            };
+
+            if let Some(client) = client_opt.clone() {
+                restoring.set(true);
+                let options = restore_options();
+                let context = ctx.clone();
+
+                spawn(async move {
+                    match restore_backup(&client, &backup, options).await {
+                        Ok(report) => {
+                            for err in report.errors.iter() {
+                                eprintln!("Restore error: {}", err);
+                            }
+
+                            if report.errors.is_empty() {
+                                show_success(&context, report.summary());
+                            } else {
+                                show_error(&context, format!(
+                                    "{} ({} error(s), see console)",
+                                    report.summary(),
+                                    report.errors.len()
+                                ));
+                            }
+
+                            // Refresh playlists
+                            if let Ok(updated_playlists) = client.get_playlists(50).await {
+                                playlists.set(updated_playlists);
+                            }
+                            restore_backup_data.set(None);
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to restore backup: {}", e);
+                            show_error(&context, format!("Failed to restore backup: {}", e));
+                        }
+                    }
+                    restoring.set(false);
+                });
+            }
+        }
+    };
+
     let mut mouse_pos = use_signal(|| (-500, -500)); // -500 so the effect doesn't appear initially
     let mut scroll_pos = use_signal(|| (0, 0));
     rsx! {
@@ -409,6 +567,14 @@ pub fn Dashboard() -> Element {
 					}
 					h1 { class: "dashboard-title", "InfoSpot" }
 				}
+				if !is_demo_mode {
+					nav { class: "dashboard-nav",
+						Link { to: Route::Transfer {}, "Transfer" }
+						Link { to: Route::SmartPlaylists {}, "Smart Playlists" }
+						Link { to: Route::History {}, "History" }
+						Link { to: Route::TasteCompare {}, "Compare Tastes" }
+					}
+				}
 				div { class: "time-range-selector",
 					button {
 						class: if time_range() == "short_term" { "active" } else { "" },
@@ -444,15 +610,36 @@ pub fn Dashboard() -> Element {
 					UserProfile {
 						user,
 						on_import: on_import_playlist,
+						on_backup: on_backup_library,
+						on_restore: on_restore_backup,
 						time_range,
 					}
-					TopTracks { tracks: top_tracks }
-					TopArtists { artists: top_artists }
+					TopTracks {
+						tracks: top_tracks,
+						on_save: if !is_demo_mode { Some(EventHandler::new(move |_| show_top_playlist_modal.set(true))) } else { None },
+						on_compare: if !is_demo_mode { Some(EventHandler::new(move |_| range_comparison.set(Some(TopListKind::Tracks)))) } else { None },
+					}
+					TopArtists {
+						artists: top_artists,
+						on_compare: if !is_demo_mode { Some(EventHandler::new(move |_| range_comparison.set(Some(TopListKind::Artists)))) } else { None },
+					}
 					Playlists { playlists }
 					RecentlyPlayed { recent_tracks: recently_played }
 				}
 			}
 
+			if show_top_playlist_modal() {
+				TopPlaylistModal {
+					time_range: time_range(),
+					market: user().and_then(|u| u.country),
+					on_close: move |_| show_top_playlist_modal.set(false),
+				}
+			}
+
+			if let Some(kind) = range_comparison() {
+				RangeComparisonModal { kind, on_close: move |_| range_comparison.set(None) }
+			}
+
 			// Import Playlist Preview Modal
 			if show_import_modal() {
 				div {
@@ -567,6 +754,106 @@ pub fn Dashboard() -> Element {
 					}
 				}
 			}
+
+			// Restore Backup Modal
+			if let Some(backup) = restore_backup_data() {
+				div {
+					class: "modal-overlay",
+					onclick: move |_| restore_backup_data.set(None),
+					div {
+						class: "modal-content import-modal",
+						onclick: move |e| e.stop_propagation(),
+						div { class: "modal-header",
+							h2 { "Restore Backup" }
+							button {
+								class: "modal-close",
+								onclick: move |_| restore_backup_data.set(None),
+								Icon { icon: FaXmark, width: 20, height: 20 }
+							}
+						}
+
+						div { class: "modal-body",
+							div { class: "import-form",
+								div { class: "form-group",
+									label { "Backup of" }
+									p { class: "author-text",
+										{backup.profile.display_name.clone().unwrap_or_else(|| backup.profile.id.clone())}
+										" from "
+										{format_release_date(backup.created_at.split('T').next().unwrap_or_default())}
+									}
+								}
+
+								div { class: "form-group",
+									label { "Restore into the account you are logged in with" }
+									label { class: "restore-option",
+										input {
+											r#type: "checkbox",
+											checked: restore_options().playlists,
+											onchange: move |e| restore_options.write().playlists = e.checked(),
+										}
+										"Playlists ({backup.owned_playlists().count()} created, {backup.followed_playlists().count()} followed)"
+									}
+									label { class: "restore-option",
+										input {
+											r#type: "checkbox",
+											checked: restore_options().skip_same_name,
+											disabled: !restore_options().playlists,
+											onchange: move |e| restore_options.write().skip_same_name = e.checked(),
+										}
+										"Skip playlists named like one I already have"
+									}
+									label { class: "restore-option",
+										input {
+											r#type: "checkbox",
+											checked: restore_options().followed_artists,
+											onchange: move |e| restore_options.write().followed_artists = e.checked(),
+										}
+										"Followed artists ({backup.followed_artists.len()})"
+									}
+									label { class: "restore-option",
+										input {
+											r#type: "checkbox",
+											checked: restore_options().saved_tracks,
+											onchange: move |e| restore_options.write().saved_tracks = e.checked(),
+										}
+										"Saved tracks ({backup.saved_tracks.len()})"
+									}
+									label { class: "restore-option",
+										input {
+											r#type: "checkbox",
+											checked: restore_options().saved_albums,
+											onchange: move |e| restore_options.write().saved_albums = e.checked(),
+										}
+										"Saved albums ({backup.saved_albums.len()})"
+									}
+								}
+
+								p { class: "author-text",
+									"Only missing playlists are recreated and items already in the library are skipped."
+								}
+							}
+						}
+
+						div { class: "modal-footer",
+							button {
+								class: "modal-button cancel-button",
+								onclick: move |_| restore_backup_data.set(None),
+								"Cancel"
+							}
+							button {
+								class: "modal-button import-confirm-button",
+								onclick: confirm_restore,
+								disabled: restoring(),
+								if restoring() {
+									"Restoring..."
+								} else {
+									"Restore"
+								}
+							}
+						}
+					}
+				}
+			}
 		}
 	}
 }
\ No newline at end of file
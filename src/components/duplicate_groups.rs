@@ -0,0 +1,200 @@
+use crate::models::PlaylistTrackItem;
+use crate::utils::{duplicate_removals, format_duration, DuplicateGroup, DuplicateStrictness, KeepStrategy, MatchReason};
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+/// Unchanged tracks shown around each removal in the preview
+const PREVIEW_CONTEXT: usize = 2;
+
+#[component]
+pub fn DuplicateStrictnessSelect(
+    strictness: DuplicateStrictness,
+    on_change: EventHandler<DuplicateStrictness>,
+) -> Element {
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		div { class: "duplicate-strictness",
+			span { class: "duplicate-strictness-label", "Match:" }
+			for option in DuplicateStrictness::all() {
+				button {
+					key: "{option.key()}",
+					class: if option == strictness { "strictness-button active" } else { "strictness-button" },
+					onclick: move |_| on_change.call(option),
+					"{option.label()}"
+				}
+			}
+		}
+	}
+}
+
+/// Strategy applied to every group at once
+#[component]
+pub fn KeepStrategySelect(strategy: KeepStrategy, on_change: EventHandler<KeepStrategy>) -> Element {
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		div { class: "duplicate-strictness",
+			span { class: "duplicate-strictness-label", "Keep:" }
+			for option in KeepStrategy::all() {
+				button {
+					key: "{option.key()}",
+					class: if option == strategy { "strictness-button active" } else { "strictness-button" },
+					onclick: move |_| on_change.call(option),
+					"{option.label()}"
+				}
+			}
+		}
+	}
+}
+
+/// `on_keep_change` receives the index of the group and its new strategy
+#[component]
+pub fn DuplicateGroupList(
+    items: Vec<PlaylistTrackItem>,
+    groups: Vec<DuplicateGroup>,
+    on_keep_change: EventHandler<(usize, KeepStrategy)>,
+) -> Element {
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		div { class: "duplicates-list",
+			for (index , group) in groups.iter().enumerate() {
+				{
+				    let kept = group.kept_position(&items);
+				    let track = &items[kept].track;
+				    let count = group.positions.len();
+				    rsx! {
+					div { class: "duplicate-item", key: "{group.positions[0]}",
+						if let Some(image_url) = track.image_url() {
+							img {
+								class: "duplicate-image",
+								src: "{image_url}",
+								alt: "{track.name()}",
+							}
+						}
+						div { class: "duplicate-info",
+							div { class: "duplicate-name", "{track.name()}" }
+							div { class: "duplicate-artist", {track.artist_names()} }
+							div { class: "duplicate-count",
+								"Appears {count} times (will remove {count - 1})"
+								span { class: "duplicate-reason", "{group.reason.label()}" }
+							}
+							div { class: "duplicate-keep",
+								span { class: "duplicate-strictness-label", "Keep:" }
+								select {
+									class: "duplicate-keep-select",
+									value: "{group.keep.key()}",
+									onchange: move |e| {
+									    if let Some(keep) = KeepStrategy::from_key(&e.value()) {
+									        on_keep_change.call((index, keep));
+									    }
+									},
+									for option in KeepStrategy::all() {
+										option { key: "{option.key()}", value: "{option.key()}", "{option.label()}" }
+									}
+									for & position in group.positions.iter() {
+										option {
+											key: "position-{position}",
+											value: "{KeepStrategy::Position(position).key()}",
+											"{KeepStrategy::Position(position).label()}"
+										}
+									}
+								}
+							}
+
+							// Every copy is listed so the match and the kept copy can be checked
+							div { class: "duplicate-versions",
+								for & position in group.positions.iter() {
+									div {
+										class: if position == kept { "duplicate-version kept" } else { "duplicate-version" },
+										key: "{position}",
+										span { class: "duplicate-position", "#{position + 1}" }
+										if group.reason != MatchReason::SameTrack {
+											"{items[position].track.name()} · {items[position].track.album_name()} · {format_duration(items[position].track.duration_ms())} · "
+										}
+										"added {format_added_at(&items[position].added_at)}"
+										if items[position].as_track().is_some_and(|t| t.explicit) {
+											span { class: "duplicate-explicit", "E" }
+										}
+									}
+								}
+							}
+						}
+					}
+				}
+				}
+			}
+		}
+	}
+}
+
+
+/// The playlist as it will look after the removal, with unchanged stretches collapsed
+#[component]
+pub fn DuplicateRemovalPreview(items: Vec<PlaylistTrackItem>, groups: Vec<DuplicateGroup>) -> Element {
+    let removed: HashSet<usize> = duplicate_removals(&items, &groups)
+        .into_iter()
+        .map(|(_, position)| position)
+        .collect();
+
+    // Only positions close to a removal are shown, the rest is folded into "unchanged" rows
+    let visible = |position: usize| {
+        let start = position.saturating_sub(PREVIEW_CONTEXT);
+        (start..=position + PREVIEW_CONTEXT).any(|p| removed.contains(&p))
+    };
+
+    let mut rows = Vec::new();
+    let mut hidden = 0;
+    for position in 0..items.len() {
+        if visible(position) {
+            if hidden > 0 {
+                rows.push(PreviewRow::Unchanged(hidden));
+                hidden = 0;
+            }
+            rows.push(PreviewRow::Track(position));
+        } else {
+            hidden += 1;
+        }
+    }
+    if hidden > 0 {
+        rows.push(PreviewRow::Unchanged(hidden));
+    }
+
+    let remaining = items.len() - removed.len();
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		p { class: "duplicate-preview-summary",
+			"{items.len()} tracks → {remaining} tracks ({removed.len()} removed)"
+		}
+		div { class: "duplicate-preview",
+			for (index , row) in rows.into_iter().enumerate() {
+				match row {
+					PreviewRow::Track(position) => rsx! {
+						div {
+							class: if removed.contains(&position) { "duplicate-preview-row removed" } else { "duplicate-preview-row" },
+							key: "{index}",
+							span { class: "duplicate-position", "#{position + 1}" }
+							span { class: "duplicate-preview-name",
+								"{items[position].track.name()} – {items[position].track.artist_names()}"
+							}
+						}
+					},
+					PreviewRow::Unchanged(count) => rsx! {
+						div { class: "duplicate-preview-row unchanged", key: "{index}",
+							"… {count} unchanged track(s) …"
+						}
+					},
+				}
+			}
+		}
+	}
+}
+
+enum PreviewRow {
+    Track(usize),
+    /// Number of consecutive unchanged tracks that are not shown
+    Unchanged(usize),
+}
+
+fn format_added_at(added_at: &str) -> String {
+    added_at.split('T').next().unwrap_or(added_at).to_string()
+}
\ No newline at end of file
@@ -0,0 +1,211 @@
+use crate::models::{Playlist, PlaylistDetailsUpdate};
+use crate::utils::*;
+use crate::AppContext;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::{FaImage, FaXmark};
+use dioxus_free_icons::Icon;
+
+/// Form for changing a playlist's details and cover
+#[component]
+pub fn EditPlaylistModal(playlist: Playlist, on_saved: EventHandler<Playlist>, on_close: EventHandler<()>) -> Element {
+    let context = use_context::<AppContext>();
+    let spotify_client = context.spotify_client;
+
+    let original = PlaylistDetailsUpdate::from_playlist(&playlist);
+    let mut details = use_signal(|| original.clone());
+    // Base64 payload of the chosen cover, already validated
+    let mut cover = use_signal(|| None::<String>);
+    let mut form_error = use_signal(|| None::<String>);
+    let mut saving = use_signal(|| false);
+
+    let choose_cover = move |_| {
+        let Some(path) = pick_file("Select a JPEG cover image", &["jpg", "jpeg"]) else {
+            return;
+        };
+        let encoded = std::fs::read(&path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e))
+            .and_then(|bytes| encode_cover_image(&bytes));
+        match encoded {
+            Ok(encoded) => {
+                cover.set(Some(encoded));
+                form_error.set(None);
+            }
+            Err(e) => form_error.set(Some(e.to_string())),
+        }
+    };
+
+    let save = {
+        let context = context.clone();
+        let playlist_id = playlist.playlist_id();
+        let original = original.clone();
+        move |_| {
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+            let mut update = details();
+            update.name = update.name.trim().to_string();
+            if let Err(e) = validate_playlist_details(&update) {
+                form_error.set(Some(e.to_string()));
+                return;
+            }
+            let context = context.clone();
+            let playlist_id = playlist_id.clone();
+            let details_changed = update != original;
+            let cover = cover();
+
+            form_error.set(None);
+            saving.set(true);
+            spawn(async move {
+                let mut result = Ok(());
+                if details_changed {
+                    result = client.update_playlist_details(&playlist_id, &update).await;
+                }
+                if let (Ok(_), Some(cover)) = (&result, cover) {
+                    result = client.upload_playlist_cover(&playlist_id, cover).await;
+                }
+
+                match result {
+                    Ok(_) => {
+                        show_success(&context, format!("Saved the details of \"{}\"", update.name));
+                        if let Ok(fresh) = client.get_playlist(&playlist_id).await {
+                            on_saved.call(fresh);
+                        }
+                        on_close.call(());
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to save playlist details: {}", e);
+                        form_error.set(Some(e.to_string()));
+                    }
+                }
+                saving.set(false);
+            });
+        }
+    };
+
+    let current = details();
+    let description_length = current.description.chars().count();
+    let cover_url = match cover() {
+        Some(encoded) => Some(format!("data:image/jpeg;base64,{}", encoded)),
+        None => playlist.images.first().map(|image| image.url.clone()),
+    };
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/playlists.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/dashboard.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/tools.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/playlist_detail.css") }
+		div { class: "modal-overlay", onclick: move |_| on_close.call(()),
+			div {
+				class: "modal-content",
+				onclick: move |e| e.stop_propagation(),
+
+				div { class: "modal-header",
+					h2 { "Edit Details" }
+					button {
+						class: "modal-close",
+						onclick: move |_| on_close.call(()),
+						Icon { icon: FaXmark, width: 20, height: 20 }
+					}
+				}
+
+				div { class: "modal-body",
+					div { class: "edit-playlist-form",
+						div { class: "edit-playlist-cover",
+							if let Some(url) = cover_url {
+								img { src: "{url}", alt: "Cover" }
+							} else {
+								div { class: "edit-playlist-cover-placeholder",
+									Icon { icon: FaImage, width: 40, height: 40 }
+								}
+							}
+							button {
+								class: "button secondary",
+								disabled: saving(),
+								onclick: choose_cover,
+								"Choose JPEG..."
+							}
+							span { class: "tool-hint", "Up to {MAX_COVER_BASE64_BYTES / 1024} KB once encoded" }
+						}
+
+						div { class: "import-form",
+							div { class: "form-group",
+								label { "Name" }
+								input {
+									class: "form-input",
+									r#type: "text",
+									maxlength: "{MAX_PLAYLIST_NAME_LENGTH}",
+									value: "{current.name}",
+									oninput: move |e| details.write().name = e.value(),
+								}
+							}
+
+							div { class: "form-group",
+								label { "Description" }
+								textarea {
+									class: "form-textarea",
+									rows: "3",
+									value: "{current.description}",
+									oninput: move |e| details.write().description = e.value(),
+								}
+								span { class: if description_length > MAX_PLAYLIST_DESCRIPTION_LENGTH { "tool-hint over-limit" } else { "tool-hint" },
+									"{description_length} / {MAX_PLAYLIST_DESCRIPTION_LENGTH}"
+								}
+							}
+
+							label { class: "tool-option",
+								input {
+									r#type: "checkbox",
+									checked: current.public,
+									onchange: move |e| {
+									    let mut details = details.write();
+									    details.public = e.checked();
+									    if details.public {
+									        details.collaborative = false;
+									    }
+									},
+								}
+								"Public"
+							}
+							label { class: "tool-option",
+								input {
+									r#type: "checkbox",
+									checked: current.collaborative,
+									onchange: move |e| {
+									    let mut details = details.write();
+									    details.collaborative = e.checked();
+									    if details.collaborative {
+									        details.public = false;
+									    }
+									},
+								}
+								"Collaborative (only for private playlists)"
+							}
+						}
+					}
+
+					if let Some(message) = form_error() {
+						p { class: "edit-playlist-error", "{message}" }
+					}
+				}
+
+				div { class: "modal-footer",
+					button {
+						class: "modal-button cancel-button",
+						onclick: move |_| on_close.call(()),
+						"Cancel"
+					}
+					button {
+						class: "modal-button remove-button",
+						disabled: saving(),
+						onclick: save,
+						if saving() {
+							"Saving..."
+						} else {
+							"Save"
+						}
+					}
+				}
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,249 @@
+use crate::models::{time_range_label, Artist, Track};
+use crate::utils::*;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::FaXmark;
+use dioxus_free_icons::Icon;
+
+/// Genres listed in the genre view
+const LISTED_GENRES: usize = 15;
+
+/// Most recent snapshot days shown in the drift view
+const DRIFT_DAYS: usize = 14;
+
+/// Part of the genre breakdown on display
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GenreView {
+    Families,
+    Genres,
+    Drift,
+}
+
+impl GenreView {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Families => "Families",
+            Self::Genres => "Genres",
+            Self::Drift => "Drift",
+        }
+    }
+}
+
+/// Weighted genres and genre families of the top artists, per time range and over time
+#[component]
+pub fn GenreBreakdown(ranges: ReadSignal<Option<Vec<RangeGenres>>>, time_range: ReadSignal<String>) -> Element {
+    let history = use_hook(load_top_history);
+    let mut view = use_signal(|| GenreView::Families);
+    let mut selected_genre = use_signal(|| None::<String>);
+
+    let Some(range_list) = ranges() else {
+        return rsx! {
+			div { class: "top-genres-section",
+				h3 { class: "genres-title", "Top Genres" }
+				div { class: "genres-loading", "Loading genres..." }
+			}
+		};
+    };
+    let current_range = time_range();
+    let Some(current) = range_list.iter().find(|r| r.profile.time_range == current_range).cloned() else {
+        return rsx! {};
+    };
+    let drift = genre_drift(&history, &current_range);
+    let drift_start = drift.len().saturating_sub(DRIFT_DAYS);
+    let selected = selected_genre().and_then(|name| current.profile.genre(&name).cloned());
+
+    rsx! {
+		div { class: "top-genres-section",
+			div { class: "genres-header",
+				h3 { "Top Genres" }
+				div { class: "genre-time-range",
+					for option in [GenreView::Families, GenreView::Genres, GenreView::Drift] {
+						button {
+							key: "{option.label()}",
+							class: if option == view() { "active" } else { "" },
+							onclick: move |_| view.set(option),
+							"{option.label()}"
+						}
+					}
+				}
+			}
+
+			if view() == GenreView::Families {
+				div { class: "genre-stacks",
+					for range in range_list.iter() {
+						div {
+							key: "{range.profile.time_range}",
+							class: if range.profile.time_range == current_range { "genre-stack-row current" } else { "genre-stack-row" },
+							span { class: "genre-stack-label", "{time_range_label(&range.profile.time_range)}" }
+							FamilyStack { families: range.profile.families.clone() }
+						}
+					}
+				}
+				div { class: "genre-family-legend",
+					for family in current.profile.families.iter() {
+						span { key: "{family.family}", class: "genre-family",
+							span {
+								class: "genre-family-dot",
+								style: "background: {family_color(family.family)};",
+							}
+							"{family.family} {format_share(family.share)}"
+						}
+					}
+				}
+			} else if view() == GenreView::Genres {
+				div { class: "genres-list",
+					for (index , genre) in current.profile.genres.iter().take(LISTED_GENRES).enumerate() {
+						button {
+							class: "genre-tag clickable",
+							key: "{genre.genre}",
+							title: "{genre.family}",
+							onclick: {
+							    let name = genre.genre.clone();
+							    move |_| selected_genre.set(Some(name.clone()))
+							},
+							span { class: "genre-rank", "#{index + 1}" }
+							span { class: "genre-name", "{genre.genre}" }
+							span { class: "genre-share", "{format_share(genre.share)}" }
+						}
+					}
+				}
+			} else if drift.len() < 2 {
+				div { class: "genres-loading",
+					"Genre drift shows up once there are top list snapshots from more than one day."
+				}
+			} else {
+				div { class: "genre-stacks",
+					for point in drift[drift_start..].iter() {
+						div { key: "{point.date}", class: "genre-stack-row",
+							span { class: "genre-stack-label", "{point.date}" }
+							FamilyStack { families: point.families.clone() }
+						}
+					}
+				}
+			}
+		}
+
+		if let Some(genre) = selected {
+			GenreDetailModal {
+				genre,
+				artists: current.artists.clone(),
+				tracks: current.tracks.clone(),
+				time_range: current_range.clone(),
+				on_close: move |_| selected_genre.set(None),
+			}
+		}
+	}
+}
+
+/// One bar split into the share of every genre family
+#[component]
+fn FamilyStack(families: Vec<FamilyShare>) -> Element {
+    rsx! {
+		div { class: "genre-stack",
+			for family in families.iter() {
+				div {
+					key: "{family.family}",
+					class: "genre-stack-segment",
+					title: "{family.family} {format_share(family.share)}",
+					style: "width: {family.share * 100.0}%; background: {family_color(family.family)};",
+				}
+			}
+		}
+	}
+}
+
+/// Artists and tracks that make up a genre's share
+#[component]
+fn GenreDetailModal(
+    genre: GenreShare,
+    artists: Vec<Artist>,
+    tracks: Vec<Track>,
+    time_range: String,
+    on_close: EventHandler<()>,
+) -> Element {
+    let genre_artists: Vec<(usize, &Artist)> = artists
+        .iter()
+        .enumerate()
+        .filter(|(_, artist)| genre.artist_ids.contains(&artist.id))
+        .map(|(index, artist)| (index + 1, artist))
+        .collect();
+    let genre_tracks: Vec<(usize, &Track)> = tracks
+        .iter()
+        .enumerate()
+        .filter(|(_, track)| genre.track_ids.contains(&track.id))
+        .map(|(index, track)| (index + 1, track))
+        .collect();
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/playlists.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		div { class: "modal-overlay", onclick: move |_| on_close.call(()),
+			div {
+				class: "modal-content",
+				onclick: move |e| e.stop_propagation(),
+
+				div { class: "modal-header",
+					h2 { style: "text-transform: capitalize;", "{genre.genre}" }
+					button {
+						class: "modal-close",
+						onclick: move |_| on_close.call(()),
+						Icon { icon: FaXmark, width: 20, height: 20 }
+					}
+				}
+
+				div { class: "modal-body",
+					p { class: "duplicate-preview-summary",
+						"{format_share(genre.share)} of your {time_range_label(&time_range)} genres, part of {genre.family}"
+					}
+
+					h3 { class: "shared-title", "Artists ({genre_artists.len()})" }
+					div { class: "duplicate-preview",
+						for (rank , artist) in genre_artists.iter() {
+							div { class: "duplicate-preview-row", key: "{artist.id}",
+								span { class: "duplicate-position", "#{rank}" }
+								span { class: "duplicate-preview-name", "{artist.name}" }
+							}
+						}
+					}
+
+					h3 { class: "shared-title", "Tracks ({genre_tracks.len()})" }
+					if genre_tracks.is_empty() {
+						p { class: "duplicate-preview-summary", "None of your top tracks are by these artists." }
+					} else {
+						div { class: "duplicate-preview",
+							for (rank , track) in genre_tracks.iter() {
+								div { class: "duplicate-preview-row", key: "{track.id}",
+									span { class: "duplicate-position", "#{rank}" }
+									span { class: "duplicate-preview-name",
+										"{track.name} – "
+										{track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", ")}
+									}
+								}
+							}
+						}
+					}
+				}
+
+				div { class: "modal-footer",
+					button {
+						class: "modal-button cancel-button",
+						onclick: move |_| on_close.call(()),
+						"Close"
+					}
+				}
+			}
+		}
+	}
+}
+
+fn format_share(share: f64) -> String {
+    format!("{:.0}%", share * 100.0)
+}
+
+/// Stable color per family, spread around the hue circle
+fn family_color(family: &str) -> String {
+    if family == OTHER_FAMILY {
+        return "var(--text-secondary)".to_string();
+    }
+    let hash = family.bytes().fold(0u32, |hash, byte| hash.wrapping_mul(31).wrapping_add(byte as u32));
+    format!("hsl({}, 65%, 55%)", hash % 360)
+}
\ No newline at end of file
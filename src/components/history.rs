@@ -0,0 +1,163 @@
+use crate::utils::*;
+use crate::{Route, AppContext};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::FaArrowLeft;
+use dioxus_free_icons::Icon;
+
+#[component]
+pub fn History() -> Element {
+    let context = use_context::<AppContext>();
+    let nav = navigator();
+    let journal = context.operation_journal;
+
+    let mut undo_count = use_signal(|| 1usize);
+    let mut force = use_signal(|| false);
+    let mut running = use_signal(|| false);
+
+    if context.spotify_client.read().is_none() {
+        use_effect(move || {
+            nav.push(Route::Home {});
+        });
+
+        return rsx! {
+			div { class: "loading", "Redirecting to login..." }
+		};
+    }
+
+    let run_undo = {
+        let context = context.clone();
+        move |ids: Vec<String>| {
+            let context = context.clone();
+            running.set(true);
+            spawn(async move {
+                undo_operations(context, ids, force()).await;
+                running.set(false);
+            });
+        }
+    };
+
+    // Newest first, as that is the order operations have to be undone in
+    let entries: Vec<_> = journal().into_iter().rev().collect();
+    let pending: Vec<String> = entries.iter().filter(|e| !e.undone).map(|e| e.id.clone()).collect();
+
+    rsx! {
+		document::Link {
+			rel: "stylesheet",
+			href: asset!("assets/compiled/playlist_detail.css"),
+		}
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/tools.css") }
+		div { class: "tool-container",
+			header { class: "tool-header",
+				button {
+					class: "back-button",
+					onclick: move |_| {
+					    nav.push(Route::Dashboard {});
+					},
+					Icon { icon: FaArrowLeft, width: 42, height: 42 }
+				}
+				h1 { class: "tool-title", "Operation History" }
+			}
+
+			div { class: "tool-section",
+				h2 { class: "tool-section-title", "Undo" }
+				div { class: "tool-row",
+					label { class: "tool-option",
+						"Last"
+						input {
+							class: "tool-number",
+							r#type: "number",
+							min: "1",
+							max: "{pending.len().max(1)}",
+							value: "{undo_count()}",
+							oninput: move |e| {
+							    if let Ok(count) = e.value().parse::<usize>() {
+							        undo_count.set(count.max(1));
+							    }
+							},
+						}
+						"operation(s)"
+					}
+					button {
+						class: "button secondary",
+						disabled: running() || pending.is_empty(),
+						onclick: {
+						    let mut run_undo = run_undo.clone();
+						    let ids: Vec<String> = pending.iter().take(undo_count()).cloned().collect();
+						    move |_| run_undo(ids.clone())
+						},
+						if running() {
+							"Undoing..."
+						} else {
+							"Undo"
+						}
+					}
+					label { class: "tool-option",
+						input {
+							r#type: "checkbox",
+							checked: force(),
+							onchange: move |e| force.set(e.checked()),
+						}
+						"Restore even if the playlist was edited since"
+					}
+				}
+				p { class: "tool-hint",
+					"Removed tracks are put back at their original positions, added tracks are taken out again and sorted playlists get their previous order. Operations are undone newest first."
+				}
+			}
+
+			div { class: "tool-section",
+				h2 { class: "tool-section-title", "{entries.len()} recorded operation(s)" }
+				if entries.is_empty() {
+					p { class: "tool-hint", "Nothing recorded yet. Removing tracks from a playlist adds an entry here." }
+				}
+				div { class: "tool-list",
+					for entry in entries.iter() {
+						div { class: "tool-list-item", key: "{entry.id}",
+							span { class: "item-name",
+								"{entry.description} · {entry.playlist_name}"
+							}
+							span { class: "item-meta",
+								if !entry.previous_uris.is_empty() {
+									"{entry.previous_uris.len()} track(s) reordered · {format_timestamp(&entry.created_at)}"
+								} else if !entry.added.is_empty() && !entry.removed.is_empty() {
+									"{entry.removed.len()} track(s) replaced by {entry.added.len()} · {format_timestamp(&entry.created_at)}"
+								} else if !entry.added.is_empty() {
+									"{entry.added.len()} track(s) added · {format_timestamp(&entry.created_at)}"
+								} else {
+									"{entry.removed.len()} track(s) removed · {format_timestamp(&entry.created_at)}"
+								}
+							}
+							if entry.undone {
+								span { class: "status-badge", "Undone" }
+							} else {
+								button {
+									class: "button outline",
+									disabled: running(),
+									onclick: {
+									    let mut run_undo = run_undo.clone();
+									    let id = entry.id.clone();
+									    move |_| run_undo(vec![id.clone()])
+									},
+									"Undo"
+								}
+							}
+						}
+						if !entry.undone && !entry.removed.is_empty() {
+							p { class: "tool-hint history-tracks",
+								{
+								    let names: Vec<&str> = entry.removed.iter().take(5).map(|t| t.name.as_str()).collect();
+								    let more = entry.removed.len().saturating_sub(names.len());
+								    if more > 0 {
+								        format!("{} and {} more", names.join(", "), more)
+								    } else {
+								        names.join(", ")
+								    }
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -1,6 +1,4 @@
-use crate::api::SpotifyClient;
 use crate::{Route, AppContext};
-use crate::utils::generate_random_string;
 use crate::oauth;
 use dioxus::prelude::*;
 use std::rc::Rc;
@@ -10,12 +8,7 @@ pub fn Home() -> Element {
     let nav = navigator();
     let mut context = use_context::<AppContext>();
 
-    let client_id = std::env::var("SPOTIFY_CLIENT_ID")
-        .unwrap_or_else(|_| "".to_string());
-    let client_secret = std::env::var("SPOTIFY_CLIENT_SECRET")
-        .unwrap_or_else(|_| "".to_string());
-    let redirect_uri = std::env::var("SPOTIFY_REDIRECT_URI")
-        .unwrap_or_else(|_| "http://127.0.0.1:8888/callback".to_string());
+    let (client_id, client_secret, redirect_uri) = oauth::credentials_from_env();
 
     // Check if credentials are configured
     let credentials_missing = client_id.is_empty() || client_secret.is_empty();
@@ -54,46 +47,16 @@ pub fn Home() -> Element {
         let nav_clone = nav.clone();
 
         spawn(async move {
-            let spotify_client = SpotifyClient::new(
-                client_id_clone,
-                client_secret_clone,
-                redirect_uri_clone
-            );
+            match oauth::login(client_id_clone, client_secret_clone, redirect_uri_clone, false).await {
+                Ok(spotify_client) => {
+                    // Store client in context
+                    context.spotify_client.set(Some(Rc::new(spotify_client)));
 
-            let state = generate_random_string(16);
-            let auth_url = spotify_client.get_auth_url(&state);
-
-            // Open browser
-            if let Err(e) = open::that(&auth_url) {
-                eprintln!("Failed to open browser: {}", e);
-                error_msg.set(Some(format!("Failed to open browser: {}", e)));
-                authenticating.set(false);
-                return;
-            }
-
-            // Wait for callback
-            match oauth::start_callback_server() {
-                Ok(code) => {
-                    // Exchange code for token
-                    match spotify_client.exchange_code(&code).await {
-                        Ok(token_response) => {
-                            // Store token
-                            spotify_client.set_token(token_response.access_token).await;
-
-                            // Store client in context
-                            context.spotify_client.set(Some(Rc::new(spotify_client)));
-
-                            // Navigate to dashboard
-                            nav_clone.push(Route::Dashboard {});
-                        }
-                        Err(e) => {
-                            error_msg.set(Some(format!("Authentication failed: {}", e)));
-                            authenticating.set(false);
-                        }
-                    }
+                    // Navigate to dashboard
+                    nav_clone.push(Route::Dashboard {});
                 }
                 Err(e) => {
-                    error_msg.set(Some(format!("Callback error: {}", e)));
+                    error_msg.set(Some(e));
                     authenticating.set(false);
                 }
             }
@@ -0,0 +1,290 @@
+use crate::components::{ArtistDetail, TrackDetail};
+use crate::models::{Artist, SpotifyUri, Track, TrackId};
+use crate::utils::*;
+use crate::{AppContext, Route};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::{FaLink, FaXmark};
+use dioxus_free_icons::Icon;
+
+/// Layout of the logged in pages, with a field to paste Spotify links into from anywhere
+#[component]
+pub fn LinkPasteLayout() -> Element {
+    let context = use_context::<AppContext>();
+    let spotify_client = context.spotify_client;
+    let nav = navigator();
+
+    let mut text = use_signal(String::new);
+    let mut opening = use_signal(|| false);
+    let mut selected_track = use_signal(|| None::<Track>);
+    let mut selected_artist = use_signal(|| None::<Artist>);
+    // Tracks of several pasted links, offered as a new playlist
+    let mut pasted_tracks = use_signal(|| None::<Vec<TrackId>>);
+
+    let mut open = {
+        let context = context.clone();
+        move || {
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+            let links = SpotifyUri::parse_all(&text());
+            if links.is_empty() {
+                return;
+            }
+            text.set(String::new());
+
+            if links.len() > 1 {
+                let track_ids: Vec<TrackId> = links
+                    .iter()
+                    .filter_map(|link| match link {
+                        Ok(SpotifyUri::Track(id)) => Some(id.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                // Links that don't parse are reported with their reason, not counted as other kinds
+                let invalid: Vec<String> =
+                    links.iter().filter_map(|link| link.as_ref().err()).map(|e| e.to_string()).collect();
+                let not_tracks = links.len() - track_ids.len() - invalid.len();
+                if !invalid.is_empty() {
+                    show_error(&context, format!("Skipped {} invalid link(s): {}", invalid.len(), invalid.join("; ")));
+                }
+                if not_tracks > 0 {
+                    show_info(&context, format!("Skipped {} link(s) that are not tracks", not_tracks));
+                }
+                if track_ids.is_empty() {
+                    if invalid.is_empty() {
+                        show_error(&context, "None of the pasted links are tracks".to_string());
+                    }
+                    return;
+                }
+                pasted_tracks.set(Some(track_ids));
+                return;
+            }
+
+            let link = match links.into_iter().next() {
+                Some(Ok(link)) => link,
+                Some(Err(e)) => {
+                    show_error(&context, e.to_string());
+                    return;
+                }
+                None => return,
+            };
+            let context = context.clone();
+
+            match link {
+                SpotifyUri::Playlist(id) => {
+                    nav.push(Route::PlaylistDetail { id: id.to_string() });
+                }
+                SpotifyUri::Track(id) => {
+                    opening.set(true);
+                    spawn(async move {
+                        match client.get_tracks(vec![id], None).await {
+                            Ok(tracks) if !tracks.is_empty() => selected_track.set(tracks.into_iter().next()),
+                            Ok(_) => show_error(&context, "That track doesn't exist".to_string()),
+                            Err(e) => show_error(&context, format!("Failed to load track: {}", e)),
+                        }
+                        opening.set(false);
+                    });
+                }
+                SpotifyUri::Artist(id) => {
+                    opening.set(true);
+                    spawn(async move {
+                        match client.get_artists(vec![id]).await {
+                            Ok(artists) if !artists.is_empty() => selected_artist.set(artists.into_iter().next()),
+                            Ok(_) => show_error(&context, "That artist doesn't exist".to_string()),
+                            Err(e) => show_error(&context, format!("Failed to load artist: {}", e)),
+                        }
+                        opening.set(false);
+                    });
+                }
+                other => show_info(
+                    &context,
+                    format!("Can't open {}, only playlist, track and artist links are supported", other.uri()),
+                ),
+            }
+        }
+    };
+
+    // Demo mode has no client to look anything up with
+    if spotify_client.read().is_none() {
+        return rsx! {
+			Outlet::<Route> {}
+		};
+    }
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/link_paste.css") }
+		Outlet::<Route> {}
+
+		div { class: "link-paste-bar",
+			Icon { icon: FaLink, width: 16, height: 16 }
+			// A textarea keeps the line breaks between several pasted links
+			textarea {
+				class: "link-paste-input",
+				rows: 1,
+				placeholder: "Paste a Spotify link or URI...",
+				value: "{text}",
+				oninput: move |e| text.set(e.value()),
+				onkeydown: {
+				    let mut open = open.clone();
+				    move |e: KeyboardEvent| {
+				        if e.key() == Key::Enter && !e.modifiers().shift() {
+				            e.prevent_default();
+				            open();
+				        }
+				    }
+				},
+			}
+			button {
+				class: "link-paste-button",
+				disabled: opening() || text().trim().is_empty(),
+				onclick: move |_| open(),
+				if opening() {
+					"Opening..."
+				} else {
+					"Open"
+				}
+			}
+		}
+
+		if let Some(track_ids) = pasted_tracks() {
+			PlaylistFromLinksModal { track_ids, on_close: move |_| pasted_tracks.set(None) }
+		}
+		if let Some(track) = selected_track() {
+			TrackDetail { track, on_close: move |_| selected_track.set(None) }
+		}
+		if let Some(artist) = selected_artist() {
+			ArtistDetail { artist, on_close: move |_| selected_artist.set(None) }
+		}
+	}
+}
+
+/// Turn the tracks of several pasted links into a new playlist
+#[component]
+fn PlaylistFromLinksModal(track_ids: Vec<TrackId>, on_close: EventHandler<()>) -> Element {
+    let context = use_context::<AppContext>();
+    let spotify_client = context.spotify_client;
+    let nav = navigator();
+
+    let mut tracks = use_signal(Vec::<Track>::new);
+    let mut name = use_signal(|| format!("Pasted Tracks · {}", chrono::Local::now().format("%Y-%m-%d")));
+    let mut loading = use_signal(|| true);
+    let mut creating = use_signal(|| false);
+
+    use_hook({
+        let context = context.clone();
+        move || {
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+
+            spawn(async move {
+                match client.get_tracks(track_ids, None).await {
+                    Ok(fetched) => tracks.set(fetched),
+                    Err(e) => {
+                        eprintln!("Failed to load pasted tracks: {}", e);
+                        show_error(&context, format!("Failed to load pasted tracks: {}", e));
+                    }
+                }
+                loading.set(false);
+            });
+        }
+    });
+
+    let create = {
+        let context = context.clone();
+        move |_| {
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+            let name = name().trim().to_string();
+            let tracks = tracks();
+            let context = context.clone();
+
+            creating.set(true);
+            spawn(async move {
+                match save_tracks_as_playlist(&client, &name, "Created from pasted links", &tracks).await {
+                    Ok(playlist) => {
+                        show_success(&context, format!("Created \"{}\" with {} track(s)", name, tracks.len()));
+                        on_close.call(());
+                        nav.push(Route::PlaylistDetail { id: playlist.id });
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to create playlist from links: {}", e);
+                        show_error(&context, format!("Failed to create playlist: {}", e));
+                    }
+                }
+                creating.set(false);
+            });
+        }
+    };
+
+    let track_list = tracks();
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/playlists.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/dashboard.css") }
+		div { class: "modal-overlay", onclick: move |_| on_close.call(()),
+			div {
+				class: "modal-content",
+				onclick: move |e| e.stop_propagation(),
+
+				div { class: "modal-header",
+					h2 { "New Playlist from Links" }
+					button {
+						class: "modal-close",
+						onclick: move |_| on_close.call(()),
+						Icon { icon: FaXmark, width: 20, height: 20 }
+					}
+				}
+
+				div { class: "modal-body",
+					div { class: "form-group", style: "margin-bottom: 15px;",
+						label { "Name" }
+						input {
+							class: "form-input",
+							r#type: "text",
+							value: "{name}",
+							oninput: move |e| name.set(e.value()),
+						}
+					}
+
+					if loading() {
+						p { style: "text-align: center; padding: 20px;", "Loading tracks..." }
+					} else {
+						p { class: "duplicate-preview-summary", "{track_list.len()} track(s)" }
+						div { class: "duplicate-preview",
+							for (position , track) in track_list.iter().enumerate() {
+								div { class: "duplicate-preview-row", key: "{position}",
+									span { class: "duplicate-position", "#{position + 1}" }
+									span { class: "duplicate-preview-name",
+										"{track.name} – "
+										{track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", ")}
+									}
+								}
+							}
+						}
+					}
+				}
+
+				div { class: "modal-footer",
+					button {
+						class: "modal-button cancel-button",
+						onclick: move |_| on_close.call(()),
+						"Cancel"
+					}
+					button {
+						class: "modal-button remove-button",
+						disabled: loading() || creating() || track_list.is_empty() || name().trim().is_empty(),
+						onclick: create,
+						if creating() {
+							"Creating..."
+						} else {
+							"Create Playlist"
+						}
+					}
+				}
+			}
+		}
+	}
+}
\ No newline at end of file
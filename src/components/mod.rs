@@ -10,16 +10,56 @@ pub mod dashboard;
 pub mod playlist_detail;
 pub mod track_detail;
 pub mod artist_detail;
+pub mod transfer;
+pub mod duplicate_groups;
+pub mod shared_tracks;
+pub mod history;
+pub mod apply_sort;
+pub mod smart_playlists;
+pub mod set_operations;
+pub mod split_playlist;
+pub mod top_playlist;
+pub mod edit_playlist;
+pub mod playlist_changelog;
+pub mod contributors;
+pub mod playlist_stats;
+pub mod repair_tracks;
+pub mod link_paste;
+pub mod top_comparison;
+pub mod rank_timeline;
+pub mod genres;
+pub mod taste_metrics;
+pub mod taste_compare;
 
 pub use user_profile::UserProfile;
 pub use top_tracks::TopTracks;
 pub use top_artists::TopArtists;
 pub use playlists::Playlists;
 pub use recently_played::RecentlyPlayed;
-pub use toast::{Toast, ToastType, ToastContainer};
+pub use toast::{Toast, ToastAction, ToastType, ToastContainer};
 pub use callback::Callback;
 pub use home::Home;
 pub use dashboard::Dashboard;
 pub use playlist_detail::PlaylistDetail;
 pub use track_detail::TrackDetail;
-pub use artist_detail::ArtistDetail;
\ No newline at end of file
+pub use artist_detail::ArtistDetail;
+pub use transfer::Transfer;
+pub use duplicate_groups::{DuplicateGroupList, DuplicateRemovalPreview, DuplicateStrictnessSelect, KeepStrategySelect};
+pub use shared_tracks::SharedTracksModal;
+pub use history::History;
+pub use apply_sort::ApplySortModal;
+pub use smart_playlists::SmartPlaylists;
+pub use set_operations::SetOperationsModal;
+pub use split_playlist::SplitPlaylistModal;
+pub use top_playlist::TopPlaylistModal;
+pub use edit_playlist::EditPlaylistModal;
+pub use playlist_changelog::PlaylistChangelogModal;
+pub use contributors::ContributorsModal;
+pub use playlist_stats::PlaylistStatsModal;
+pub use repair_tracks::RepairTracksModal;
+pub use link_paste::LinkPasteLayout;
+pub use top_comparison::RangeComparisonModal;
+pub use rank_timeline::RankTimelineChart;
+pub use genres::GenreBreakdown;
+pub use taste_metrics::TasteMetricsPanel;
+pub use taste_compare::TasteCompare;
\ No newline at end of file
@@ -0,0 +1,111 @@
+use crate::models::{Playlist, TrackChangeKind};
+use crate::utils::*;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::FaXmark;
+use dioxus_free_icons::Icon;
+
+/// Tracks added, removed and moved between the recorded snapshots of a playlist
+#[component]
+pub fn PlaylistChangelogModal(playlist: Playlist, on_close: EventHandler<()>) -> Element {
+    let history = use_hook(|| load_playlist_history(&playlist.id));
+    let changelog = playlist_changelog(&history);
+    // Names resolved by the contributors panel, IDs are shown for everyone else
+    let names = use_hook(cached_user_names);
+    let user_name = |user: &String| names.get(user).cloned().unwrap_or_else(|| user.clone());
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/playlists.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		div { class: "modal-overlay", onclick: move |_| on_close.call(()),
+			div {
+				class: "modal-content",
+				onclick: move |e| e.stop_propagation(),
+
+				div { class: "modal-header",
+					h2 { "Changes to \"{playlist.name}\"" }
+					button {
+						class: "modal-close",
+						onclick: move |_| on_close.call(()),
+						Icon { icon: FaXmark, width: 20, height: 20 }
+					}
+				}
+
+				div { class: "modal-body",
+					if let Some(first) = history.first() {
+						p { class: "duplicate-preview-summary",
+							"{history.len()} version(s) recorded since {format_timestamp(&first.captured_at)}. "
+							"A new version is stored whenever the playlist is opened after it changed."
+						}
+					}
+
+					if changelog.is_empty() {
+						p { style: "text-align: center; padding: 20px;",
+							"No changes seen yet. Open this playlist again after it was edited to see what changed."
+						}
+					}
+
+					for set in changelog.iter() {
+						{
+						    let (added, removed, moved) = set.counts();
+						    rsx! {
+							div { class: "changelog-set", key: "{set.snapshot_id}",
+								h3 { class: "changelog-title",
+									"Seen {format_timestamp(&set.captured_at)}"
+									span { class: "changelog-counts", "+{added} −{removed} ↕{moved}" }
+								}
+								div { class: "duplicate-preview",
+									for (index , change) in set.changes.iter().enumerate() {
+										{
+										    let (class, symbol, detail) = match change.kind {
+										        TrackChangeKind::Added => (
+										            "duplicate-preview-row changelog-added",
+										            "+",
+										            format!(
+										                "at #{} · added {}{}",
+										                change.position + 1,
+										                format_timestamp(&change.track.added_at),
+										                change.track.added_by.as_ref().map(|user| format!(" by {}", user_name(user))).unwrap_or_default()
+										            ),
+										        ),
+										        TrackChangeKind::Removed => (
+										            "duplicate-preview-row changelog-removed",
+										            "−",
+										            format!(
+										                "was #{}{}",
+										                change.position + 1,
+										                change.track.added_by.as_ref().map(|user| format!(" · added by {}", user_name(user))).unwrap_or_default()
+										            ),
+										        ),
+										        TrackChangeKind::Moved { from } => (
+										            "duplicate-preview-row changelog-moved",
+										            "↕",
+										            format!("#{} → #{}", from + 1, change.position + 1),
+										        ),
+										    };
+										    rsx! {
+											div { class, key: "{index}",
+												span { class: "changelog-symbol", "{symbol}" }
+												span { class: "duplicate-preview-name", "{change.track.name} – {change.track.artists}" }
+												span { class: "changelog-detail", "{detail}" }
+											}
+										}
+										}
+									}
+								}
+							}
+						}
+						}
+					}
+				}
+
+				div { class: "modal-footer",
+					button {
+						class: "modal-button cancel-button",
+						onclick: move |_| on_close.call(()),
+						"Close"
+					}
+				}
+			}
+		}
+	}
+}
\ No newline at end of file
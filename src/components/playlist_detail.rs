@@ -1,13 +1,13 @@
-use crate::components::TrackDetail;
+use crate::components::{TrackDetail, ApplySortModal, SplitPlaylistModal, EditPlaylistModal, PlaylistChangelogModal, ContributorsModal, PlaylistStatsModal, RepairTracksModal, DuplicateGroupList, DuplicateRemovalPreview, DuplicateStrictnessSelect, KeepStrategySelect};
 use crate::models::*;
 use crate::{Route, AppContext};
 use crate::utils::*;
 use dioxus::prelude::*;
-use dioxus_free_icons::icons::fa_solid_icons::{FaFileArrowDown, FaArrowLeft, FaXmark, FaMagnifyingGlass};
+use dioxus_free_icons::icons::fa_solid_icons::{FaFileArrowDown, FaArrowLeft, FaXmark, FaMagnifyingGlass, FaArrowDownWideShort, FaScissors, FaPen, FaClockRotateLeft, FaUsers, FaChartSimple, FaWrench};
 use dioxus_free_icons::Icon;
 
 #[component]
-pub fn PlaylistDetail(id: String) -> Element {
+pub fn PlaylistDetail(id: ReadSignal<String>) -> Element {
     let context = use_context::<AppContext>();
     let nav = navigator();
 
@@ -17,9 +17,27 @@ pub fn PlaylistDetail(id: String) -> Element {
     let mut error = use_signal(|| None::<String>);
     let mut sort_order = use_signal(|| "default".to_string());
     let mut show_duplicates_modal = use_signal(|| false);
-    let mut duplicates = use_signal(|| Vec::<(Track, Vec<usize>)>::new());
+    let mut duplicates = use_signal(|| Vec::<DuplicateGroup>::new());
+    let mut duplicate_strictness = use_signal(|| DuplicateStrictness::Recording);
+    let mut keep_strategy = use_signal(|| KeepStrategy::First);
+    let mut show_removal_preview = use_signal(|| false);
     let mut removing_duplicates = use_signal(|| false);
     let mut selected_track = use_signal(|| None::<Track>);
+    let mut show_sort_modal = use_signal(|| false);
+    let mut show_split_modal = use_signal(|| false);
+    let mut show_edit_modal = use_signal(|| false);
+    let mut show_changelog_modal = use_signal(|| false);
+    let mut show_contributors_modal = use_signal(|| false);
+    let mut show_stats_modal = use_signal(|| false);
+    let mut show_repair_modal = use_signal(|| false);
+    // The user's country, so Spotify reports which tracks can't be played there
+    let mut market = use_signal(|| None::<String>);
+    let mut search_query = use_signal(|| String::new());
+    // Playlist the shown data belongs to
+    let mut loaded_id = use_signal(|| None::<PlaylistId>);
+    // The route takes any string, e.g. from a typed or pasted URL. Pasting another playlist link
+    // navigates here again without remounting, so the ID is followed reactively.
+    let parsed_id = use_memo(move || PlaylistId::parse(&id()));
 
     // Check if we have a Spotify client with token
     let spotify_client_option = context.spotify_client.read().clone();
@@ -39,26 +57,76 @@ pub fn PlaylistDetail(id: String) -> Element {
     // Fetch playlist info and tracks
     {
         let client_clone = client.clone();
-        let playlist_id = id.clone();
+        let operation_journal = context.operation_journal;
         use_effect(move || {
+            // Reload whenever an operation is recorded or undone, the playlist may have changed
+            let _ = operation_journal.read();
+            let Ok(playlist_id_clone) = parsed_id() else {
+                return;
+            };
             let client_clone2 = client_clone.clone();
-            let playlist_id_clone = playlist_id.clone();
+
+            // Another playlist was opened, drop everything shown for the previous one
+            if loaded_id.peek().as_ref() != Some(&playlist_id_clone) {
+                loaded_id.set(Some(playlist_id_clone.clone()));
+                tracks.set(Vec::new());
+                playlist_info.set(None);
+                loading.set(true);
+                error.set(None);
+                duplicates.set(Vec::new());
+                selected_track.set(None);
+                search_query.set(String::new());
+                for mut modal in [
+                    show_duplicates_modal,
+                    show_removal_preview,
+                    show_sort_modal,
+                    show_split_modal,
+                    show_edit_modal,
+                    show_changelog_modal,
+                    show_contributors_modal,
+                    show_stats_modal,
+                    show_repair_modal,
+                ] {
+                    modal.set(false);
+                }
+            }
+            // Responses for a playlist that was navigated away from in the meantime are dropped
+            let is_current = move |playlist_id: &PlaylistId| loaded_id.peek().as_ref() == Some(playlist_id);
 
             spawn(async move {
                 // Fetch playlist details
-                match client_clone2.get_playlist(&playlist_id_clone).await {
+                let fetched_playlist = match client_clone2.get_playlist(&playlist_id_clone).await {
+                    Ok(_) if !is_current(&playlist_id_clone) => return,
                     Ok(playlist) => {
-                        playlist_info.set(Some(playlist));
+                        playlist_info.set(Some(playlist.clone()));
+                        Some(playlist)
                     }
                     Err(e) => {
                         eprintln!("Failed to fetch playlist info: {}", e);
+                        None
                     }
-                }
+                };
+
+                let country = match market.peek().clone() {
+                    Some(country) => Some(country),
+                    None => client_clone2.get_current_user().await.ok().and_then(|user| user.country),
+                };
+                market.set(country.clone());
 
                 // Fetch playlist tracks
-                match client_clone2.get_playlist_tracks(&playlist_id_clone).await {
+                let fetched_tracks = client_clone2.get_playlist_tracks(&playlist_id_clone, country.as_deref()).await;
+                if !is_current(&playlist_id_clone) {
+                    return;
+                }
+                match fetched_tracks {
                     Ok(playlist_tracks) => {
                         println!("DEBUG: Fetched {} tracks", playlist_tracks.len());
+                        // Keep a copy of the track list whenever the snapshot changed
+                        if let Some(playlist) = &fetched_playlist {
+                            if record_playlist_snapshot(playlist, &playlist_tracks) {
+                                println!("DEBUG: Recorded snapshot {:?}", playlist.snapshot_id);
+                            }
+                        }
                         tracks.set(playlist_tracks);
                     }
                     Err(e) => {
@@ -89,8 +157,9 @@ pub fn PlaylistDetail(id: String) -> Element {
                             "author": pl.owner.display_name.unwrap_or_else(|| "Unknown".to_string()),
                             "description": pl.description.unwrap_or_else(|| "".to_string()),
                         },
-                        "tracks": tracks_list.iter().map(|item| {
-                            format!("spotify:track:{}", item.track.id)
+                        // Local files and unavailable tracks can't be imported again
+                        "tracks": tracks_list.iter().filter_map(|item| {
+                            item.track.addable_uri().map(|uri| uri.uri())
                         }).collect::<Vec<_>>()
                     });
 
@@ -141,79 +210,111 @@ pub fn PlaylistDetail(id: String) -> Element {
         let ctx = use_context::<AppContext>();
         move |_| {
             let tracks_list = tracks();
-            let mut track_map: std::collections::HashMap<String, Vec<usize>> = std::collections::HashMap::new();
             let context = ctx.clone();
 
-            // Group tracks by ID
-            for (index, item) in tracks_list.iter().enumerate() {
-                track_map.entry(item.track.id.clone())
-                    .or_insert_with(Vec::new)
-                    .push(index);
-            }
+            let mut duplicate_groups = find_duplicate_groups(&tracks_list, duplicate_strictness());
+            set_keep_strategy(&mut duplicate_groups, keep_strategy());
 
-            // Find tracks that appear more than once
-            let duplicate_tracks: Vec<(Track, Vec<usize>)> = track_map
-                .into_iter()
-                .filter(|(_, indices)| indices.len() > 1)
-                .map(|(_track_id, indices)| {
-                    // Get the track from the first occurrence
-                    let track = tracks_list[indices[0]].track.clone();
-                    (track, indices)
-                })
-                .collect();
-
-            if duplicate_tracks.is_empty() {
+            if duplicate_groups.is_empty() {
                 show_info(&context, "No duplicate tracks found in this playlist".to_string());
             } else {
-                duplicates.set(duplicate_tracks);
+                duplicates.set(duplicate_groups);
+                show_removal_preview.set(false);
                 show_duplicates_modal.set(true);
             }
         }
     };
 
+    // Re-run detection when the strictness is changed in the modal
+    let change_strictness = move |strictness: DuplicateStrictness| {
+        duplicate_strictness.set(strictness);
+        let mut duplicate_groups = find_duplicate_groups(&tracks(), strictness);
+        set_keep_strategy(&mut duplicate_groups, keep_strategy());
+        duplicates.set(duplicate_groups);
+    };
+
+    // Apply a keep strategy to every group, or to a single one from its selector
+    let change_keep_strategy = move |keep: KeepStrategy| {
+        keep_strategy.set(keep);
+        set_keep_strategy(&mut duplicates.write(), keep);
+    };
+
+    let change_group_keep = move |(index, keep): (usize, KeepStrategy)| {
+        if let Some(group) = duplicates.write().get_mut(index) {
+            group.keep = keep;
+        }
+    };
+
     // Remove duplicates from playlist
     let remove_duplicates_handler = {
         let ctx = use_context::<AppContext>();
         move |_| {
             let client = client.clone();
-            let playlist_id = id.clone();
-            let duplicates_list = duplicates();
+            let Ok(playlist_id) = parsed_id() else {
+                return;
+            };
+            let tracks_list = tracks();
+            let duplicate_groups = duplicates();
+            let playlist = playlist_info();
+            let snapshot_id = playlist.as_ref().and_then(|p| p.snapshot_id.clone());
             let context = ctx.clone();
 
             removing_duplicates.set(true);
 
             spawn(async move {
-                let mut tracks_to_remove = Vec::new();
-                let total_duplicates = duplicates_list.iter()
-                    .map(|(_, indices)| indices.len() - 1)
-                    .sum::<usize>();
-
-                // For each duplicate track, keep the first occurrence and remove the rest
-                for (track, indices) in duplicates_list.iter() {
-                    println!("DEBUG: Track '{}' appears at positions: {:?}", track.name, indices);
-                    // Skip the first index (keep one copy), add the rest for removal with their positions
-                    for &index in indices.iter().skip(1) {
-                        println!("DEBUG: Will remove '{}' at position {}", track.name, index);
-                        tracks_to_remove.push((format!("spotify:track:{}", track.id), index));
+                // Positions refer to the loaded snapshot, refuse to remove anything if it changed since
+                if let Some(snapshot_id) = &snapshot_id {
+                    if let Err(e) = client.check_snapshot(&playlist_id, snapshot_id).await {
+                        eprintln!("Playlist changed before removing duplicates: {}", e);
+                        show_error(&context, format!("{}", e));
+                        removing_duplicates.set(false);
+                        return;
                     }
                 }
 
+                // For each group keep the chosen copy and remove the rest with their positions
+                let mut tracks_to_remove = duplicate_removals(&tracks_list, &duplicate_groups);
+                let total_duplicates = tracks_to_remove.len();
+
                 // Sort by position in descending order to avoid index shifting issues
                 tracks_to_remove.sort_by(|a, b| b.1.cmp(&a.1));
 
                 println!("DEBUG: Total tracks to remove: {}", tracks_to_remove.len());
 
                 if !tracks_to_remove.is_empty() {
-                    match client.remove_tracks_from_playlist(&playlist_id, tracks_to_remove).await {
-                        Ok(_) => {
+                    match client
+                        .remove_tracks_from_playlist(&playlist_id, tracks_to_remove.clone(), snapshot_id.as_deref())
+                        .await
+                    {
+                        Ok(new_snapshot_id) => {
                             println!("✓ Successfully removed duplicates");
+                            if let Some(playlist) = playlist_info.write().as_mut() {
+                                playlist.snapshot_id = Some(new_snapshot_id.clone());
+                            }
+                            let operation_ids = match &playlist {
+                                Some(playlist) => record_operations(
+                                    &context,
+                                    vec![removal_entry(
+                                        "Removed duplicates",
+                                        playlist,
+                                        &tracks_list,
+                                        &tracks_to_remove,
+                                        Some(new_snapshot_id),
+                                    )],
+                                ),
+                                None => Vec::new(),
+                            };
                             // Refresh the track list
-                            match client.get_playlist_tracks(&playlist_id).await {
+                            match client.get_playlist_tracks(&playlist_id, market().as_deref()).await {
                                 Ok(updated_tracks) => {
                                     tracks.set(updated_tracks);
                                     show_duplicates_modal.set(false);
                                     duplicates.set(Vec::new());
-                                    show_success(&context, format!("Successfully removed {} duplicate track(s)", total_duplicates));
+                                    show_success_with_undo(
+                                        &context,
+                                        format!("Successfully removed {} duplicate track(s)", total_duplicates),
+                                        operation_ids,
+                                    );
                                 }
                                 Err(e) => {
                                     eprintln!("Failed to refresh tracks: {}", e);
@@ -238,16 +339,16 @@ pub fn PlaylistDetail(id: String) -> Element {
     let sorted_tracks = {
         let mut tracks_vec = tracks();
         match sort_order().as_str() {
-            "name_asc" => tracks_vec.sort_by(|a, b| a.track.name.to_lowercase().cmp(&b.track.name.to_lowercase())),
-            "name_desc" => tracks_vec.sort_by(|a, b| b.track.name.to_lowercase().cmp(&a.track.name.to_lowercase())),
+            "name_asc" => tracks_vec.sort_by(|a, b| a.track.name().to_lowercase().cmp(&b.track.name().to_lowercase())),
+            "name_desc" => tracks_vec.sort_by(|a, b| b.track.name().to_lowercase().cmp(&a.track.name().to_lowercase())),
             "artist_asc" => tracks_vec.sort_by(|a, b| {
-                let a_artist = a.track.artists.first().map(|ar| ar.name.to_lowercase()).unwrap_or_default();
-                let b_artist = b.track.artists.first().map(|ar| ar.name.to_lowercase()).unwrap_or_default();
+                let a_artist = a.track.first_artist_name().to_lowercase();
+                let b_artist = b.track.first_artist_name().to_lowercase();
                 a_artist.cmp(&b_artist)
             }),
             "artist_desc" => tracks_vec.sort_by(|a, b| {
-                let a_artist = a.track.artists.first().map(|ar| ar.name.to_lowercase()).unwrap_or_default();
-                let b_artist = b.track.artists.first().map(|ar| ar.name.to_lowercase()).unwrap_or_default();
+                let a_artist = a.track.first_artist_name().to_lowercase();
+                let b_artist = b.track.first_artist_name().to_lowercase();
                 b_artist.cmp(&a_artist)
             }),
             "date_added_desc" => tracks_vec.sort_by(|a, b| b.added_at.cmp(&a.added_at)),
@@ -264,9 +365,6 @@ pub fn PlaylistDetail(id: String) -> Element {
         .map(|img| img.url.clone())
         .unwrap_or_default();
 
-    // Search
-    let mut search_query = use_signal(|| String::new());
-
     // Filter and sort tracks based on search query and sort order
 let sorted_tracks = {
     let mut tracks_vec = tracks();
@@ -275,13 +373,9 @@ let sorted_tracks = {
     // Filter by search query
     if !query.is_empty() {
         tracks_vec.retain(|item| {
-            let track_name = item.track.name.to_lowercase();
-            let artist_names = item.track.artists
-                .iter()
-                .map(|a| a.name.to_lowercase())
-                .collect::<Vec<_>>()
-                .join(" ");
-            let album_name = item.track.album.name.to_lowercase();
+            let track_name = item.track.name().to_lowercase();
+            let artist_names = item.track.artist_names().to_lowercase();
+            let album_name = item.track.album_name().to_lowercase();
 
             track_name.contains(&query) ||
             artist_names.contains(&query) ||
@@ -291,16 +385,16 @@ let sorted_tracks = {
 
     // Sort based on selected order
     match sort_order().as_str() {
-        "name_asc" => tracks_vec.sort_by(|a, b| a.track.name.to_lowercase().cmp(&b.track.name.to_lowercase())),
-        "name_desc" => tracks_vec.sort_by(|a, b| b.track.name.to_lowercase().cmp(&a.track.name.to_lowercase())),
+        "name_asc" => tracks_vec.sort_by(|a, b| a.track.name().to_lowercase().cmp(&b.track.name().to_lowercase())),
+        "name_desc" => tracks_vec.sort_by(|a, b| b.track.name().to_lowercase().cmp(&a.track.name().to_lowercase())),
         "artist_asc" => tracks_vec.sort_by(|a, b| {
-            let a_artist = a.track.artists.first().map(|ar| ar.name.to_lowercase()).unwrap_or_default();
-            let b_artist = b.track.artists.first().map(|ar| ar.name.to_lowercase()).unwrap_or_default();
+            let a_artist = a.track.first_artist_name().to_lowercase();
+            let b_artist = b.track.first_artist_name().to_lowercase();
             a_artist.cmp(&b_artist)
         }),
         "artist_desc" => tracks_vec.sort_by(|a, b| {
-            let a_artist = a.track.artists.first().map(|ar| ar.name.to_lowercase()).unwrap_or_default();
-            let b_artist = b.track.artists.first().map(|ar| ar.name.to_lowercase()).unwrap_or_default();
+            let a_artist = a.track.first_artist_name().to_lowercase();
+            let b_artist = b.track.first_artist_name().to_lowercase();
             b_artist.cmp(&a_artist)
         }),
         "date_added_desc" => tracks_vec.sort_by(|a, b| b.added_at.cmp(&a.added_at)),
@@ -310,6 +404,17 @@ let sorted_tracks = {
     tracks_vec
 };
 
+    let unplayable_count = unplayable_tracks(&tracks()).len();
+
+    // Rendered only after every hook ran, so the hook order is the same for valid and invalid IDs
+    if let Err(e) = parsed_id() {
+        return rsx! {
+			div { class: "error-message",
+				p { "Error: {e}" }
+			}
+		};
+    }
+
     rsx! {
 		document::Link {
 			rel: "stylesheet",
@@ -336,6 +441,16 @@ let sorted_tracks = {
 				}
 				div { class: "header-actions",
 					if playlist_info().is_some() {
+						button {
+							class: "sort-playlist-button button",
+							onclick: move |_| show_edit_modal.set(true),
+							Icon {
+								icon: FaPen,
+								width: 18,
+								height: 18,
+							}
+							"Edit Details"
+						}
 						button {
 							class: "download-button button",
 							onclick: download_json,
@@ -356,6 +471,68 @@ let sorted_tracks = {
 							}
 							"Remove Duplicates"
 						}
+						button {
+							class: "sort-playlist-button button",
+							onclick: move |_| show_sort_modal.set(true),
+							Icon {
+								icon: FaArrowDownWideShort,
+								width: 18,
+								height: 18,
+							}
+							"Save Sort Order"
+						}
+						button {
+							class: "sort-playlist-button button",
+							onclick: move |_| show_split_modal.set(true),
+							Icon {
+								icon: FaScissors,
+								width: 18,
+								height: 18,
+							}
+							"Split"
+						}
+						button {
+							class: "sort-playlist-button button",
+							onclick: move |_| show_changelog_modal.set(true),
+							Icon {
+								icon: FaClockRotateLeft,
+								width: 18,
+								height: 18,
+							}
+							"Changes"
+						}
+						button {
+							class: "sort-playlist-button button",
+							onclick: move |_| show_contributors_modal.set(true),
+							Icon {
+								icon: FaUsers,
+								width: 18,
+								height: 18,
+							}
+							"Contributors"
+						}
+						button {
+							class: "sort-playlist-button button",
+							onclick: move |_| show_stats_modal.set(true),
+							Icon {
+								icon: FaChartSimple,
+								width: 18,
+								height: 18,
+							}
+							"Stats"
+						}
+						if unplayable_count > 0 && market().is_some() {
+							button {
+								class: "sort-playlist-button button",
+								onclick: move |_| show_repair_modal.set(true),
+								Icon {
+									icon: FaWrench,
+									width: 18,
+									height: 18,
+								}
+								"Repair {unplayable_count}"
+							}
+						}
 					}
 				}
 			}
@@ -435,28 +612,52 @@ let sorted_tracks = {
 			} else {
 				div { class: "tracks-list",
 					for (index , item) in sorted_tracks.iter().enumerate() {
-						div {
-							class: "track-item clickable",
-							key: "{item.track.id}-{index}",
-							onclick: {
-							    let track = item.track.clone();
-							    move |_| selected_track.set(Some(track.clone()))
-							},
-							span { class: "track-number", "{index + 1}" }
-							if let Some(image) = item.track.album.images.first() {
-								img {
-									class: "track-image",
-									src: "{image.url}",
-									alt: "{item.track.name}",
+						{
+						    // Only Spotify tracks open the detail view, other entries get a badge instead
+						    let track = item.as_track().cloned();
+						    let unplayable = track.as_ref().filter(|t| t.is_unplayable()).map(|t| t.restriction_label());
+						    let class = match &item.track {
+						        PlaylistItem::Track(_) if unplayable.is_some() => "track-item clickable unplayable",
+						        PlaylistItem::Track(_) => "track-item clickable",
+						        PlaylistItem::Episode(_) => "track-item episode",
+						        PlaylistItem::Local(_) => "track-item local",
+						        PlaylistItem::Unavailable => "track-item unavailable",
+						        PlaylistItem::Invalid { .. } => "track-item invalid",
+						    };
+						    rsx! {
+							div {
+								class,
+								key: "{item.track.key()}-{index}",
+								onclick: move |_| {
+								    if let Some(track) = track.clone() {
+								        selected_track.set(Some(track));
+								    }
+								},
+								span { class: "track-number", "{index + 1}" }
+								if let Some(image_url) = item.track.image_url() {
+									img {
+										class: "track-image",
+										src: "{image_url}",
+										alt: "{item.track.name()}",
+									}
+								} else {
+									div { class: "track-image placeholder" }
 								}
-							}
-							div { class: "track-info",
-								div { class: "track-name", "{item.track.name}" }
-								div { class: "track-artists",
-									{item.track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", ")}
+								div { class: "track-info",
+									div { class: "track-name",
+										"{item.track.name()}"
+										if let Some(label) = item.track.kind_label() {
+											span { class: "track-kind-badge", "{label}" }
+										}
+										if let Some(reason) = unplayable {
+											span { class: "track-kind-badge", title: "{reason}", "Unplayable" }
+										}
+									}
+									div { class: "track-artists", {item.track.artist_names()} }
 								}
+								div { class: "track-duration", {format_duration(item.track.duration_ms())} }
 							}
-							div { class: "track-duration", {format_duration(item.track.duration_ms)} }
+						}
 						}
 					}
 				}
@@ -480,35 +681,40 @@ let sorted_tracks = {
 						}
 
 						div { class: "modal-body",
+							DuplicateStrictnessSelect {
+								strictness: duplicate_strictness(),
+								on_change: change_strictness,
+							}
+							KeepStrategySelect {
+								strategy: keep_strategy(),
+								on_change: change_keep_strategy,
+							}
+
 							if duplicates().is_empty() {
 								p { style: "text-align: center; padding: 20px;",
 									"No duplicate tracks found in this playlist!"
 								}
 							} else {
-								p { style: "margin-bottom: 15px;",
-									"Found {duplicates().len()} duplicate track(s). The first occurrence will be kept."
+								div { class: "duplicate-summary",
+									p { "Found {duplicates().len()} duplicate track(s)." }
+									button {
+										class: "strictness-button",
+										onclick: move |_| show_removal_preview.toggle(),
+										if show_removal_preview() {
+											"Show Groups"
+										} else {
+											"Preview Result"
+										}
+									}
 								}
 
-								div { class: "duplicates-list",
-									for (track , indices) in duplicates().iter() {
-										div { class: "duplicate-item",
-											if let Some(image) = track.album.images.first() {
-												img {
-													class: "duplicate-image",
-													src: "{image.url}",
-													alt: "{track.name}",
-												}
-											}
-											div { class: "duplicate-info",
-												div { class: "duplicate-name", "{track.name}" }
-												div { class: "duplicate-artist",
-													{track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", ")}
-												}
-												div { class: "duplicate-count",
-													"Appears {indices.len()} times (will remove {indices.len() - 1})"
-												}
-											}
-										}
+								if show_removal_preview() {
+									DuplicateRemovalPreview { items: tracks(), groups: duplicates() }
+								} else {
+									DuplicateGroupList {
+										items: tracks(),
+										groups: duplicates(),
+										on_keep_change: change_group_keep,
 									}
 								}
 							}
@@ -538,9 +744,102 @@ let sorted_tracks = {
 			}
 		}
 
+		// Write a sort order to the real playlist, starting from the current view's sort
+		if show_sort_modal() {
+			if let Some(playlist) = playlist_info() {
+				ApplySortModal {
+					playlist,
+					items: tracks(),
+					initial_keys: initial_sort_keys(&sort_order()),
+					on_close: move |_| show_sort_modal.set(false),
+				}
+			}
+		}
+
+		// Create one playlist per genre, decade, artist or chunk
+		if show_split_modal() {
+			if let Some(playlist) = playlist_info() {
+				SplitPlaylistModal {
+					playlist,
+					items: tracks(),
+					on_close: move |_| show_split_modal.set(false),
+				}
+			}
+		}
+
+		// Change name, description, visibility and cover
+		if show_edit_modal() {
+			if let Some(playlist) = playlist_info() {
+				EditPlaylistModal {
+					playlist,
+					on_saved: move |playlist| playlist_info.set(Some(playlist)),
+					on_close: move |_| show_edit_modal.set(false),
+				}
+			}
+		}
+
+		// Changelog built from the stored snapshots
+		if show_changelog_modal() {
+			if let Some(playlist) = playlist_info() {
+				PlaylistChangelogModal { playlist, on_close: move |_| show_changelog_modal.set(false) }
+			}
+		}
+
+		// Per-person counts, timeline and genres
+		if show_contributors_modal() {
+			if let Some(playlist) = playlist_info() {
+				ContributorsModal {
+					playlist,
+					items: tracks(),
+					on_close: move |_| show_contributors_modal.set(false),
+				}
+			}
+		}
+
+		// Durations, popularity, years, artists, genres and freshness
+		if show_stats_modal() {
+			if let Some(playlist) = playlist_info() {
+				PlaylistStatsModal {
+					playlist,
+					items: tracks(),
+					on_close: move |_| show_stats_modal.set(false),
+				}
+			}
+		}
+
+		// Swap unplayable tracks for versions available in the user's country
+		if show_repair_modal() {
+			if let (Some(playlist), Some(market)) = (playlist_info(), market()) {
+				RepairTracksModal {
+					playlist,
+					items: tracks(),
+					market,
+					on_repaired: move |(playlist, items): (Playlist, Vec<PlaylistTrackItem>)| {
+					    record_playlist_snapshot(&playlist, &items);
+					    playlist_info.set(Some(playlist));
+					    tracks.set(items);
+					},
+					on_close: move |_| show_repair_modal.set(false),
+				}
+			}
+		}
+
 		// Track detail modal
 		if let Some(track) = selected_track() {
 			TrackDetail { track, on_close: move |_| selected_track.set(None) }
 		}
 	}
+}
+
+/// Sort keys matching one of the view's sort buttons
+fn initial_sort_keys(sort_order: &str) -> Vec<SortKey> {
+    let (field, descending) = match sort_order {
+        "name_asc" => (SortField::Title, false),
+        "name_desc" => (SortField::Title, true),
+        "artist_desc" => (SortField::Artist, true),
+        "date_added_desc" => (SortField::AddedAt, true),
+        "date_added_asc" => (SortField::AddedAt, false),
+        _ => (SortField::Artist, false),
+    };
+    vec![SortKey { field, descending }]
 }
\ No newline at end of file
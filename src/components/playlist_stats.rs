@@ -0,0 +1,180 @@
+use crate::models::{Playlist, PlaylistTrackItem};
+use crate::utils::*;
+use crate::AppContext;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::FaXmark;
+use dioxus_free_icons::Icon;
+use std::collections::HashMap;
+
+/// Aggregate numbers, histograms and rankings for the loaded tracks of a playlist
+#[component]
+pub fn PlaylistStatsModal(playlist: Playlist, items: Vec<PlaylistTrackItem>, on_close: EventHandler<()>) -> Element {
+    let context = use_context::<AppContext>();
+    let spotify_client = context.spotify_client;
+
+    // Artist genres, the rest of the stats only needs the loaded tracks
+    let mut genres = use_signal(HashMap::<String, Vec<String>>::new);
+    let mut loading_genres = use_signal(|| true);
+
+    {
+        let items = items.clone();
+        use_hook(move || {
+            let Some(client) = spotify_client.read().clone() else {
+                loading_genres.set(false);
+                return;
+            };
+            spawn(async move {
+                match fetch_artist_genres(&client, items.iter().filter_map(|item| item.as_track())).await {
+                    Ok(fetched) => genres.set(fetched),
+                    Err(e) => eprintln!("Failed to load artist genres: {}", e),
+                }
+                loading_genres.set(false);
+            });
+        });
+    }
+
+    let stats = playlist_stats(&items, &genres.read());
+    let hours = stats.total_duration_ms / 3_600_000;
+    let minutes = stats.total_duration_ms / 60_000 % 60;
+    let average = stats.average_popularity.map(|p| format!("{:.0}", p)).unwrap_or_else(|| "–".to_string());
+    let median = stats.median_popularity.map(|p| format!("{:.0}", p)).unwrap_or_else(|| "–".to_string());
+    let explicit = (stats.explicit_share * 100.0).round();
+    let busiest_year = stats.release_years.iter().map(|(_, count)| *count).max().unwrap_or(1);
+    let busiest_month = stats.added_timeline.iter().map(|(_, count)| *count).max().unwrap_or(1);
+    let top_artist_count = stats.top_artists.first().map(|(_, count)| *count).unwrap_or(1);
+    let top_genre_count = stats.top_genres.first().map(|(_, count)| *count).unwrap_or(1);
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/playlists.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/stats.css") }
+		div { class: "modal-overlay", onclick: move |_| on_close.call(()),
+			div {
+				class: "modal-content stats-modal",
+				onclick: move |e| e.stop_propagation(),
+
+				div { class: "modal-header",
+					h2 { "Stats for \"{playlist.name}\"" }
+					button {
+						class: "modal-close",
+						onclick: move |_| on_close.call(()),
+						Icon { icon: FaXmark, width: 20, height: 20 }
+					}
+				}
+
+				div { class: "modal-body",
+					div { class: "stats-tiles",
+						StatTile { value: "{stats.track_count}", label: "Tracks" }
+						StatTile { value: "{hours} h {minutes} min", label: "Total duration" }
+						StatTile { value: "{average}", label: "Average popularity" }
+						StatTile { value: "{median}", label: "Median popularity" }
+						StatTile { value: "{explicit}%", label: "Explicit" }
+						StatTile { value: "{stats.added_last_30_days}", label: "Added in the last 30 days" }
+					}
+					p { class: "stats-freshness",
+						{stats.freshness_label()}
+						if let Some(last) = &stats.last_added {
+							" ({format_timestamp(last)})"
+						}
+					}
+
+					if !stats.release_years.is_empty() {
+						h3 { class: "stats-section-title", "Release years" }
+						div { class: "stats-histogram",
+							for (year , count) in stats.release_years.iter() {
+								div {
+									class: "stats-bar",
+									key: "{year}",
+									title: "{year}: {count} track(s)",
+									style: "height: {count * 100 / busiest_year}%;",
+								}
+							}
+						}
+						div { class: "stats-axis",
+							span { "{stats.release_years[0].0}" }
+							span { "{stats.release_years[stats.release_years.len() - 1].0}" }
+						}
+					}
+
+					if !stats.added_timeline.is_empty() {
+						h3 { class: "stats-section-title", "Added over time" }
+						div { class: "stats-histogram",
+							for (month , count) in stats.added_timeline.iter() {
+								div {
+									class: "stats-bar",
+									key: "{month}",
+									title: "{month}: {count} track(s)",
+									style: "height: {count * 100 / busiest_month}%;",
+								}
+							}
+						}
+						div { class: "stats-axis",
+							span { "{stats.added_timeline[0].0}" }
+							span { "{stats.added_timeline[stats.added_timeline.len() - 1].0}" }
+						}
+					}
+
+					div { class: "stats-rankings",
+						div {
+							h3 { class: "stats-section-title", "Top artists" }
+							for (name , count) in stats.top_artists.iter() {
+								RankingRow {
+									key: "{name}",
+									name: name.clone(),
+									count: *count,
+									max: top_artist_count,
+								}
+							}
+						}
+						div {
+							h3 { class: "stats-section-title", "Top genres" }
+							if loading_genres() {
+								p { class: "stats-freshness", "Loading genres..." }
+							}
+							for (name , count) in stats.top_genres.iter() {
+								RankingRow {
+									key: "{name}",
+									name: name.clone(),
+									count: *count,
+									max: top_genre_count,
+								}
+							}
+						}
+					}
+				}
+
+				div { class: "modal-footer",
+					button {
+						class: "modal-button cancel-button",
+						onclick: move |_| on_close.call(()),
+						"Close"
+					}
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn StatTile(value: String, label: String) -> Element {
+    rsx! {
+		div { class: "stats-tile",
+			span { class: "stats-value", "{value}" }
+			span { class: "stats-label", "{label}" }
+		}
+	}
+}
+
+#[component]
+fn RankingRow(name: String, count: usize, max: usize) -> Element {
+    let width = count * 100 / max.max(1);
+
+    rsx! {
+		div { class: "stats-ranking-row",
+			span { class: "stats-ranking-name", title: "{name}", "{name}" }
+			div { class: "stats-ranking-track",
+				div { class: "stats-ranking-fill", style: "width: {width}%;" }
+			}
+			span { class: "stats-ranking-count", "{count}" }
+		}
+	}
+}
\ No newline at end of file
@@ -1,8 +1,9 @@
-use crate::models::{Playlist, Track};
+use crate::components::{SharedTracksModal, SetOperationsModal, DuplicateGroupList, DuplicateRemovalPreview, DuplicateStrictnessSelect, KeepStrategySelect};
+use crate::models::{Playlist, PlaylistTrackItem};
 use crate::{Route, AppContext};
 use crate::utils::*;
 use dioxus::prelude::*;
-use dioxus_free_icons::icons::fa_solid_icons::{FaFileArrowDown, FaMagnifyingGlass, FaCheck, FaXmark};
+use dioxus_free_icons::icons::fa_solid_icons::{FaFileArrowDown, FaMagnifyingGlass, FaCheck, FaXmark, FaTableCells, FaObjectGroup};
 use dioxus_free_icons::Icon;
 use std::collections::HashSet;
 
@@ -14,8 +15,13 @@ pub fn Playlists(playlists: ReadSignal<Vec<Playlist>>) -> Element {
     let mut show_duplicates_modal = use_signal(|| false);
     let mut show_all = use_signal(|| false);
     let mut search_query = use_signal(|| String::new());
-    let mut duplicates_data = use_signal(|| Vec::<(Playlist, Vec<(Track, usize)>)>::new());
+    let mut duplicates_data = use_signal(|| Vec::<(Playlist, Vec<PlaylistTrackItem>, Vec<DuplicateGroup>)>::new());
+    let mut duplicate_strictness = use_signal(|| DuplicateStrictness::Recording);
+    let mut keep_strategy = use_signal(|| KeepStrategy::First);
+    let mut show_removal_preview = use_signal(|| false);
     let mut removing_duplicates = use_signal(|| false);
+    let mut show_shared_modal = use_signal(|| false);
+    let mut show_combine_modal = use_signal(|| false);
     let context = use_context::<AppContext>();
 
     // Clone for use in closures
@@ -73,12 +79,12 @@ pub fn Playlists(playlists: ReadSignal<Vec<Playlist>>) -> Element {
                 for playlist_id in selected.iter() {
                     if let Some(playlist) = playlist_items_clone.iter().find(|p| &p.id == playlist_id) {
                         // Fetch tracks
-                        match client.get_playlist_tracks(&playlist.id).await {
+                        match client.get_playlist_tracks(&playlist.playlist_id(), None).await {
                             Ok(tracks) => {
+                                // Local files and unavailable tracks can't be imported again
                                 let track_uris: Vec<String> = tracks
                                     .iter()
-                                    .map(|item| &item.track)
-                                    .map(|track| format!("spotify:track:{}", track.id))
+                                    .filter_map(|item| item.track.addable_uri().map(|uri| uri.uri()))
                                     .collect();
 
                                 let json_data = serde_json::json!({
@@ -144,34 +150,16 @@ pub fn Playlists(playlists: ReadSignal<Vec<Playlist>>) -> Element {
 
                 for playlist_id in selected.iter() {
                     if let Some(playlist) = playlist_items_clone.iter().find(|p| &p.id == playlist_id) {
-                        match client.get_playlist_tracks(&playlist.id).await {
+                        // Reload the playlist so positions match its current snapshot
+                        let fresh = client.get_playlist(&playlist.playlist_id()).await.unwrap_or_else(|_| playlist.clone());
+                        match client.get_playlist_tracks(&playlist.playlist_id(), None).await {
                             Ok(tracks) => {
                                 // Find duplicates in this playlist
-                                let mut track_map = std::collections::HashMap::new();
-                                for (idx, item) in tracks.iter().enumerate() {
-                                    let track = &item.track;
-                                    track_map
-                                        .entry(track.id.clone())
-                                        .or_insert_with(Vec::new)
-                                        .push(idx);
-                                }
-
-                                let playlist_duplicates: Vec<(Track, usize)> = track_map
-                                    .into_iter()
-                                    .filter(|(_, indices)| indices.len() > 1)
-                                    .map(|(track_id, indices)| {
-                                        let track = tracks
-                                            .iter()
-                                            .find_map(|item| {
-                                                Some(&item.track).filter(|t| t.id == track_id)
-                                            })
-                                            .unwrap();
-                                        (track.clone(), indices.len())
-                                    })
-                                    .collect();
+                                let mut groups = find_duplicate_groups(&tracks, duplicate_strictness());
+                                set_keep_strategy(&mut groups, keep_strategy());
 
-                                if !playlist_duplicates.is_empty() {
-                                    all_duplicates.push((playlist.clone(), playlist_duplicates));
+                                if !groups.is_empty() {
+                                    all_duplicates.push((fresh, tracks, groups));
                                 }
                             }
                             Err(e) => {
@@ -185,6 +173,7 @@ pub fn Playlists(playlists: ReadSignal<Vec<Playlist>>) -> Element {
                 if all_duplicates.is_empty() {
                     show_info(&context_clone, "No duplicates found in selected playlists".to_string());
                 } else {
+                    show_removal_preview.set(false);
                     show_duplicates_modal.set(true);
                 }
             });
@@ -192,60 +181,74 @@ pub fn Playlists(playlists: ReadSignal<Vec<Playlist>>) -> Element {
         }
     };
 
+    // Re-run detection on the already fetched tracks when the strictness changes
+    let change_strictness = move |strictness: DuplicateStrictness| {
+        duplicate_strictness.set(strictness);
+        let mut data = duplicates_data.write();
+        for (_, tracks, groups) in data.iter_mut() {
+            *groups = find_duplicate_groups(tracks, strictness);
+            set_keep_strategy(groups, keep_strategy());
+        }
+    };
+
+    // Apply a keep strategy to every group of every playlist
+    let change_keep_strategy = move |keep: KeepStrategy| {
+        keep_strategy.set(keep);
+        for (_, _, groups) in duplicates_data.write().iter_mut() {
+            set_keep_strategy(groups, keep);
+        }
+    };
+
     // Remove duplicates from selected playlists
     let remove_duplicates = {
         let context = context_for_closures.clone();
-        let playlist_items = playlist_items_for_closures.clone();
         move |_| {
             removing_duplicates.set(true);
-            let selected = selected_playlists();
 
             let spotify_client_option = context.spotify_client.read().clone();
-            let playlist_items_clone = playlist_items.clone();
+            let duplicates = duplicates_data();
             let context_clone = context.clone();
 
             if let Some(client) = spotify_client_option {
             spawn(async move {
                 let mut total_removed = 0;
+                let mut failed = Vec::new();
+                let mut entries = Vec::new();
 
-                for playlist_id in selected.iter() {
-                    if let Some(playlist) = playlist_items_clone.iter().find(|p| &p.id == playlist_id) {
-                        match client.get_playlist_tracks(&playlist.id).await {
-                            Ok(tracks) => {
-                                // Find duplicate positions
-                                let mut track_map = std::collections::HashMap::new();
-                                for (idx, item) in tracks.iter().enumerate() {
-                                    let track = &item.track;
-                                    track_map
-                                        .entry(track.id.clone())
-                                        .or_insert_with(Vec::new)
-                                        .push(idx);
-                                }
+                for (playlist, tracks, groups) in duplicates.iter() {
+                    // Get tracks with URIs and positions to remove (keep the chosen copy)
+                    let tracks_to_remove = duplicate_removals(tracks, groups);
 
-                                // Get tracks with URIs and positions to remove (keep first occurrence)
-                                let tracks_to_remove: Vec<(String, usize)> = track_map
-                                    .iter()
-                                    .filter(|(_, indices)| indices.len() > 1)
-                                    .flat_map(|(track_id, indices)| {
-                                        let uri = format!("spotify:track:{}", track_id);
-                                        indices.iter().skip(1).map(move |&pos| (uri.clone(), pos))
-                                    })
-                                    .collect();
+                    if tracks_to_remove.is_empty() {
+                        continue;
+                    }
 
-                                if !tracks_to_remove.is_empty() {
-                                    match client.remove_tracks_from_playlist(&playlist.id, tracks_to_remove).await {
-                                        Ok(_) => {
-                                            total_removed += 1;
-                                        }
-                                        Err(e) => {
-                                            eprintln!("Failed to remove duplicates from {}: {}", playlist.name, e);
-                                        }
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                eprintln!("Failed to process {}: {}", playlist.name, e);
-                            }
+                    // Skip playlists that were edited since their duplicates were found
+                    if let Some(snapshot_id) = &playlist.snapshot_id {
+                        if let Err(e) = client.check_snapshot(&playlist.playlist_id(), snapshot_id).await {
+                            eprintln!("Skipping {}: {}", playlist.name, e);
+                            failed.push(playlist.name.clone());
+                            continue;
+                        }
+                    }
+
+                    match client
+                        .remove_tracks_from_playlist(&playlist.playlist_id(), tracks_to_remove.clone(), playlist.snapshot_id.as_deref())
+                        .await
+                    {
+                        Ok(new_snapshot_id) => {
+                            total_removed += 1;
+                            entries.push(removal_entry(
+                                "Removed duplicates",
+                                playlist,
+                                tracks,
+                                &tracks_to_remove,
+                                Some(new_snapshot_id),
+                            ));
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to remove duplicates from {}: {}", playlist.name, e);
+                            failed.push(playlist.name.clone());
                         }
                     }
                 }
@@ -254,7 +257,18 @@ pub fn Playlists(playlists: ReadSignal<Vec<Playlist>>) -> Element {
                 show_duplicates_modal.set(false);
 
                 if total_removed > 0 {
-                    show_success(&context_clone, format!("Removed duplicates from {} playlist(s)", total_removed));
+                    let operation_ids = record_operations(&context_clone, entries);
+                    show_success_with_undo(
+                        &context_clone,
+                        format!("Removed duplicates from {} playlist(s)", total_removed),
+                        operation_ids,
+                    );
+                }
+                if !failed.is_empty() {
+                    show_error(
+                        &context_clone,
+                        format!("Could not clean {} (changed or failed), check them again", failed.join(", ")),
+                    );
                 }
             });
         }
@@ -306,6 +320,29 @@ pub fn Playlists(playlists: ReadSignal<Vec<Playlist>>) -> Element {
 								}
 								"Find Duplicates"
 							}
+
+							if selected_count > 1 {
+								button {
+									class: "batch-duplicates-button",
+									onclick: move |_| show_shared_modal.set(true),
+									Icon {
+										icon: FaTableCells,
+										width: 16,
+										height: 16,
+									}
+									"Shared Tracks"
+								}
+								button {
+									class: "batch-duplicates-button",
+									onclick: move |_| show_combine_modal.set(true),
+									Icon {
+										icon: FaObjectGroup,
+										width: 16,
+										height: 16,
+									}
+									"Combine"
+								}
+							}
 						}
 						button {
 							class: "select-all-button",
@@ -435,6 +472,22 @@ pub fn Playlists(playlists: ReadSignal<Vec<Playlist>>) -> Element {
 			}
 		}
 
+		// Shared tracks matrix of the selected playlists
+		if show_shared_modal() {
+			SharedTracksModal {
+				playlists: playlist_items.iter().filter(|p| selected_playlists().contains(&p.id)).cloned().collect::<Vec<_>>(),
+				on_close: move |_| show_shared_modal.set(false),
+			}
+		}
+
+		// Union / intersection / difference of the selected playlists
+		if show_combine_modal() {
+			SetOperationsModal {
+				playlists: playlist_items.iter().filter(|p| selected_playlists().contains(&p.id)).cloned().collect::<Vec<_>>(),
+				on_close: move |_| show_combine_modal.set(false),
+			}
+		}
+
 		// Duplicates Modal
 		if show_duplicates_modal() {
 			div {
@@ -454,17 +507,37 @@ pub fn Playlists(playlists: ReadSignal<Vec<Playlist>>) -> Element {
 					}
 
 					div { class: "modal-body",
-						if duplicates_data().is_empty() {
+						DuplicateStrictnessSelect {
+							strictness: duplicate_strictness(),
+							on_change: change_strictness,
+						}
+						KeepStrategySelect {
+							strategy: keep_strategy(),
+							on_change: change_keep_strategy,
+						}
+
+						if duplicates_data().iter().all(|(_, _, groups)| groups.is_empty()) {
 							p { style: "text-align: center; padding: 20px;",
 								"No duplicate tracks found in selected playlists!"
 							}
 						} else {
-							p { style: "margin-bottom: 20px; color: var(--text-secondary);",
-								"Found duplicates in {duplicates_data().len()} playlist(s). First occurrence of each track will be kept."
+							div { class: "duplicate-summary",
+								p { style: "color: var(--text-secondary);",
+									"Found duplicates in {duplicates_data().iter().filter(|(_, _, groups)| !groups.is_empty()).count()} playlist(s)."
+								}
+								button {
+									class: "strictness-button",
+									onclick: move |_| show_removal_preview.toggle(),
+									if show_removal_preview() {
+										"Show Groups"
+									} else {
+										"Preview Result"
+									}
+								}
 							}
 
-							for (playlist , dups) in duplicates_data().iter() {
-								div { class: "playlist-section",
+							for (index , (playlist , tracks , groups)) in duplicates_data().iter().enumerate().filter(|(_, (_, _, groups))| !groups.is_empty()) {
+								div { class: "playlist-section", key: "{playlist.id}",
 									div { class: "playlist-section-header",
 										if let Some(image) = playlist.images.first() {
 											img {
@@ -480,31 +553,22 @@ pub fn Playlists(playlists: ReadSignal<Vec<Playlist>>) -> Element {
 										div { class: "playlist-section-info",
 											div { class: "playlist-section-name", "{playlist.name}" }
 											div { class: "playlist-section-meta",
-												"{dups.len()} duplicate track(s)"
+												"{groups.len()} duplicate track(s)"
 											}
 										}
 									}
 
-									div { class: "duplicates-list",
-										for (track , count) in dups.iter() {
-											div { class: "duplicate-item",
-												if let Some(image) = track.album.images.first() {
-													img {
-														class: "duplicate-image",
-														src: "{image.url}",
-														alt: "{track.name}",
-													}
-												}
-												div { class: "duplicate-info",
-													div { class: "duplicate-name", "{track.name}" }
-													div { class: "duplicate-artist",
-														{track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", ")}
-													}
-													div { class: "duplicate-count",
-														"Appears {count} times (will remove {count - 1})"
-													}
-												}
-											}
+									if show_removal_preview() {
+										DuplicateRemovalPreview { items: tracks.clone(), groups: groups.clone() }
+									} else {
+										DuplicateGroupList {
+											items: tracks.clone(),
+											groups: groups.clone(),
+											on_keep_change: move |(group, keep): (usize, KeepStrategy)| {
+											    if let Some(group) = duplicates_data.write()[index].2.get_mut(group) {
+											        group.keep = keep;
+											    }
+											},
 										}
 									}
 								}
@@ -518,7 +582,7 @@ pub fn Playlists(playlists: ReadSignal<Vec<Playlist>>) -> Element {
 							onclick: move |_| show_duplicates_modal.set(false),
 							"Cancel"
 						}
-						if !duplicates_data().is_empty() {
+						if duplicates_data().iter().any(|(_, _, groups)| !groups.is_empty()) {
 							button {
 								class: "modal-button remove-button",
 								onclick: remove_duplicates,
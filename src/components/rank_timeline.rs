@@ -0,0 +1,144 @@
+use crate::models::{time_range_label, RankTimeline};
+use crate::utils::*;
+use dioxus::prelude::*;
+
+/// Size of the chart's coordinate system, it scales to the modal's width
+const CHART_WIDTH: f64 = 400.0;
+const CHART_HEIGHT: f64 = 160.0;
+/// Room for the rank labels on the left and the dates below
+const CHART_LEFT: f64 = 32.0;
+const CHART_BOTTOM: f64 = 20.0;
+const CHART_TOP: f64 = 8.0;
+const CHART_RIGHT: f64 = 8.0;
+
+/// CSS modifier of each time range's line, in the order of [`TIME_RANGES`]
+const SERIES_CLASSES: [&str; 3] = ["short", "medium", "long"];
+
+/// Rank of a track or artist in the stored top list snapshots. Renders nothing if it never
+/// appeared in one.
+#[component]
+pub fn RankTimelineChart(kind: TopListKind, id: String) -> Element {
+    let timeline = use_hook(move || rank_timeline(&load_top_history(), kind, &id));
+
+    let (Some(first_appeared), Some(last_seen)) = (&timeline.first_appeared, &timeline.last_seen) else {
+        return rsx! {};
+    };
+    let peak = timeline.peak().unwrap_or_default();
+    let series = chart_series(&timeline);
+    let first_date = timeline.points.first().map(|p| p.date.clone()).unwrap_or_default();
+    let last_date = timeline.points.last().map(|p| p.date.clone()).unwrap_or_default();
+
+    rsx! {
+		div { class: "rank-timeline",
+			div { class: "detail-metadata",
+				div { class: "metadata-item",
+					span { class: "metadata-label", "First Appeared" }
+					span { class: "metadata-value", "{first_appeared}" }
+				}
+				div { class: "metadata-item",
+					span { class: "metadata-label", "Last Seen" }
+					span { class: "metadata-value", "{last_seen}" }
+				}
+				div { class: "metadata-item",
+					span { class: "metadata-label", "Peak Rank" }
+					span { class: "metadata-value", "#{peak}" }
+				}
+			}
+
+			svg {
+				class: "rank-timeline-chart",
+				view_box: "0 0 {CHART_WIDTH} {CHART_HEIGHT}",
+				text {
+					x: "{CHART_LEFT - 6.0}",
+					y: "{CHART_TOP + 4.0}",
+					text_anchor: "end",
+					"#1"
+				}
+				text {
+					x: "{CHART_LEFT - 6.0}",
+					y: "{CHART_HEIGHT - CHART_BOTTOM}",
+					text_anchor: "end",
+					"#{TOP_LIST_LIMIT}"
+				}
+				text { x: "{CHART_LEFT}", y: "{CHART_HEIGHT - 4.0}", "{first_date}" }
+				text {
+					x: "{CHART_WIDTH - CHART_RIGHT}",
+					y: "{CHART_HEIGHT - 4.0}",
+					text_anchor: "end",
+					"{last_date}"
+				}
+				for (index , line) in series.iter().enumerate() {
+					g { key: "{index}", class: "rank-series {SERIES_CLASSES[index]}",
+						for (segment_index , points) in line.segments.iter().enumerate() {
+							polyline { key: "{segment_index}", points: "{points}" }
+						}
+						for (dot_index , (x , y)) in line.dots.iter().enumerate() {
+							circle {
+								key: "{dot_index}",
+								cx: "{x}",
+								cy: "{y}",
+								r: "2.5",
+							}
+						}
+					}
+				}
+			}
+
+			div { class: "rank-timeline-legend",
+				for (index , range) in TIME_RANGES.iter().enumerate() {
+					span { key: "{range}", class: "rank-series {SERIES_CLASSES[index]}",
+						"{time_range_label(range)}"
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Line and dots of one time range
+struct ChartSeries {
+    /// Polyline points of every unbroken run
+    segments: Vec<String>,
+    dots: Vec<(f64, f64)>,
+}
+
+/// One series per entry of [`TIME_RANGES`]
+fn chart_series(timeline: &RankTimeline) -> Vec<ChartSeries> {
+    let count = timeline.points.len();
+    let plot_width = CHART_WIDTH - CHART_LEFT - CHART_RIGHT;
+    let plot_height = CHART_HEIGHT - CHART_TOP - CHART_BOTTOM;
+    let x_of = |index: usize| {
+        if count > 1 {
+            CHART_LEFT + plot_width * index as f64 / (count - 1) as f64
+        } else {
+            CHART_LEFT + plot_width / 2.0
+        }
+    };
+    let y_of = |rank: usize| CHART_TOP + plot_height * (rank - 1) as f64 / (TOP_LIST_LIMIT - 1) as f64;
+
+    (0..TIME_RANGES.len())
+        .map(|range| {
+            let mut segments = Vec::new();
+            let mut dots = Vec::new();
+            let mut current: Vec<String> = Vec::new();
+
+            for (index, point) in timeline.points.iter().enumerate() {
+                match point.ranks[range] {
+                    Some(rank) => {
+                        let (x, y) = (x_of(index), y_of(rank));
+                        current.push(format!("{:.1},{:.1}", x, y));
+                        dots.push((x, y));
+                    }
+                    // Days the item was out of the list break the line
+                    None if !current.is_empty() => segments.push(std::mem::take(&mut current).join(" ")),
+                    None => {}
+                }
+            }
+            if !current.is_empty() {
+                segments.push(current.join(" "));
+            }
+
+            ChartSeries { segments, dots }
+        })
+        .collect()
+}
\ No newline at end of file
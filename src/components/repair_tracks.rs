@@ -0,0 +1,193 @@
+use crate::models::{Playlist, PlaylistTrackItem};
+use crate::utils::*;
+use crate::AppContext;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::FaXmark;
+use dioxus_free_icons::Icon;
+use std::collections::HashSet;
+
+/// Finds playable versions of the playlist's unplayable tracks and swaps them in place
+#[component]
+pub fn RepairTracksModal(
+    playlist: Playlist,
+    items: Vec<PlaylistTrackItem>,
+    market: String,
+    on_repaired: EventHandler<(Playlist, Vec<PlaylistTrackItem>)>,
+    on_close: EventHandler<()>,
+) -> Element {
+    let context = use_context::<AppContext>();
+    let spotify_client = context.spotify_client;
+
+    let mut candidates = use_signal(Vec::<RepairCandidate>::new);
+    // Positions of the candidates to swap
+    let mut selected = use_signal(HashSet::<usize>::new);
+    let mut loading = use_signal(|| true);
+    let mut repairing = use_signal(|| false);
+
+    {
+        let items = items.clone();
+        let market = market.clone();
+        use_hook(move || {
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+
+            spawn(async move {
+                let found = find_repair_candidates(&client, &items, &market).await;
+                // Relinked tracks already play, so only the broken ones are picked by default
+                selected.set(
+                    found
+                        .iter()
+                        .filter(|c| c.replacement.is_some() && c.method != Some(RepairMethod::Relinked))
+                        .map(|c| c.position)
+                        .collect(),
+                );
+                candidates.set(found);
+                loading.set(false);
+            });
+        });
+    }
+
+    let repair = {
+        let context = context.clone();
+        let playlist = playlist.clone();
+        let market = market.clone();
+        move |_| {
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+            let chosen: Vec<RepairCandidate> =
+                candidates().into_iter().filter(|c| selected.read().contains(&c.position)).collect();
+            let context = context.clone();
+            let playlist = playlist.clone();
+            let items = items.clone();
+            let market = market.clone();
+
+            repairing.set(true);
+            spawn(async move {
+                match apply_repairs(&client, &context, &playlist, &items, &chosen).await {
+                    Ok(operation_ids) => {
+                        show_success_with_undo(
+                            &context,
+                            format!("Swapped {} track(s) in \"{}\" for playable versions", chosen.len(), playlist.name),
+                            operation_ids,
+                        );
+                        let fresh = client.get_playlist(&playlist.playlist_id()).await.unwrap_or_else(|_| playlist.clone());
+                        match client.get_playlist_tracks(&playlist.playlist_id(), Some(&market)).await {
+                            Ok(items) => on_repaired.call((fresh, items)),
+                            Err(e) => eprintln!("Failed to reload playlist tracks: {}", e),
+                        }
+                        on_close.call(());
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to repair tracks: {}", e);
+                        show_error(&context, format!("Failed to repair tracks: {}", e));
+                    }
+                }
+                repairing.set(false);
+            });
+        }
+    };
+
+    let candidate_list = candidates();
+    let unplayable_count = candidate_list.iter().filter(|c| c.method != Some(RepairMethod::Relinked)).count();
+    let found_count =
+        candidate_list.iter().filter(|c| c.replacement.is_some() && c.method != Some(RepairMethod::Relinked)).count();
+    let selected_count = selected.read().len();
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/playlists.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		div { class: "modal-overlay", onclick: move |_| on_close.call(()),
+			div {
+				class: "modal-content",
+				onclick: move |e| e.stop_propagation(),
+
+				div { class: "modal-header",
+					h2 { "Repair Unplayable Tracks" }
+					button {
+						class: "modal-close",
+						onclick: move |_| on_close.call(()),
+						Icon { icon: FaXmark, width: 20, height: 20 }
+					}
+				}
+
+				div { class: "modal-body",
+					if loading() {
+						p { style: "text-align: center; padding: 20px;", "Looking for playable versions..." }
+					} else if candidate_list.is_empty() {
+						p { style: "text-align: center; padding: 20px;",
+							"Every track in this playlist is playable in your country."
+						}
+					} else {
+						p { class: "duplicate-preview-summary",
+							"Found playable versions for {found_count} of {unplayable_count} unplayable track(s) in {market}"
+						}
+						div { class: "duplicate-preview",
+							for candidate in candidate_list.iter() {
+								{
+								    let position = candidate.position;
+								    let checked = selected.read().contains(&position);
+								    let artists = candidate
+								        .original
+								        .artists
+								        .iter()
+								        .map(|a| a.name.clone())
+								        .collect::<Vec<_>>()
+								        .join(", ");
+								    rsx! {
+									div { class: "duplicate-preview-row repair-row", key: "{position}",
+										input {
+											r#type: "checkbox",
+											checked,
+											disabled: candidate.replacement.is_none(),
+											onchange: move |_| {
+											    let mut selected = selected.write();
+											    if !selected.remove(&position) {
+											        selected.insert(position);
+											    }
+											},
+										}
+										span { class: "duplicate-position", "#{position + 1}" }
+										div { class: "repair-tracks",
+											span { class: "duplicate-preview-name", "{candidate.original.name} – {artists}" }
+											if let (Some(replacement), Some(method)) = (&candidate.replacement, candidate.method) {
+												span { class: "repair-replacement",
+													"→ {replacement.name} ({replacement.album.name})"
+													span { class: "duplicate-reason", "{method.label()}" }
+												}
+											} else {
+												span { class: "repair-missing",
+													"{candidate.original.restriction_label()}, no playable version found"
+												}
+											}
+										}
+									}
+								}
+								}
+							}
+						}
+					}
+				}
+
+				div { class: "modal-footer",
+					button {
+						class: "modal-button cancel-button",
+						onclick: move |_| on_close.call(()),
+						"Cancel"
+					}
+					button {
+						class: "modal-button remove-button",
+						disabled: loading() || repairing() || selected_count == 0,
+						onclick: repair,
+						if repairing() {
+							"Swapping..."
+						} else {
+							"Swap In {selected_count} Track(s)"
+						}
+					}
+				}
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,219 @@
+use crate::models::{PlayableUri, Playlist, PlaylistTrackItem};
+use crate::utils::*;
+use crate::AppContext;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::FaXmark;
+use dioxus_free_icons::Icon;
+
+/// Combine the selected playlists into a new one, previewing the result first
+#[component]
+pub fn SetOperationsModal(playlists: Vec<Playlist>, on_close: EventHandler<()>) -> Element {
+    let context = use_context::<AppContext>();
+    let spotify_client = context.spotify_client;
+
+    let mut loaded = use_signal(Vec::<(Playlist, Vec<PlaylistTrackItem>)>::new);
+    let mut loading = use_signal(|| true);
+    // Names of playlists whose tracks could not be loaded, combining without them would be wrong
+    let mut failed = use_signal(Vec::<String>::new);
+    let mut operation = use_signal(|| SetOperation::Union);
+    let mut order = use_signal(|| CombineOrder::Preserve);
+    // Playlist ID used as "A" of a difference
+    let mut base_id = use_signal(|| playlists.first().map(|p| p.id.clone()).unwrap_or_default());
+    let mut custom_name = use_signal(|| None::<String>);
+    let mut creating = use_signal(|| false);
+
+    use_hook({
+        let context = context.clone();
+        move || {
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+            spawn(async move {
+                let mut result = Vec::new();
+                let mut errors = Vec::new();
+                for playlist in playlists {
+                    match client.get_playlist_tracks(&playlist.playlist_id(), None).await {
+                        Ok(tracks) => result.push((playlist, tracks)),
+                        Err(e) => {
+                            eprintln!("Failed to load tracks of {}: {}", playlist.name, e);
+                            errors.push(format!("{}: {}", playlist.name, e));
+                            failed.write().push(playlist.name);
+                        }
+                    }
+                }
+                if !errors.is_empty() {
+                    show_error(&context, format!("Failed to load playlist tracks. {}", errors.join("; ")));
+                }
+                loaded.set(result);
+                loading.set(false);
+            });
+        }
+    });
+
+    // The base playlist goes first, which only matters for the difference
+    let mut ordered = loaded();
+    if let Some(index) = ordered.iter().position(|(p, _)| p.id == base_id()) {
+        let base = ordered.remove(index);
+        ordered.insert(0, base);
+    }
+
+    let lists: Vec<Vec<PlaylistTrackItem>> = ordered.iter().map(|(_, tracks)| tracks.clone()).collect();
+    let result = combine_playlists(&lists, operation(), order());
+    let names: Vec<String> = ordered.iter().map(|(p, _)| p.name.clone()).collect();
+    let generated_name = names.join(&format!(" {} ", operation().symbol()));
+    let name = custom_name().unwrap_or(generated_name);
+    let failed_names = failed().join(", ");
+
+    let create = {
+        let context = context.clone();
+        let result = result.clone();
+        let name = name.clone();
+        let description = format!("{} of {}", operation().label(), names.join(", "));
+        move |_| {
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+            let context = context.clone();
+            let uris: Vec<PlayableUri> = result.iter().map(|t| t.playable_uri()).collect();
+            let name = name.clone();
+            let description = description.clone();
+
+            creating.set(true);
+            spawn(async move {
+                match client.create_playlist(&name, &description, false, false).await {
+                    Ok(playlist) => match client.add_tracks_to_playlist(&playlist.playlist_id(), uris.clone()).await {
+                        Ok(_) => {
+                            show_success(&context, format!("Created \"{}\" with {} track(s)", name, uris.len()));
+                            on_close.call(());
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to add tracks: {}", e);
+                            show_error(&context, format!("Playlist created but adding tracks failed: {}", e));
+                        }
+                    },
+                    Err(e) => {
+                        eprintln!("Failed to create playlist: {}", e);
+                        show_error(&context, format!("Failed to create playlist: {}", e));
+                    }
+                }
+                creating.set(false);
+            });
+        }
+    };
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/playlists.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		div { class: "modal-overlay", onclick: move |_| on_close.call(()),
+			div {
+				class: "modal-content",
+				onclick: move |e| e.stop_propagation(),
+
+				div { class: "modal-header",
+					h2 { "Combine Playlists" }
+					button {
+						class: "modal-close",
+						onclick: move |_| on_close.call(()),
+						Icon { icon: FaXmark, width: 20, height: 20 }
+					}
+				}
+
+				div { class: "modal-body",
+					if loading() {
+						p { style: "text-align: center; padding: 20px;", "Loading playlists..." }
+					} else {
+						div { class: "duplicate-strictness",
+							span { class: "duplicate-strictness-label", "Operation:" }
+							for option in SetOperation::all() {
+								button {
+									key: "{option.key()}",
+									class: if option == operation() { "strictness-button active" } else { "strictness-button" },
+									onclick: move |_| operation.set(option),
+									"{option.label()}"
+								}
+							}
+						}
+
+						if operation() == SetOperation::Difference {
+							div { class: "duplicate-strictness",
+								span { class: "duplicate-strictness-label", "Keep tracks of:" }
+								select {
+									class: "duplicate-keep-select",
+									value: "{base_id()}",
+									onchange: move |e| base_id.set(e.value()),
+									for (playlist , _) in loaded().iter() {
+										option { key: "{playlist.id}", value: "{playlist.id}", "{playlist.name}" }
+									}
+								}
+								span { class: "duplicate-strictness-label", "that are not in the others" }
+							}
+						} else {
+							div { class: "duplicate-strictness",
+								span { class: "duplicate-strictness-label", "Order:" }
+								button {
+									class: if order() == CombineOrder::Preserve { "strictness-button active" } else { "strictness-button" },
+									onclick: move |_| order.set(CombineOrder::Preserve),
+									"Playlist by playlist"
+								}
+								button {
+									class: if order() == CombineOrder::Interleave { "strictness-button active" } else { "strictness-button" },
+									onclick: move |_| order.set(CombineOrder::Interleave),
+									"Interleave"
+								}
+							}
+						}
+
+						div { class: "form-group", style: "margin-bottom: 15px;",
+							label { "Name" }
+							input {
+								class: "form-input",
+								r#type: "text",
+								value: "{name}",
+								oninput: move |e| custom_name.set(Some(e.value())),
+							}
+						}
+
+						if !failed_names.is_empty() {
+							p { class: "duplicate-preview-summary",
+								"Could not load {failed_names}. Close and try again, the result would be wrong without them."
+							}
+						}
+						p { class: "duplicate-preview-summary",
+							"{result.len()} track(s) from "
+							{ordered.iter().map(|(p, tracks)| format!("{} ({})", p.name, tracks.len())).collect::<Vec<_>>().join(", ")}
+						}
+						div { class: "duplicate-preview",
+							for (position , track) in result.iter().enumerate() {
+								div { class: "duplicate-preview-row", key: "{track.id}",
+									span { class: "duplicate-position", "#{position + 1}" }
+									span { class: "duplicate-preview-name",
+										"{track.name} – "
+										{track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", ")}
+									}
+								}
+							}
+						}
+					}
+				}
+
+				div { class: "modal-footer",
+					button {
+						class: "modal-button cancel-button",
+						onclick: move |_| on_close.call(()),
+						"Cancel"
+					}
+					button {
+						class: "modal-button remove-button",
+						disabled: loading() || creating() || !failed_names.is_empty() || result.is_empty() || name.trim().is_empty(),
+						onclick: create,
+						if creating() {
+							"Creating..."
+						} else {
+							"Create Playlist"
+						}
+					}
+				}
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,303 @@
+use crate::api::SpotifyClient;
+use crate::components::DuplicateStrictnessSelect;
+use crate::models::{Playlist, PlaylistTrackItem};
+use crate::utils::*;
+use crate::AppContext;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::FaXmark;
+use dioxus_free_icons::Icon;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Matrix of the songs that the given playlists have in common
+#[component]
+pub fn SharedTracksModal(playlists: Vec<Playlist>, on_close: EventHandler<()>) -> Element {
+    let context = use_context::<AppContext>();
+    let spotify_client = context.spotify_client;
+
+    let mut loaded = use_signal(Vec::<(Playlist, Vec<PlaylistTrackItem>)>::new);
+    let mut shared = use_signal(Vec::<SharedTrack>::new);
+    let mut strictness = use_signal(|| DuplicateStrictness::Recording);
+    // Playlist index to keep each shared song in, by index into `shared`
+    let mut keep_in = use_signal(HashMap::<usize, usize>::new);
+    let mut loading = use_signal(|| true);
+    let mut removing = use_signal(|| None::<usize>);
+
+    // Recompute the shared songs from the loaded tracks
+    let mut refresh_shared = move || {
+        let items: Vec<Vec<PlaylistTrackItem>> = loaded().into_iter().map(|(_, tracks)| tracks).collect();
+        shared.set(find_shared_tracks(&items, strictness()));
+        keep_in.write().clear();
+    };
+
+    use_hook(move || {
+        let Some(client) = spotify_client.read().clone() else {
+            return;
+        };
+
+        spawn(async move {
+            loaded.set(load_playlists(&client, &playlists).await);
+            refresh_shared();
+            loading.set(false);
+        });
+    });
+
+    let change_strictness = move |value: DuplicateStrictness| {
+        strictness.set(value);
+        refresh_shared();
+    };
+
+    let remove_from_others = {
+        let context = context.clone();
+        move |index: usize| {
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+            let Some(track) = shared().get(index).cloned() else {
+                return;
+            };
+            let keep = keep_in().get(&index).copied().unwrap_or(track.occurrences[0].0);
+            let data = loaded();
+            let context = context.clone();
+
+            removing.set(Some(index));
+
+            spawn(async move {
+                let items: Vec<Vec<PlaylistTrackItem>> = data.iter().map(|(_, tracks)| tracks.clone()).collect();
+                let mut changed = Vec::new();
+                let mut failed = Vec::new();
+                let mut entries = Vec::new();
+
+                for (playlist_index, tracks) in shared_track_removals(&items, &track, keep) {
+                    let playlist = &data[playlist_index].0;
+                    let result = match &playlist.snapshot_id {
+                        Some(snapshot_id) => match client.check_snapshot(&playlist.playlist_id(), snapshot_id).await {
+                            Ok(_) => {
+                                client
+                                    .remove_tracks_from_playlist(&playlist.playlist_id(), tracks.clone(), Some(snapshot_id))
+                                    .await
+                            }
+                            Err(e) => Err(e),
+                        },
+                        None => client.remove_tracks_from_playlist(&playlist.playlist_id(), tracks.clone(), None).await,
+                    };
+
+                    match result {
+                        Ok(new_snapshot_id) => {
+                            changed.push(playlist_index);
+                            entries.push(removal_entry(
+                                "Removed shared track",
+                                playlist,
+                                &items[playlist_index],
+                                &tracks,
+                                Some(new_snapshot_id),
+                            ));
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to remove shared track from {}: {}", playlist.name, e);
+                            failed.push(playlist.name.clone());
+                        }
+                    }
+                }
+
+                // Positions and snapshots of the edited playlists are stale now, reload them
+                let reloaded =
+                    load_playlists(&client, &changed.iter().map(|&i| data[i].0.clone()).collect::<Vec<_>>()).await;
+                {
+                    let mut loaded = loaded.write();
+                    for (playlist, tracks) in reloaded {
+                        if let Some(entry) = loaded.iter_mut().find(|(p, _)| p.id == playlist.id) {
+                            *entry = (playlist, tracks);
+                        }
+                    }
+                }
+                refresh_shared();
+                removing.set(None);
+
+                if !changed.is_empty() {
+                    let operation_ids = record_operations(&context, entries);
+                    show_success_with_undo(
+                        &context,
+                        format!(
+                            "Removed \"{}\" from {} playlist(s), kept in {}",
+                            track.track.name(),
+                            changed.len(),
+                            data[keep].0.name
+                        ),
+                        operation_ids,
+                    );
+                }
+                if !failed.is_empty() {
+                    show_error(&context, format!("Could not update {}, reload and try again", failed.join(", ")));
+                }
+            });
+        }
+    };
+
+    let data = loaded();
+    let shared_list = shared();
+    let counts = shared_counts(&shared_list, data.len());
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/playlists.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		div { class: "modal-overlay", onclick: move |_| on_close.call(()),
+			div {
+				class: "modal-content shared-modal",
+				onclick: move |e| e.stop_propagation(),
+
+				div { class: "modal-header",
+					h2 { "Tracks Shared Between Playlists" }
+					button {
+						class: "modal-close",
+						onclick: move |_| on_close.call(()),
+						Icon { icon: FaXmark, width: 20, height: 20 }
+					}
+				}
+
+				div { class: "modal-body",
+					if loading() {
+						p { style: "text-align: center; padding: 20px;", "Loading playlists..." }
+					} else {
+						DuplicateStrictnessSelect {
+							strictness: strictness(),
+							on_change: change_strictness,
+						}
+
+						// How many songs every pair of playlists has in common
+						h3 { class: "shared-title", "Overlap" }
+						div { class: "shared-table-wrapper",
+							table { class: "shared-matrix",
+								thead {
+									tr {
+										th {}
+										for (playlist , _) in data.iter() {
+											th { key: "{playlist.id}", title: "{playlist.name}", "{playlist.name}" }
+										}
+									}
+								}
+								tbody {
+									for (row , (playlist , _)) in data.iter().enumerate() {
+										tr { key: "{playlist.id}",
+											th { title: "{playlist.name}", "{playlist.name}" }
+											for (column , count) in counts[row].iter().enumerate() {
+												if row == column {
+													td { class: "shared-cell self", "–" }
+												} else {
+													td { class: if *count > 0 { "shared-cell hit" } else { "shared-cell" }, "{count}" }
+												}
+											}
+										}
+									}
+								}
+							}
+						}
+
+						// Which playlists contain each shared song
+						h3 { class: "shared-title", "{shared_list.len()} shared track(s)" }
+						if shared_list.is_empty() {
+							p { style: "text-align: center; padding: 20px;",
+								"The selected playlists have no tracks in common."
+							}
+						} else {
+							div { class: "shared-table-wrapper",
+								table { class: "shared-matrix",
+									thead {
+										tr {
+											th { "Track" }
+											for (playlist , _) in data.iter() {
+												th { key: "{playlist.id}", title: "{playlist.name}", "{playlist.name}" }
+											}
+											th { "Keep only in" }
+										}
+									}
+									tbody {
+										for (index , track) in shared_list.iter().enumerate() {
+											tr { key: "{track.track.key()}-{index}",
+												th { class: "shared-track",
+													div { class: "duplicate-name", "{track.track.name()}" }
+													div { class: "duplicate-artist", {track.track.artist_names()} }
+													if track.reason != MatchReason::SameTrack {
+														span { class: "duplicate-reason", "{track.reason.label()}" }
+													}
+												}
+												for playlist_index in 0..data.len() {
+													{
+													    let count = track.count_in(playlist_index);
+													    rsx! {
+														td { class: if count > 0 { "shared-cell hit" } else { "shared-cell" },
+															if count > 1 {
+																"✓ ×{count}"
+															} else if count == 1 {
+																"✓"
+															}
+														}
+													}
+													}
+												}
+												td { class: "shared-actions",
+													select {
+														class: "duplicate-keep-select",
+														value: "{keep_in().get(&index).copied().unwrap_or(track.occurrences[0].0)}",
+														onchange: move |e| {
+														    if let Ok(playlist_index) = e.value().parse::<usize>() {
+														        keep_in.write().insert(index, playlist_index);
+														    }
+														},
+														for playlist_index in track.playlists() {
+															option {
+																key: "{playlist_index}",
+																value: "{playlist_index}",
+																"{data[playlist_index].0.name}"
+															}
+														}
+													}
+													button {
+														class: "strictness-button",
+														disabled: removing().is_some(),
+														onclick: {
+														    let mut remove_from_others = remove_from_others.clone();
+														    move |_| remove_from_others(index)
+														},
+														if removing() == Some(index) {
+															"Removing..."
+														} else {
+															"Remove from others"
+														}
+													}
+												}
+											}
+										}
+									}
+								}
+							}
+						}
+					}
+				}
+
+				div { class: "modal-footer",
+					button {
+						class: "modal-button cancel-button",
+						onclick: move |_| on_close.call(()),
+						"Close"
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Fetch each playlist again (for a current snapshot ID) together with its tracks
+async fn load_playlists(client: &Rc<SpotifyClient>, playlists: &[Playlist]) -> Vec<(Playlist, Vec<PlaylistTrackItem>)> {
+    let mut loaded = Vec::new();
+
+    for playlist in playlists {
+        let fresh = client.get_playlist(&playlist.playlist_id()).await.unwrap_or_else(|_| playlist.clone());
+        match client.get_playlist_tracks(&playlist.playlist_id(), None).await {
+            Ok(tracks) => loaded.push((fresh, tracks)),
+            Err(e) => eprintln!("Failed to load tracks of {}: {}", playlist.name, e),
+        }
+    }
+
+    loaded
+}
\ No newline at end of file
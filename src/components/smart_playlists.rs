@@ -0,0 +1,542 @@
+use crate::models::*;
+use crate::utils::*;
+use crate::{Route, AppContext};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::{FaArrowLeft, FaXmark};
+use dioxus_free_icons::Icon;
+
+#[component]
+pub fn SmartPlaylists() -> Element {
+    let context = use_context::<AppContext>();
+    let nav = navigator();
+    let spotify_client = context.spotify_client;
+
+    let mut rules = use_signal(load_smart_playlists);
+    let mut editing = use_signal(|| None::<SmartPlaylist>);
+    let mut preview = use_signal(|| None::<Vec<RuleCandidate>>);
+    let mut user_playlists = use_signal(Vec::<Playlist>::new);
+    let mut busy = use_signal(|| None::<String>);
+
+    if spotify_client.read().is_none() {
+        use_effect(move || {
+            nav.push(Route::Home {});
+        });
+
+        return rsx! {
+			div { class: "loading", "Redirecting to login..." }
+		};
+    }
+
+    // Playlists offered as sources
+    use_hook(move || {
+        let Some(client) = spotify_client.read().clone() else {
+            return;
+        };
+        spawn(async move {
+            match client.get_all_playlists().await {
+                Ok(playlists) => user_playlists.set(playlists),
+                Err(e) => eprintln!("Failed to load playlists: {}", e),
+            }
+        });
+    });
+
+    // Store a definition, replacing the saved one with the same ID
+    let mut store_rule = move |rule: SmartPlaylist| {
+        let mut all = rules();
+        match all.iter_mut().find(|r| r.id == rule.id) {
+            Some(existing) => *existing = rule,
+            None => all.push(rule),
+        }
+        save_smart_playlists(&all);
+        rules.set(all);
+    };
+
+    let run_preview = {
+        let context = context.clone();
+        move |_| {
+            let (Some(client), Some(rule)) = (spotify_client.read().clone(), editing()) else {
+                return;
+            };
+            let context = context.clone();
+            busy.set(Some(rule.id.clone()));
+            spawn(async move {
+                match evaluate_smart_playlist(&client, &rule).await {
+                    Ok(tracks) => preview.set(Some(tracks)),
+                    Err(e) => show_error(&context, format!("Failed to evaluate rules: {}", e)),
+                }
+                busy.set(None);
+            });
+        }
+    };
+
+    // Create or update the real Spotify playlist for a definition
+    let materialize = {
+        let context = context.clone();
+        move |rule: SmartPlaylist| {
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+            let context = context.clone();
+            busy.set(Some(rule.id.clone()));
+            spawn(async move {
+                let first_run = rule.target_playlist_id.is_none();
+                match materialize_smart_playlist(&client, &rule).await {
+                    Ok((updated, count, entry)) => {
+                        store_rule(updated.clone());
+                        if editing().map(|r| r.id == updated.id).unwrap_or(false) {
+                            editing.set(Some(updated.clone()));
+                        }
+                        let verb = if first_run { "Created" } else { "Updated" };
+                        let message = format!("{} \"{}\" with {} track(s)", verb, updated.name, count);
+                        match entry {
+                            Some(entry) => {
+                                let operation_ids = record_operations(&context, vec![entry]);
+                                show_success_with_undo(&context, message, operation_ids);
+                            }
+                            None => show_success(&context, message),
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to materialize smart playlist: {}", e);
+                        show_error(&context, format!("Failed to update \"{}\": {}", rule.name, e));
+                    }
+                }
+                busy.set(None);
+            });
+        }
+    };
+
+    let mut delete_rule = move |id: String| {
+        let mut all = rules();
+        all.retain(|r| r.id != id);
+        save_smart_playlists(&all);
+        rules.set(all);
+        if editing().map(|r| r.id == id).unwrap_or(false) {
+            editing.set(None);
+        }
+    };
+
+    rsx! {
+		document::Link {
+			rel: "stylesheet",
+			href: asset!("assets/compiled/playlist_detail.css"),
+		}
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/tools.css") }
+		div { class: "tool-container",
+			header { class: "tool-header",
+				button {
+					class: "back-button",
+					onclick: move |_| {
+					    nav.push(Route::Dashboard {});
+					},
+					Icon { icon: FaArrowLeft, width: 42, height: 42 }
+				}
+				h1 { class: "tool-title", "Smart Playlists" }
+			}
+
+			div { class: "tool-section",
+				h2 { class: "tool-section-title", "Your rules" }
+				if rules().is_empty() {
+					p { class: "tool-hint",
+						"A smart playlist collects tracks from your top tracks, library or playlists and keeps those matching its rules."
+					}
+				}
+				div { class: "tool-list",
+					for rule in rules().into_iter() {
+						div { class: "tool-list-item", key: "{rule.id}",
+							span { class: "item-name", "{rule.name}" }
+							span { class: "item-meta", "{rule.summary()}" }
+							button {
+								class: "button outline",
+								onclick: {
+								    let rule = rule.clone();
+								    move |_| {
+								        preview.set(None);
+								        editing.set(Some(rule.clone()));
+								    }
+								},
+								"Edit"
+							}
+							button {
+								class: "button secondary",
+								disabled: busy().is_some(),
+								onclick: {
+								    let mut materialize = materialize.clone();
+								    let rule = rule.clone();
+								    move |_| materialize(rule.clone())
+								},
+								if busy() == Some(rule.id.clone()) {
+									"Running..."
+								} else if rule.target_playlist_id.is_some() {
+									"Update"
+								} else {
+									"Create"
+								}
+							}
+							button {
+								class: "back-button",
+								title: "Delete",
+								onclick: {
+								    let id = rule.id.clone();
+								    move |_| delete_rule(id.clone())
+								},
+								Icon { icon: FaXmark, width: 16, height: 16 }
+							}
+						}
+					}
+				}
+				div { class: "tool-row", style: "margin-top: var(--gap-small);",
+					button {
+						class: "button",
+						onclick: move |_| {
+						    preview.set(None);
+						    editing.set(Some(SmartPlaylist::new(generate_random_string(12))));
+						},
+						"New Smart Playlist"
+					}
+				}
+			}
+
+			if let Some(rule) = editing() {
+				RuleEditor {
+					rule,
+					playlists: user_playlists(),
+					on_change: move |rule| editing.set(Some(rule)),
+				}
+
+				div { class: "tool-section",
+					div { class: "tool-row",
+						button {
+							class: "button outline",
+							disabled: busy().is_some(),
+							onclick: run_preview,
+							"Preview"
+						}
+						button {
+							class: "button",
+							onclick: move |_| {
+							    if let Some(rule) = editing() {
+							        store_rule(rule);
+							    }
+							},
+							"Save"
+						}
+						button {
+							class: "button secondary",
+							disabled: busy().is_some(),
+							onclick: {
+							    let mut materialize = materialize.clone();
+							    move |_| {
+							        if let Some(rule) = editing() {
+							            store_rule(rule.clone());
+							            materialize(rule);
+							        }
+							    }
+							},
+							"Save & Materialize"
+						}
+						button {
+							class: "button outline",
+							onclick: move |_| {
+							    editing.set(None);
+							    preview.set(None);
+							},
+							"Close"
+						}
+					}
+					if let Some(last) = editing().and_then(|r| r.last_materialized) {
+						p { class: "tool-hint", "Last materialized {last}" }
+					}
+
+					if let Some(tracks) = preview() {
+						h3 { class: "tool-section-title", style: "margin-top: var(--gap-small);",
+							"{tracks.len()} matching track(s)"
+						}
+						div { class: "tool-list",
+							for candidate in tracks.iter() {
+								div {
+									class: "tool-list-item",
+									key: "{candidate.track.id}",
+									span { class: "item-name",
+										"{candidate.track.name} – "
+										{candidate.track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", ")}
+									}
+									span { class: "item-meta",
+										{format_release_date(&candidate.track.album.release_date)}
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Form for the sources and conditions of a smart playlist
+#[component]
+fn RuleEditor(rule: SmartPlaylist, playlists: Vec<Playlist>, on_change: EventHandler<SmartPlaylist>) -> Element {
+    // Apply a change to a copy of the rule and hand it back
+    let update = {
+        let rule = rule.clone();
+        move |change: Box<dyn FnOnce(&mut SmartPlaylist)>| {
+            let mut updated = rule.clone();
+            change(&mut updated);
+            on_change.call(updated);
+        }
+    };
+
+    let toggle_source = {
+        let update = update.clone();
+        move |source: RuleSource, enabled: bool| {
+            update(Box::new(move |rule| {
+                rule.sources.retain(|s| *s != source);
+                if enabled {
+                    rule.sources.push(source);
+                }
+            }))
+        }
+    };
+
+    let selected_playlists = rule
+        .sources
+        .iter()
+        .filter(|s| matches!(s, RuleSource::Playlist { .. }))
+        .count();
+
+    rsx! {
+		div { class: "tool-section",
+			h2 { class: "tool-section-title", "Definition" }
+			div { class: "tool-row",
+				input {
+					class: "tool-input",
+					r#type: "text",
+					placeholder: "Playlist name",
+					value: "{rule.name}",
+					oninput: {
+					    let update = update.clone();
+					    move |e: FormEvent| update(Box::new(move |rule| rule.name = e.value()))
+					},
+				}
+				input {
+					class: "tool-input",
+					r#type: "text",
+					placeholder: "Description",
+					value: "{rule.description}",
+					oninput: {
+					    let update = update.clone();
+					    move |e: FormEvent| update(Box::new(move |rule| rule.description = e.value()))
+					},
+				}
+			}
+
+			h3 { class: "tool-section-title", "Sources" }
+			div { class: "tool-row",
+				for time_range in RuleSource::time_ranges() {
+					{
+					    let source = RuleSource::TopTracks {
+					        time_range: time_range.to_string(),
+					    };
+					    rsx! {
+						label { class: "tool-option", key: "{time_range}",
+							input {
+								r#type: "checkbox",
+								checked: rule.sources.contains(&source),
+								onchange: {
+								    let toggle_source = toggle_source.clone();
+								    let source = source.clone();
+								    move |e: FormEvent| toggle_source(source.clone(), e.checked())
+								},
+							}
+							"{source.label()}"
+						}
+					}
+					}
+				}
+				for source in [RuleSource::SavedTracks, RuleSource::RecentlyPlayed] {
+					label { class: "tool-option", key: "{source.label()}",
+						input {
+							r#type: "checkbox",
+							checked: rule.sources.contains(&source),
+							onchange: {
+							    let toggle_source = toggle_source.clone();
+							    let source = source.clone();
+							    move |e: FormEvent| toggle_source(source.clone(), e.checked())
+							},
+						}
+						"{source.label()}"
+					}
+				}
+			}
+			details { class: "tool-details",
+				summary { "Playlists ({selected_playlists} selected)" }
+				div { class: "tool-list",
+					for playlist in playlists.iter() {
+						{
+						    let source = RuleSource::Playlist {
+						        id: playlist.id.clone(),
+						        name: playlist.name.clone(),
+						    };
+						    rsx! {
+							label { class: "tool-list-item tool-option", key: "{playlist.id}",
+								input {
+									r#type: "checkbox",
+									checked: rule.sources.contains(&source),
+									onchange: {
+									    let toggle_source = toggle_source.clone();
+									    let source = source.clone();
+									    move |e: FormEvent| toggle_source(source.clone(), e.checked())
+									},
+								}
+								span { class: "item-name", "{playlist.name}" }
+								span { class: "item-meta", "{playlist.tracks.total} tracks" }
+							}
+						}
+						}
+					}
+				}
+			}
+
+			h3 { class: "tool-section-title", "Rules" }
+			div { class: "tool-row",
+				"Tracks must match"
+				select {
+					class: "tool-select",
+					value: if rule.match_all { "all" } else { "any" },
+					onchange: {
+					    let update = update.clone();
+					    move |e: FormEvent| update(Box::new(move |rule| rule.match_all = e.value() == "all"))
+					},
+					option { value: "all", "all conditions" }
+					option { value: "any", "any condition" }
+				}
+			}
+			div { class: "tool-list",
+				for (index , condition) in rule.conditions.iter().enumerate() {
+					div { class: "tool-row rule-condition", key: "{index}",
+						select {
+							class: "tool-select",
+							value: "{condition.field.key()}",
+							onchange: {
+							    let update = update.clone();
+							    move |e: FormEvent| {
+							        if let Some(field) = RuleField::from_key(&e.value()) {
+							            update(
+							                Box::new(move |rule| {
+							                    let condition = &mut rule.conditions[index];
+							                    condition.field = field;
+							                    condition.operator = field.operators()[0];
+							                    condition.value = default_value(field);
+							                }),
+							            )
+							        }
+							    }
+							},
+							for field in RuleField::all() {
+								option { key: "{field.key()}", value: "{field.key()}", "{field.label()}" }
+							}
+						}
+						select {
+							class: "tool-select",
+							value: "{condition.operator.key()}",
+							onchange: {
+							    let update = update.clone();
+							    move |e: FormEvent| {
+							        if let Some(operator) = RuleOperator::from_key(&e.value()) {
+							            update(Box::new(move |rule| rule.conditions[index].operator = operator))
+							        }
+							    }
+							},
+							for operator in condition.field.operators() {
+								option { key: "{operator.key()}", value: "{operator.key()}", "{operator.label()}" }
+							}
+						}
+						if condition.field.kind() == FieldKind::Flag {
+							select {
+								class: "tool-select",
+								value: "{condition.value}",
+								onchange: {
+								    let update = update.clone();
+								    move |e: FormEvent| update(Box::new(move |rule| rule.conditions[index].value = e.value()))
+								},
+								option { value: "true", "yes" }
+								option { value: "false", "no" }
+							}
+						} else {
+							input {
+								class: "tool-input",
+								r#type: if condition.field.kind() == FieldKind::Number { "number" } else { "text" },
+								value: "{condition.value}",
+								oninput: {
+								    let update = update.clone();
+								    move |e: FormEvent| update(Box::new(move |rule| rule.conditions[index].value = e.value()))
+								},
+							}
+						}
+						button {
+							class: "back-button",
+							title: "Remove condition",
+							onclick: {
+							    let update = update.clone();
+							    move |_| update(
+							        Box::new(move |rule| {
+							            rule.conditions.remove(index);
+							        }),
+							    )
+							},
+							Icon { icon: FaXmark, width: 16, height: 16 }
+						}
+					}
+				}
+			}
+			div { class: "tool-row", style: "margin-top: var(--gap-tiny);",
+				button {
+					class: "button outline",
+					onclick: {
+					    let update = update.clone();
+					    move |_| update(
+					        Box::new(|rule| {
+					            rule.conditions
+					                .push(RuleCondition {
+					                    field: RuleField::ReleaseYear,
+					                    operator: RuleOperator::LessThan,
+					                    value: default_value(RuleField::ReleaseYear),
+					                })
+					        }),
+					    )
+					},
+					"Add Condition"
+				}
+				label { class: "tool-option",
+					"Limit"
+					input {
+						class: "tool-number",
+						r#type: "number",
+						min: "1",
+						placeholder: "none",
+						value: "{rule.limit.map(|l| l.to_string()).unwrap_or_default()}",
+						oninput: {
+						    let update = update.clone();
+						    move |e: FormEvent| update(Box::new(move |rule| rule.limit = e.value().parse().ok()))
+						},
+					}
+					"tracks"
+				}
+			}
+		}
+	}
+}
+
+fn default_value(field: RuleField) -> String {
+    match field.kind() {
+        FieldKind::Flag => "false".to_string(),
+        FieldKind::Number => match field {
+            RuleField::ReleaseYear | RuleField::AddedYear => "2000".to_string(),
+            RuleField::Popularity => "50".to_string(),
+            RuleField::DurationSeconds => "240".to_string(),
+            _ => "10".to_string(),
+        },
+        FieldKind::Text => String::new(),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,295 @@
+use crate::models::{PlayableUri, Playlist, PlaylistTrackItem};
+use crate::utils::*;
+use crate::AppContext;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::FaXmark;
+use dioxus_free_icons::Icon;
+use std::collections::{HashMap, HashSet};
+
+const DEFAULT_CHUNK_SIZE: usize = 50;
+const DEFAULT_DURATION_MINUTES: u32 = 60;
+
+/// Split a playlist into several new ones, previewing the groups first
+#[component]
+pub fn SplitPlaylistModal(playlist: Playlist, items: Vec<PlaylistTrackItem>, on_close: EventHandler<()>) -> Element {
+    let context = use_context::<AppContext>();
+    let spotify_client = context.spotify_client;
+
+    let mut mode = use_signal(|| SplitMode::Decade);
+    let mut chunk_size = use_signal(|| DEFAULT_CHUNK_SIZE);
+    let mut duration_minutes = use_signal(|| DEFAULT_DURATION_MINUTES);
+    let mut min_size = use_signal(|| 1usize);
+    // Artist genres, only fetched once the genre split is picked
+    let mut genres = use_signal(HashMap::<String, Vec<String>>::new);
+    let mut loading_genres = use_signal(|| false);
+    // Labels of the groups that should not become a playlist
+    let mut skipped = use_signal(HashSet::<String>::new);
+    let mut creating = use_signal(|| false);
+
+    let select_genre = {
+        let context = context.clone();
+        let items = items.clone();
+        move |_| {
+            let previous = mode();
+            mode.set(SplitMode::Genre);
+            if !genres.read().is_empty() || loading_genres() {
+                return;
+            }
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+            let items = items.clone();
+            let context = context.clone();
+            loading_genres.set(true);
+            spawn(async move {
+                match fetch_artist_genres(&client, items.iter().filter_map(|item| item.as_track())).await {
+                    Ok(fetched) => genres.set(fetched),
+                    Err(e) => {
+                        // Without genres every track would land in one group, so go back to the previous split
+                        eprintln!("Failed to load artist genres: {}", e);
+                        show_error(&context, format!("Failed to load artist genres: {}", e));
+                        if mode() == SplitMode::Genre {
+                            mode.set(previous);
+                        }
+                    }
+                }
+                loading_genres.set(false);
+            });
+        }
+    };
+
+    let groups = split_playlist(&items, mode(), &genres.read(), min_size());
+    let selected: Vec<SplitGroup> =
+        groups.iter().filter(|group| !skipped.read().contains(&group.label)).cloned().collect();
+
+    let create = {
+        let context = context.clone();
+        let selected = selected.clone();
+        let playlist = playlist.clone();
+        move |_| {
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+            let context = context.clone();
+            let selected = selected.clone();
+            let playlist = playlist.clone();
+            let mode = mode();
+
+            creating.set(true);
+            spawn(async move {
+                let mut created = 0;
+                let mut failed = Vec::new();
+
+                for group in &selected {
+                    let name = format!("{} – {}", playlist.name, group.label);
+                    let description = format!(
+                        "{} track(s) from \"{}\", {}",
+                        group.tracks.len(),
+                        playlist.name,
+                        mode.describe()
+                    );
+                    let uris: Vec<PlayableUri> = group.tracks.iter().map(|t| t.playable_uri()).collect();
+
+                    let result = match client.create_playlist(&name, &description, false, false).await {
+                        Ok(new_playlist) => client.add_tracks_to_playlist(&new_playlist.playlist_id(), uris).await,
+                        Err(e) => Err(e),
+                    };
+                    match result {
+                        Ok(_) => created += 1,
+                        Err(e) => {
+                            eprintln!("Failed to create {}: {}", name, e);
+                            failed.push(group.label.clone());
+                        }
+                    }
+                }
+
+                creating.set(false);
+                if created > 0 {
+                    show_success(&context, format!("Created {} playlist(s) from \"{}\"", created, playlist.name));
+                }
+                if failed.is_empty() {
+                    on_close.call(());
+                } else {
+                    show_error(&context, format!("Could not create: {}", failed.join(", ")));
+                }
+            });
+        }
+    };
+
+    let modes = [
+        SplitMode::Genre,
+        SplitMode::Decade,
+        SplitMode::PrimaryArtist,
+        SplitMode::ChunkSize(chunk_size()),
+        SplitMode::Duration(duration_minutes()),
+    ];
+    let selected_count = selected.len();
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/playlists.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/sorting.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/tools.css") }
+		div { class: "modal-overlay", onclick: move |_| on_close.call(()),
+			div {
+				class: "modal-content sort-modal",
+				onclick: move |e| e.stop_propagation(),
+
+				div { class: "modal-header",
+					h2 { "Split \"{playlist.name}\"" }
+					button {
+						class: "modal-close",
+						onclick: move |_| on_close.call(()),
+						Icon { icon: FaXmark, width: 20, height: 20 }
+					}
+				}
+
+				div { class: "modal-body",
+					div { class: "duplicate-strictness",
+						span { class: "duplicate-strictness-label", "Split by:" }
+						for option in modes {
+							button {
+								key: "{option.key()}",
+								class: if option.key() == mode().key() { "strictness-button active" } else { "strictness-button" },
+								onclick: {
+								    let mut select_genre = select_genre.clone();
+								    move |e| {
+								        if option == SplitMode::Genre {
+								            select_genre(e);
+								        } else {
+								            mode.set(option);
+								        }
+								    }
+								},
+								"{option.label()}"
+							}
+						}
+					}
+
+					match mode() {
+						SplitMode::ChunkSize(_) => rsx! {
+							div { class: "duplicate-strictness",
+								span { class: "duplicate-strictness-label", "Tracks per playlist:" }
+								input {
+									class: "tool-number",
+									r#type: "number",
+									min: "1",
+									value: "{chunk_size()}",
+									oninput: move |e| {
+									    if let Ok(value) = e.value().parse::<usize>() {
+									        let value = value.max(1);
+									        chunk_size.set(value);
+									        mode.set(SplitMode::ChunkSize(value));
+									    }
+									},
+								}
+							}
+						},
+						SplitMode::Duration(_) => rsx! {
+							div { class: "duplicate-strictness",
+								span { class: "duplicate-strictness-label", "Minutes per playlist:" }
+								input {
+									class: "tool-number",
+									r#type: "number",
+									min: "1",
+									value: "{duration_minutes()}",
+									oninput: move |e| {
+									    if let Ok(value) = e.value().parse::<u32>() {
+									        let value = value.max(1);
+									        duration_minutes.set(value);
+									        mode.set(SplitMode::Duration(value));
+									    }
+									},
+								}
+							}
+						},
+						_ => rsx! {
+							div { class: "duplicate-strictness",
+								span { class: "duplicate-strictness-label", "Merge groups smaller than" }
+								input {
+									class: "tool-number",
+									r#type: "number",
+									min: "1",
+									value: "{min_size()}",
+									oninput: move |e| {
+									    if let Ok(value) = e.value().parse::<usize>() {
+									        min_size.set(value.max(1));
+									    }
+									},
+								}
+								span { class: "duplicate-strictness-label", "tracks into \"Other\"" }
+							}
+						},
+					}
+
+					if mode() == SplitMode::Genre && loading_genres() {
+						p { style: "text-align: center; padding: 20px;", "Loading artist genres..." }
+					} else {
+						p { class: "duplicate-preview-summary",
+							"{groups.len()} group(s), {selected_count} selected"
+						}
+						div { class: "split-groups",
+							for group in groups.iter() {
+								{
+								    let label = group.label.clone();
+								    let included = !skipped.read().contains(&group.label);
+								    let minutes = group.duration_ms() / 60_000;
+								    rsx! {
+									details { class: if included { "split-group" } else { "split-group skipped" }, key: "{group.label}",
+										summary {
+											input {
+												r#type: "checkbox",
+												checked: included,
+												onclick: move |e| e.stop_propagation(),
+												onchange: move |_| {
+												    let mut skipped = skipped.write();
+												    if !skipped.remove(&label) {
+												        skipped.insert(label.clone());
+												    }
+												},
+											}
+											span { class: "split-group-name", "{playlist.name} – {group.label}" }
+											span { class: "split-group-meta",
+												"{group.tracks.len()} track(s) · {minutes} min"
+											}
+										}
+										div { class: "duplicate-preview",
+											for (position , track) in group.tracks.iter().enumerate() {
+												div { class: "duplicate-preview-row", key: "{position}",
+													span { class: "duplicate-position", "#{position + 1}" }
+													span { class: "duplicate-preview-name",
+														"{track.name} – "
+														{track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", ")}
+													}
+												}
+											}
+										}
+									}
+								}
+								}
+							}
+						}
+					}
+				}
+
+				div { class: "modal-footer",
+					button {
+						class: "modal-button cancel-button",
+						onclick: move |_| on_close.call(()),
+						"Cancel"
+					}
+					button {
+						class: "modal-button remove-button",
+						disabled: creating() || loading_genres() || selected_count == 0,
+						onclick: create,
+						if creating() {
+							"Creating..."
+						} else {
+							"Create {selected_count} Playlist(s)"
+						}
+					}
+				}
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,359 @@
+use crate::models::{time_range_label, TasteProfile, TasteProfileItem};
+use crate::utils::*;
+use crate::{Route, AppContext};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::FaArrowLeft;
+use dioxus_free_icons::Icon;
+
+/// Unique artists and tracks listed per side
+const HIGHLIGHTS: usize = 10;
+
+const DEFAULT_BLEND_SIZE: usize = 40;
+
+#[component]
+pub fn TasteCompare() -> Element {
+    let context = use_context::<AppContext>();
+    let nav = navigator();
+
+    let mut my_profile = use_signal(|| None::<TasteProfile>);
+    let mut their_profile = use_signal(|| None::<TasteProfile>);
+    let mut time_range = use_signal(|| "medium_term".to_string());
+    let mut blend_size = use_signal(|| DEFAULT_BLEND_SIZE);
+    let mut blending = use_signal(|| false);
+
+    use_hook({
+        let context = context.clone();
+        move || {
+            let Some(client) = context.spotify_client.read().clone() else {
+                return;
+            };
+            spawn(async move {
+                match fetch_taste_profile(&client).await {
+                    Ok(profile) => my_profile.set(Some(profile)),
+                    Err(e) => {
+                        eprintln!("Failed to load taste profile: {}", e);
+                        show_error(&context, format!("Failed to load your top lists: {}", e));
+                    }
+                }
+            });
+        }
+    });
+
+    if context.spotify_client.read().is_none() {
+        use_effect(move || {
+            nav.push(Route::Home {});
+        });
+
+        return rsx! {
+			div { class: "loading", "Redirecting to login..." }
+		};
+    }
+
+    let on_export = {
+        let context = context.clone();
+        move |_| {
+            let Some(profile) = my_profile() else {
+                return;
+            };
+            let context = context.clone();
+            spawn(async move {
+                let default_filename = format!(
+                    "InfoSpot_Taste_{}_{}.json",
+                    sanitize_filename(&profile.display_name),
+                    chrono::Local::now().format("%Y-%m-%d")
+                );
+                if let Some(save_path) = save_json_file(&default_filename) {
+                    let final_path = if save_path.ends_with(".json") {
+                        save_path
+                    } else {
+                        format!("{}.json", save_path)
+                    };
+
+                    match write_taste_profile(&profile, &final_path) {
+                        Ok(_) => {
+                            println!("✓ Taste profile exported to: {}", final_path);
+                            show_success(&context, "Taste profile exported".to_string());
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to write taste profile: {}", e);
+                            show_error(&context, format!("Failed to write taste profile: {}", e));
+                        }
+                    }
+                }
+            });
+        }
+    };
+
+    let on_load = {
+        let context = context.clone();
+        move |_| {
+            let context = context.clone();
+            spawn(async move {
+                if let Some(file_path) = pick_file("Select a taste profile", &["json"]) {
+                    match read_taste_profile(&file_path) {
+                        Ok(profile) => their_profile.set(Some(profile)),
+                        Err(e) => {
+                            eprintln!("Failed to read taste profile: {}", e);
+                            show_error(&context, format!("Failed to read taste profile: {}", e));
+                        }
+                    }
+                }
+            });
+        }
+    };
+
+    let comparison = match (my_profile(), their_profile()) {
+        (Some(mine), Some(theirs)) => Some((compare_taste_profiles(&mine, &theirs, &time_range()), mine, theirs)),
+        _ => None,
+    };
+
+    let on_blend = {
+        let context = context.clone();
+        let comparison = comparison.clone();
+        move |_| {
+            let Some((comparison, mine, theirs)) = comparison.clone() else {
+                return;
+            };
+            let Some(client) = context.spotify_client.read().clone() else {
+                return;
+            };
+
+            let ids = blend_track_ids(&comparison, blend_size());
+            if ids.is_empty() {
+                show_info(&context, "Neither profile has top tracks for this range".to_string());
+                return;
+            }
+            let name = format!(
+                "Blend: {} + {} · {}",
+                mine.display_name,
+                theirs.display_name,
+                chrono::Local::now().format("%Y-%m-%d")
+            );
+            let description = format!(
+                "Top tracks of {} and {} of the {}",
+                mine.display_name,
+                theirs.display_name,
+                time_range_label(&comparison.time_range)
+            );
+
+            let context = context.clone();
+            blending.set(true);
+            spawn(async move {
+                let result = match client.get_tracks(ids, None).await {
+                    Ok(tracks) => save_tracks_as_playlist(&client, &name, &description, &tracks)
+                        .await
+                        .map(|_| tracks.len()),
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(count) => show_success(&context, format!("Created \"{}\" with {} tracks", name, count)),
+                    Err(e) => {
+                        eprintln!("Failed to create blend playlist: {}", e);
+                        show_error(&context, format!("Failed to create playlist: {}", e));
+                    }
+                }
+                blending.set(false);
+            });
+        }
+    };
+
+    rsx! {
+		document::Link {
+			rel: "stylesheet",
+			href: asset!("assets/compiled/playlist_detail.css"),
+		}
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/tools.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		div { class: "tool-container",
+			header { class: "tool-header",
+				button {
+					class: "back-button",
+					onclick: move |_| {
+					    nav.push(Route::Dashboard {});
+					},
+					Icon { icon: FaArrowLeft, width: 42, height: 42 }
+				}
+				h1 { class: "tool-title", "Compare Tastes" }
+			}
+
+			div { class: "tool-section",
+				h2 { class: "tool-section-title", "Profiles" }
+				div { class: "tool-row",
+					div { class: "account-card",
+						div { class: "account-label", "You" }
+						if let Some(profile) = my_profile() {
+							div { class: "account-name", "{profile.display_name}" }
+						} else {
+							div { class: "account-name", "Loading top lists..." }
+						}
+					}
+					div { class: "account-card",
+						div { class: "account-label", "Them" }
+						if let Some(profile) = their_profile() {
+							div { class: "account-name", "{profile.display_name}" }
+							div { class: "account-label",
+								"Exported {format_timestamp(&profile.exported_at)}"
+							}
+						} else {
+							div { class: "account-name", "No profile loaded" }
+						}
+					}
+				}
+				div { class: "tool-row",
+					button {
+						class: "button secondary",
+						disabled: my_profile().is_none(),
+						onclick: on_export,
+						"Export My Profile"
+					}
+					button { class: "button outline", onclick: on_load, "Load Their Profile" }
+				}
+				p { class: "tool-hint",
+					"A taste profile holds your top artists, tracks and genres of every time range with their ranks. Send yours to a friend and load theirs to see how your tastes match."
+				}
+			}
+
+			if let Some((comparison, mine, theirs)) = comparison {
+				div { class: "duplicate-strictness",
+					span { class: "duplicate-strictness-label", "Range:" }
+					for option in TIME_RANGES {
+						button {
+							key: "{option}",
+							class: if option == time_range() { "strictness-button active" } else { "strictness-button" },
+							onclick: move |_| time_range.set(option.to_string()),
+							"{time_range_label(option)}"
+						}
+					}
+				}
+
+				div { class: "tool-section",
+					h2 { class: "tool-section-title", "{format_match(comparison.overall())} match" }
+					div { class: "tool-row",
+						div { class: "account-card",
+							div { class: "account-label", "Artists" }
+							div { class: "account-name", "{format_match(comparison.artist_overlap)}" }
+						}
+						div { class: "account-card",
+							div { class: "account-label", "Tracks" }
+							div { class: "account-name", "{format_match(comparison.track_overlap)}" }
+						}
+						div { class: "account-card",
+							div { class: "account-label", "Genres" }
+							div { class: "account-name", "{format_match(comparison.genre_overlap)}" }
+						}
+					}
+				}
+
+				div { class: "tool-section",
+					h2 { class: "tool-section-title", "In Common" }
+					div { class: "compare-columns",
+						div {
+							h3 { class: "compare-column-title", "Artists ({comparison.shared_artists.len()})" }
+							SharedList { items: comparison.shared_artists.clone(), empty: "No shared artists" }
+						}
+						div {
+							h3 { class: "compare-column-title", "Tracks ({comparison.shared_tracks.len()})" }
+							SharedList { items: comparison.shared_tracks.clone(), empty: "No shared tracks" }
+						}
+					}
+				}
+
+				div { class: "tool-section",
+					h2 { class: "tool-section-title", "Only One of You" }
+					div { class: "compare-columns",
+						div {
+							h3 { class: "compare-column-title", "{mine.display_name}" }
+							HighlightList { title: "Artists", items: comparison.my_artists.clone() }
+							HighlightList { title: "Tracks", items: comparison.my_tracks.clone() }
+						}
+						div {
+							h3 { class: "compare-column-title", "{theirs.display_name}" }
+							HighlightList { title: "Artists", items: comparison.their_artists.clone() }
+							HighlightList { title: "Tracks", items: comparison.their_tracks.clone() }
+						}
+					}
+				}
+
+				div { class: "tool-section",
+					h2 { class: "tool-section-title", "Blended Playlist" }
+					div { class: "tool-row",
+						label { class: "tool-option",
+							input {
+								class: "tool-number",
+								r#type: "number",
+								min: "1",
+								max: "100",
+								value: "{blend_size()}",
+								oninput: move |e| {
+								    if let Ok(size) = e.value().parse::<usize>() {
+								        blend_size.set(size.clamp(1, 100));
+								    }
+								},
+							}
+							"tracks"
+						}
+						button {
+							class: "button secondary",
+							disabled: blending(),
+							onclick: on_blend,
+							if blending() {
+								"Creating..."
+							} else {
+								"Create Playlist"
+							}
+						}
+					}
+					p { class: "tool-hint",
+						"Starts with the tracks you share, then takes turns between your top tracks and theirs."
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Items in both profiles with each side's rank
+#[component]
+fn SharedList(items: Vec<SharedItem>, empty: &'static str) -> Element {
+    rsx! {
+		if items.is_empty() {
+			p { class: "tool-hint", "{empty}" }
+		}
+		div { class: "tool-list",
+			for shared in items.iter() {
+				div { class: "tool-list-item", key: "{shared.item.id}",
+					span { class: "item-name", "{item_label(&shared.item)}" }
+					span { class: "item-meta", "#{shared.item.rank} · #{shared.their_rank}" }
+				}
+			}
+		}
+	}
+}
+
+/// Highest ranked items only one side has
+#[component]
+fn HighlightList(title: &'static str, items: Vec<TasteProfileItem>) -> Element {
+    rsx! {
+		p { class: "tool-hint", "{title}" }
+		div { class: "tool-list compare-highlights",
+			for item in items.iter().take(HIGHLIGHTS) {
+				div { class: "tool-list-item", key: "{item.id}",
+					span { class: "item-meta", "#{item.rank}" }
+					span { class: "item-name", "{item_label(item)}" }
+				}
+			}
+		}
+	}
+}
+
+fn item_label(item: &TasteProfileItem) -> String {
+    if item.detail.is_empty() {
+        item.name.clone()
+    } else {
+        format!("{} · {}", item.name, item.detail)
+    }
+}
+
+fn format_match(share: f64) -> String {
+    format!("{:.0}%", share * 100.0)
+}
\ No newline at end of file
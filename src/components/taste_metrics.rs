@@ -0,0 +1,151 @@
+use crate::models::time_range_label;
+use crate::utils::*;
+use dioxus::prelude::*;
+
+/// A metric of both compared time ranges, formatted, with the change between them
+struct MetricRow {
+    label: &'static str,
+    recent: String,
+    all_time: String,
+    change: String,
+}
+
+/// Mainstream score, diversity, era and explicit share of the top lists, and how the last
+/// 4 weeks differ from all time
+#[component]
+pub fn TasteMetricsPanel(ranges: ReadSignal<Option<Vec<RangeGenres>>>, time_range: ReadSignal<String>) -> Element {
+    let Some(range_list) = ranges() else {
+        return rsx! {};
+    };
+    let metrics: Vec<TasteMetrics> = range_list.iter().map(taste_metrics).collect();
+    let find = |range: &str| metrics.iter().find(|m| m.time_range == range);
+    let (Some(current), Some(recent), Some(all_time)) = (find(&time_range()), find("short_term"), find("long_term"))
+    else {
+        return rsx! {};
+    };
+
+    let rows = [
+        MetricRow {
+            label: "Mainstream score",
+            recent: format_score(recent.mainstream_score),
+            all_time: format_score(all_time.mainstream_score),
+            change: format_change(recent.mainstream_score, all_time.mainstream_score, ""),
+        },
+        MetricRow {
+            label: "Artist diversity",
+            recent: format_percent(recent.artist_diversity),
+            all_time: format_percent(all_time.artist_diversity),
+            change: format_share_change(recent.artist_diversity, all_time.artist_diversity),
+        },
+        MetricRow {
+            label: "Genre diversity",
+            recent: format_percent(recent.genre_diversity),
+            all_time: format_percent(all_time.genre_diversity),
+            change: format_share_change(recent.genre_diversity, all_time.genre_diversity),
+        },
+        MetricRow {
+            label: "Average release year",
+            recent: format_year(recent.average_release_year),
+            all_time: format_year(all_time.average_release_year),
+            change: format_change(recent.average_release_year, all_time.average_release_year, " yrs"),
+        },
+        MetricRow {
+            label: "Explicit tracks",
+            recent: format_percent(recent.explicit_share),
+            all_time: format_percent(all_time.explicit_share),
+            change: format_share_change(recent.explicit_share, all_time.explicit_share),
+        },
+    ];
+    let largest_decade = current.decades.iter().map(|(_, share)| *share).fold(0.0, f64::max);
+
+    rsx! {
+		div { class: "taste-section",
+			h3 { class: "genres-title", "Taste Metrics" }
+
+			div { class: "taste-cards",
+				div { class: "taste-card",
+					span { class: "taste-value", "{format_score(current.mainstream_score)}" }
+					span { class: "taste-label", "{current.mainstream_label()}" }
+				}
+				div { class: "taste-card",
+					span { class: "taste-value", "{format_percent(current.artist_diversity)}" }
+					span { class: "taste-label", "Artist diversity" }
+				}
+				div { class: "taste-card",
+					span { class: "taste-value", "{format_percent(current.genre_diversity)}" }
+					span { class: "taste-label", "Genre diversity" }
+				}
+				div { class: "taste-card",
+					span { class: "taste-value", "{format_year(current.average_release_year)}" }
+					span { class: "taste-label", "Average release year" }
+				}
+				div { class: "taste-card",
+					span { class: "taste-value", "{format_percent(current.explicit_share)}" }
+					span { class: "taste-label", "Explicit" }
+				}
+			}
+
+			if !current.decades.is_empty() {
+				div { class: "taste-decades",
+					for (decade , share) in current.decades.iter() {
+						div { key: "{decade}", class: "taste-decade",
+							span { class: "taste-decade-share", "{format_percent(*share)}" }
+							div {
+								class: "taste-decade-bar",
+								style: "height: {share / largest_decade * 100.0}%;",
+							}
+							span { class: "taste-decade-label", "{decade}s" }
+						}
+					}
+				}
+			}
+
+			table { class: "taste-comparison",
+				thead {
+					tr {
+						th { "" }
+						th { "{time_range_label(\"short_term\")}" }
+						th { "{time_range_label(\"long_term\")}" }
+						th { "Change" }
+					}
+				}
+				tbody {
+					for row in rows.iter() {
+						tr { key: "{row.label}",
+							td { class: "taste-metric", "{row.label}" }
+							td { "{row.recent}" }
+							td { "{row.all_time}" }
+							td { class: "taste-change", "{row.change}" }
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+fn format_score(score: Option<f64>) -> String {
+    score.map(|s| format!("{:.0}", s)).unwrap_or_else(|| "–".to_string())
+}
+
+fn format_percent(share: f64) -> String {
+    format!("{:.0}%", share * 100.0)
+}
+
+fn format_year(year: Option<f64>) -> String {
+    year.map(|y| format!("{:.0}", y)).unwrap_or_else(|| "–".to_string())
+}
+
+/// Signed difference of the recent value to the all-time one
+fn format_change(recent: Option<f64>, all_time: Option<f64>, unit: &str) -> String {
+    match (recent, all_time) {
+        (Some(recent), Some(all_time)) if (recent - all_time).abs() < 0.5 => "=".to_string(),
+        (Some(recent), Some(all_time)) => format!("{:+.0}{}", recent - all_time, unit),
+        _ => "–".to_string(),
+    }
+}
+
+/// Difference of two shares in percentage points
+fn format_share_change(recent: f64, all_time: f64) -> String {
+    format_change(Some(recent * 100.0), Some(all_time * 100.0), " pts")
+}
\ No newline at end of file
@@ -1,3 +1,5 @@
+use crate::utils::undo_operations;
+use crate::AppContext;
 use dioxus::prelude::*;
 use dioxus_free_icons::icons::fa_solid_icons::FaXmark;
 use dioxus_free_icons::Icon;
@@ -10,11 +12,38 @@ pub enum ToastType {
     Info,
 }
 
+// Button shown next to the toast message
+#[derive(Clone, PartialEq)]
+pub enum ToastAction {
+    /// Undo the journal entries with these IDs
+    Undo(Vec<String>),
+}
+
+impl ToastAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ToastAction::Undo(_) => "Undo",
+        }
+    }
+}
+
 #[derive(Clone, PartialEq)]
 pub struct Toast {
     pub message: String,
     pub toast_type: ToastType,
     pub id: usize,
+    pub action: Option<ToastAction>,
+}
+
+impl Toast {
+    /// Toasts with an action stay longer so there is time to use it
+    pub fn duration_ms(&self) -> u64 {
+        if self.action.is_some() {
+            8000
+        } else {
+            3000
+        }
+    }
 }
 
 #[component]
@@ -31,7 +60,9 @@ pub fn ToastContainer(toasts: Signal<Vec<Toast>>) -> Element {
 
 #[component]
 fn ToastItem(toast: Toast, toasts: Signal<Vec<Toast>>) -> Element {
+    let context = use_context::<AppContext>();
     let toast_id = toast.id;
+    let duration_ms = toast.duration_ms();
     let mut is_fading = use_signal(|| false);
 
     let class_name = match toast.toast_type {
@@ -46,15 +77,15 @@ fn ToastItem(toast: Toast, toasts: Signal<Vec<Toast>>) -> Element {
         ToastType::Info => "ℹ",
     };
 
-    // Start fade out animation after 2.7 seconds (300ms before removal)
+    // Start fade out animation 300ms before removal
     use_effect(move || {
         spawn(async move {
-            tokio::time::sleep(tokio::time::Duration::from_millis(2700)).await;
+            tokio::time::sleep(tokio::time::Duration::from_millis(duration_ms - 300)).await;
             is_fading.set(true);
         });
     });
 
-    let remove_toast = move |_| {
+    let mut remove_toast = move |_| {
         is_fading.set(true);
         spawn(async move {
             tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
@@ -67,6 +98,18 @@ fn ToastItem(toast: Toast, toasts: Signal<Vec<Toast>>) -> Element {
         });
     };
 
+    let run_action = move |action: ToastAction| {
+        let context = context.clone();
+        move |e: MouseEvent| {
+            match &action {
+                ToastAction::Undo(ids) => {
+                    spawn(undo_operations(context.clone(), ids.clone(), false));
+                }
+            }
+            remove_toast(e);
+        }
+    };
+
     let final_class = if is_fading() {
         format!("{} fade-out", class_name)
     } else {
@@ -74,9 +117,18 @@ fn ToastItem(toast: Toast, toasts: Signal<Vec<Toast>>) -> Element {
     };
 
     rsx! {
-		div { class: "{final_class}",
+		div {
+			class: "{final_class}",
+			style: "--toast-duration: {duration_ms}ms;",
 			span { class: "toast-icon", "{icon}" }
 			span { class: "toast-message", "{toast.message}" }
+			if let Some(action) = toast.action.clone() {
+				button {
+					class: "toast-action",
+					onclick: run_action(action.clone()),
+					"{action.label()}"
+				}
+			}
 			button { class: "toast-close", onclick: remove_toast,
 				Icon { icon: FaXmark, width: 20, height: 20 }
 			}
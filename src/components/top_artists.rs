@@ -1,9 +1,11 @@
 use crate::components::ArtistDetail;
 use crate::models::Artist;
 use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::FaChartLine;
+use dioxus_free_icons::Icon;
 
 #[component]
-pub fn TopArtists(artists: ReadSignal<Vec<Artist>>) -> Element {
+pub fn TopArtists(artists: ReadSignal<Vec<Artist>>, on_compare: Option<EventHandler<()>>) -> Element {
     let mut position = use_signal(|| (0.0, 0.0));
     let mut selected_artist = use_signal(|| None::<Artist>);
 
@@ -19,7 +21,17 @@ pub fn TopArtists(artists: ReadSignal<Vec<Artist>>) -> Element {
 			    });
 			},
 			style: "--position-x: {position().0}px; --position-y: {position().1}px;",
-			h2 { class: "section-title", "Top Artists" }
+			div { class: "top-header",
+				h2 { class: "section-title", "Top Artists" }
+				if let Some(on_compare) = on_compare {
+					button {
+						class: "top-save-button button",
+						onclick: move |_| on_compare.call(()),
+						Icon { icon: FaChartLine, width: 18, height: 18 }
+						"Compare Ranges"
+					}
+				}
+			}
 			div { class: "top-scroll-container",
 				for (index , artist) in artists().iter().enumerate() {
 					div {
@@ -0,0 +1,248 @@
+use crate::models::{time_range_label, Artist, Track};
+use crate::utils::*;
+use crate::AppContext;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::FaXmark;
+use dioxus_free_icons::Icon;
+
+/// Part of the comparison shown in the modal
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ComparisonView {
+    Movement,
+    Entered,
+    Dropped,
+    Staying,
+}
+
+impl ComparisonView {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Movement => "Movement",
+            Self::Entered => "New",
+            Self::Dropped => "Dropped",
+            Self::Staying => "Staying Power",
+        }
+    }
+
+    fn hint(&self) -> &'static str {
+        match self {
+            Self::Movement => "Your last 4 weeks compared to your all-time ranks.",
+            Self::Entered => "In your last 4 weeks but not in your all-time top 50.",
+            Self::Dropped => "In your all-time top 50 but not in your last 4 weeks.",
+            Self::Staying => "In all three time ranges, by average rank.",
+        }
+    }
+
+    fn select<'a, T: Clone>(&self, comparison: &'a RangeComparison<T>) -> Vec<&'a RankedItem<T>> {
+        match self {
+            Self::Movement => comparison.current(),
+            Self::Entered => comparison.entered(),
+            Self::Dropped => comparison.dropped(),
+            Self::Staying => comparison.staying(),
+        }
+    }
+}
+
+/// A track or artist prepared for display
+#[derive(Debug, Clone, PartialEq)]
+struct ComparisonRow {
+    id: String,
+    name: String,
+    detail: String,
+    image: Option<String>,
+    ranks: [Option<usize>; 3],
+    movement: RankMovement,
+}
+
+impl ComparisonRow {
+    fn from_track(ranked: &RankedItem<Track>) -> Self {
+        let track = &ranked.item;
+        Self {
+            id: track.id.clone(),
+            name: track.name.clone(),
+            detail: track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", "),
+            image: track.album.images.last().map(|i| i.url.clone()),
+            ranks: ranked.ranks,
+            movement: ranked.movement(),
+        }
+    }
+
+    fn from_artist(ranked: &RankedItem<Artist>) -> Self {
+        let artist = &ranked.item;
+        Self {
+            id: artist.id.clone(),
+            name: artist.name.clone(),
+            detail: artist.genres.iter().flatten().take(3).cloned().collect::<Vec<_>>().join(", "),
+            image: artist.images.as_ref().and_then(|images| images.last()).map(|i| i.url.clone()),
+            ranks: ranked.ranks,
+            movement: ranked.movement(),
+        }
+    }
+}
+
+/// Compares the top tracks or artists of all three time ranges
+#[component]
+pub fn RangeComparisonModal(kind: TopListKind, on_close: EventHandler<()>) -> Element {
+    let context = use_context::<AppContext>();
+    let spotify_client = context.spotify_client;
+
+    let mut kind = use_signal(|| kind);
+    let mut view = use_signal(|| ComparisonView::Movement);
+    let mut tracks = use_signal(|| None::<RangeComparison<Track>>);
+    let mut artists = use_signal(|| None::<RangeComparison<Artist>>);
+    let mut error = use_signal(|| None::<String>);
+
+    use_hook(move || {
+        let Some(client) = spotify_client.read().clone() else {
+            return;
+        };
+
+        spawn(async move {
+            match compare_top_tracks(&client).await {
+                Ok(comparison) => tracks.set(Some(comparison)),
+                Err(e) => {
+                    eprintln!("Failed to compare top tracks: {}", e);
+                    error.set(Some(format!("Failed to load top tracks: {}", e)));
+                }
+            }
+            match compare_top_artists(&client).await {
+                Ok(comparison) => artists.set(Some(comparison)),
+                Err(e) => {
+                    eprintln!("Failed to compare top artists: {}", e);
+                    error.set(Some(format!("Failed to load top artists: {}", e)));
+                }
+            }
+        });
+    });
+
+    let views = [ComparisonView::Movement, ComparisonView::Entered, ComparisonView::Dropped, ComparisonView::Staying];
+    // Rows and item count of every view for the selected list, None while loading
+    let sections: Option<Vec<(ComparisonView, Vec<ComparisonRow>)>> = match kind() {
+        TopListKind::Tracks => tracks.read().as_ref().map(|comparison| {
+            views
+                .iter()
+                .map(|v| (*v, v.select(comparison).into_iter().map(ComparisonRow::from_track).collect()))
+                .collect()
+        }),
+        TopListKind::Artists => artists.read().as_ref().map(|comparison| {
+            views
+                .iter()
+                .map(|v| (*v, v.select(comparison).into_iter().map(ComparisonRow::from_artist).collect()))
+                .collect()
+        }),
+    };
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/playlists.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/top.css") }
+		div { class: "modal-overlay", onclick: move |_| on_close.call(()),
+			div {
+				class: "modal-content range-comparison-modal",
+				onclick: move |e| e.stop_propagation(),
+
+				div { class: "modal-header",
+					h2 { "Compare Time Ranges" }
+					button {
+						class: "modal-close",
+						onclick: move |_| on_close.call(()),
+						Icon { icon: FaXmark, width: 20, height: 20 }
+					}
+				}
+
+				div { class: "modal-body",
+					div { class: "duplicate-strictness",
+						span { class: "duplicate-strictness-label", "Compare:" }
+						for option in [TopListKind::Tracks, TopListKind::Artists] {
+							button {
+								key: "{option.label()}",
+								class: if option == kind() { "strictness-button active" } else { "strictness-button" },
+								onclick: move |_| kind.set(option),
+								"{option.label()}"
+							}
+						}
+					}
+
+					if let Some(sections) = sections {
+						div { class: "duplicate-strictness",
+							for (option , rows) in sections.iter() {
+								button {
+									key: "{option.label()}",
+									class: if *option == view() { "strictness-button active" } else { "strictness-button" },
+									onclick: {
+									    let option = *option;
+									    move |_| view.set(option)
+									},
+									"{option.label()} ({rows.len()})"
+								}
+							}
+						}
+
+						p { class: "duplicate-preview-summary", "{view().hint()}" }
+
+						if let Some((_, rows)) = sections.iter().find(|(option, _)| *option == view()) {
+							if rows.is_empty() {
+								p { style: "text-align: center; padding: 20px;", "Nothing here." }
+							} else {
+								table { class: "range-comparison-table",
+									thead {
+										tr {
+											th { "" }
+											th { class: "range-comparison-name", "{kind().label()}" }
+											for range in TIME_RANGES {
+												th { key: "{range}", "{time_range_label(range)}" }
+											}
+										}
+									}
+									tbody {
+										for row in rows.iter() {
+											tr { key: "{row.id}",
+												td {
+													span { class: "rank-movement {row.movement.class()}",
+														"{row.movement.label()}"
+													}
+												}
+												td { class: "range-comparison-name",
+													div { class: "range-comparison-item",
+														if let Some(image) = &row.image {
+															img { src: "{image}", alt: "{row.name}" }
+														}
+														div { class: "range-comparison-text",
+															span { class: "top-name", "{row.name}" }
+															span { class: "top-artist", "{row.detail}" }
+														}
+													}
+												}
+												for (index , rank) in row.ranks.iter().enumerate() {
+													td { key: "{index}", class: "range-comparison-rank",
+														if let Some(rank) = rank {
+															"#{rank}"
+														} else {
+															"–"
+														}
+													}
+												}
+											}
+										}
+									}
+								}
+							}
+						}
+					} else if let Some(err) = error() {
+						p { style: "text-align: center; padding: 20px; color: #ff4444;", "{err}" }
+					} else {
+						p { style: "text-align: center; padding: 20px;", "Loading all three time ranges..." }
+					}
+				}
+
+				div { class: "modal-footer",
+					button {
+						class: "modal-button cancel-button",
+						onclick: move |_| on_close.call(()),
+						"Close"
+					}
+				}
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,233 @@
+use crate::models::{time_range_label, Track};
+use crate::utils::*;
+use crate::AppContext;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::FaXmark;
+use dioxus_free_icons::Icon;
+
+/// Save the top tracks, or a mix of the top artists' top tracks, as a dated playlist
+#[component]
+pub fn TopPlaylistModal(time_range: String, market: Option<String>, on_close: EventHandler<()>) -> Element {
+    let context = use_context::<AppContext>();
+    let spotify_client = context.spotify_client;
+
+    let mut options = use_signal(|| TopPlaylistOptions::new(&time_range));
+    let mut custom_name = use_signal(|| None::<String>);
+    // Collected tracks for the current options, cleared whenever they change
+    let mut preview = use_signal(|| None::<Vec<Track>>);
+    let mut loading = use_signal(|| false);
+    let mut creating = use_signal(|| false);
+
+    let mut update_options = move |update: Box<dyn FnOnce(&mut TopPlaylistOptions)>| {
+        update(&mut options.write());
+        preview.set(None);
+    };
+
+    let load_preview = {
+        let context = context.clone();
+        move |_| {
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+            let current = options();
+            let market = market.clone();
+            let context = context.clone();
+
+            loading.set(true);
+            spawn(async move {
+                match build_top_playlist(&client, &current, market.as_deref()).await {
+                    Ok(tracks) => preview.set(Some(tracks)),
+                    Err(e) => {
+                        eprintln!("Failed to collect top tracks: {}", e);
+                        show_error(&context, format!("Failed to collect top tracks: {}", e));
+                    }
+                }
+                loading.set(false);
+            });
+        }
+    };
+
+    let current = options();
+    let name = custom_name().unwrap_or_else(|| current.dated_name());
+
+    let create = {
+        let context = context.clone();
+        let name = name.clone();
+        let description = current.description();
+        move |_| {
+            let Some(client) = spotify_client.read().clone() else {
+                return;
+            };
+            let Some(tracks) = preview() else {
+                return;
+            };
+            let context = context.clone();
+            let name = name.clone();
+            let description = description.clone();
+
+            creating.set(true);
+            spawn(async move {
+                match save_tracks_as_playlist(&client, &name, &description, &tracks).await {
+                    Ok(_) => {
+                        show_success(&context, format!("Created \"{}\" with {} track(s)", name, tracks.len()));
+                        on_close.call(());
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to save top playlist: {}", e);
+                        show_error(&context, format!("Failed to create playlist: {}", e));
+                    }
+                }
+                creating.set(false);
+            });
+        }
+    };
+
+    let limit_label = match current.source {
+        TopPlaylistSource::TopTracks => "Tracks:",
+        TopPlaylistSource::TopArtistsMix => "Artists:",
+    };
+
+    rsx! {
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/playlists.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/duplicates.css") }
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/tools.css") }
+		div { class: "modal-overlay", onclick: move |_| on_close.call(()),
+			div {
+				class: "modal-content",
+				onclick: move |e| e.stop_propagation(),
+
+				div { class: "modal-header",
+					h2 { "Save as Playlist" }
+					button {
+						class: "modal-close",
+						onclick: move |_| on_close.call(()),
+						Icon { icon: FaXmark, width: 20, height: 20 }
+					}
+				}
+
+				div { class: "modal-body",
+					div { class: "duplicate-strictness",
+						span { class: "duplicate-strictness-label", "Source:" }
+						for source in [TopPlaylistSource::TopTracks, TopPlaylistSource::TopArtistsMix] {
+							button {
+								key: "{source.label()}",
+								class: if source == current.source { "strictness-button active" } else { "strictness-button" },
+								onclick: move |_| update_options(Box::new(move |o| o.source = source)),
+								"{source.label()}"
+							}
+						}
+					}
+
+					div { class: "duplicate-strictness",
+						span { class: "duplicate-strictness-label", "Time range:" }
+						for range in ["short_term", "medium_term", "long_term"] {
+							button {
+								key: "{range}",
+								class: if range == current.time_range { "strictness-button active" } else { "strictness-button" },
+								onclick: move |_| update_options(Box::new(move |o| o.time_range = range.to_string())),
+								"{time_range_label(range)}"
+							}
+						}
+					}
+
+					div { class: "duplicate-strictness",
+						span { class: "duplicate-strictness-label", "{limit_label}" }
+						input {
+							class: "tool-number",
+							r#type: "number",
+							min: "1",
+							max: "50",
+							value: "{current.limit}",
+							oninput: move |e| {
+							    if let Ok(value) = e.value().parse::<u32>() {
+							        update_options(Box::new(move |o| o.limit = value.clamp(1, 50)));
+							    }
+							},
+						}
+						if current.source == TopPlaylistSource::TopArtistsMix {
+							span { class: "duplicate-strictness-label", "Tracks per artist:" }
+							input {
+								class: "tool-number",
+								r#type: "number",
+								min: "1",
+								max: "10",
+								value: "{current.tracks_per_artist}",
+								oninput: move |e| {
+								    if let Ok(value) = e.value().parse::<usize>() {
+								        update_options(Box::new(move |o| o.tracks_per_artist = value.clamp(1, 10)));
+								    }
+								},
+							}
+						}
+					}
+
+					label { class: "tool-option",
+						input {
+							r#type: "checkbox",
+							checked: current.exclude_library,
+							onchange: move |e| {
+							    let checked = e.checked();
+							    update_options(Box::new(move |o| o.exclude_library = checked));
+							},
+						}
+						"Leave out tracks already in my library"
+					}
+
+					div { class: "form-group", style: "margin: 15px 0;",
+						label { "Name" }
+						input {
+							class: "form-input",
+							r#type: "text",
+							value: "{name}",
+							oninput: move |e| custom_name.set(Some(e.value())),
+						}
+					}
+
+					if loading() {
+						p { style: "text-align: center; padding: 20px;", "Collecting tracks..." }
+					} else if let Some(tracks) = preview() {
+						p { class: "duplicate-preview-summary", "{tracks.len()} track(s)" }
+						div { class: "duplicate-preview",
+							for (position , track) in tracks.iter().enumerate() {
+								div { class: "duplicate-preview-row", key: "{track.id}",
+									span { class: "duplicate-position", "#{position + 1}" }
+									span { class: "duplicate-preview-name",
+										"{track.name} – "
+										{track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", ")}
+									}
+								}
+							}
+						}
+					}
+				}
+
+				div { class: "modal-footer",
+					button {
+						class: "modal-button cancel-button",
+						onclick: move |_| on_close.call(()),
+						"Cancel"
+					}
+					if preview().is_none() {
+						button {
+							class: "modal-button remove-button",
+							disabled: loading(),
+							onclick: load_preview,
+							"Preview"
+						}
+					} else {
+						button {
+							class: "modal-button remove-button",
+							disabled: creating() || name.trim().is_empty() || preview().is_some_and(|t| t.is_empty()),
+							onclick: create,
+							if creating() {
+								"Creating..."
+							} else {
+								"Create Playlist"
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+}
\ No newline at end of file
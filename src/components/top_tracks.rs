@@ -2,9 +2,15 @@ use crate::components::TrackDetail;
 use crate::models::Track;
 use crate::utils::format_duration;
 use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::{FaChartLine, FaFloppyDisk};
+use dioxus_free_icons::Icon;
 
 #[component]
-pub fn TopTracks(tracks: ReadSignal<Vec<Track>>) -> Element {
+pub fn TopTracks(
+    tracks: ReadSignal<Vec<Track>>,
+    on_save: Option<EventHandler<()>>,
+    on_compare: Option<EventHandler<()>>,
+) -> Element {
     let mut position = use_signal(|| (0.0, 0.0));
     let mut selected_track = use_signal(|| None::<Track>);
 
@@ -20,7 +26,27 @@ pub fn TopTracks(tracks: ReadSignal<Vec<Track>>) -> Element {
 			    });
 			},
 			style: "--position-x: {position().0}px; --position-y: {position().1}px;",
-			h2 { class: "section-title", "Top Tracks" }
+			div { class: "top-header",
+				h2 { class: "section-title", "Top Tracks" }
+				div { class: "top-actions",
+					if let Some(on_compare) = on_compare {
+						button {
+							class: "top-save-button button",
+							onclick: move |_| on_compare.call(()),
+							Icon { icon: FaChartLine, width: 18, height: 18 }
+							"Compare Ranges"
+						}
+					}
+					if let Some(on_save) = on_save {
+						button {
+							class: "top-save-button button",
+							onclick: move |_| on_save.call(()),
+							Icon { icon: FaFloppyDisk, width: 18, height: 18 }
+							"Save as Playlist"
+						}
+					}
+				}
+			}
 			div { class: "top-scroll-container",
 				for (index , track) in tracks().iter().enumerate() {
 					div {
@@ -1,5 +1,6 @@
+use crate::components::RankTimelineChart;
 use crate::models::Track;
-use crate::utils::{format_duration, format_release_date};
+use crate::utils::{format_duration, format_release_date, TopListKind};
 use dioxus::prelude::*;
 use dioxus_free_icons::icons::fa_solid_icons::FaXmark;
 use dioxus_free_icons::icons::fa_brands_icons::FaSpotify;
@@ -8,7 +9,6 @@ use dioxus_free_icons::Icon;
 #[component]
 pub fn TrackDetail(track: Track, on_close: EventHandler<()>) -> Element {
     let spotify_url = track.external_urls.spotify.clone();
-    let track_uri = format!("spotify:track:{}", track.id);
     let artists = track.artists.iter()
         .map(|a| a.name.clone())
         .collect::<Vec<_>>()
@@ -57,6 +57,19 @@ pub fn TrackDetail(track: Track, on_close: EventHandler<()>) -> Element {
 							span { class: "metadata-value", "{track.album.name}" }
 						}
 
+						if track.track_number > 0 {
+							div { class: "metadata-item",
+								span { class: "metadata-label", "Track" }
+								span { class: "metadata-value",
+									if track.disc_number > 1 {
+										"{track.track_number} (disc {track.disc_number})"
+									} else {
+										"{track.track_number}"
+									}
+								}
+							}
+						}
+
 						div { class: "metadata-item",
 							span { class: "metadata-label", "Duration" }
 							span { class: "metadata-value", "{duration}" }
@@ -80,12 +93,29 @@ pub fn TrackDetail(track: Track, on_close: EventHandler<()>) -> Element {
 							}
 						}
 
+						if let Some(isrc) = &track.external_ids.isrc {
+							div { class: "metadata-item",
+								span { class: "metadata-label", "ISRC" }
+								span { class: "metadata-value uri", "{isrc}" }
+							}
+						}
+
 						div { class: "metadata-item",
 							span { class: "metadata-label", "Track URI" }
-							span { class: "metadata-value uri", "{track_uri}" }
+							span { class: "metadata-value uri", "{track.spotify_uri()}" }
+						}
+					}
+
+					if let Some(preview_url) = &track.preview_url {
+						audio {
+							class: "detail-preview",
+							controls: true,
+							src: "{preview_url}",
 						}
 					}
 
+					RankTimelineChart { kind: TopListKind::Tracks, id: track.id.clone() }
+
 					// Spotify button
 					a {
 						class: "detail-spotify-button",
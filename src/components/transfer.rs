@@ -0,0 +1,445 @@
+use crate::api::SpotifyClient;
+use crate::models::*;
+use crate::oauth;
+use crate::utils::*;
+use crate::{Route, AppContext};
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons::{FaArrowLeft, FaRightLeft};
+use dioxus_free_icons::Icon;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+#[component]
+pub fn Transfer() -> Element {
+    let context = use_context::<AppContext>();
+    let nav = navigator();
+    // Local to the wizard, so swapping the direction doesn't log the rest of the app into the
+    // other account. The connected second account is still kept in the context.
+    let mut secondary_client = context.secondary_client;
+    let mut source_client = use_signal(|| context.spotify_client.read().clone());
+    let mut target_client = use_signal(|| secondary_client.read().clone());
+
+    let mut source_user = use_signal(|| None::<User>);
+    let mut target_user = use_signal(|| None::<User>);
+    let mut source_playlists = use_signal(Vec::<Playlist>::new);
+    let mut selected_playlists = use_signal(HashSet::<String>::new);
+    let mut copy_saved_tracks = use_signal(|| false);
+    let mut copy_followed_artists = use_signal(|| false);
+    let mut transfer_state = use_signal(load_transfer_state);
+    let mut connecting = use_signal(|| false);
+    let mut running = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    if context.spotify_client.read().is_none() {
+        use_effect(move || {
+            nav.push(Route::Home {});
+        });
+
+        return rsx! {
+			div { class: "loading", "Redirecting to login..." }
+		};
+    }
+
+    // Load the source account whenever it changes (e.g. after swapping)
+    use_effect(move || {
+        let Some(client) = source_client.read().clone() else {
+            return;
+        };
+
+        spawn(async move {
+            match client.get_current_user().await {
+                Ok(user) => source_user.set(Some(user)),
+                Err(e) => error.set(Some(format!("Failed to load source account: {}", e))),
+            }
+            match client.get_all_playlists().await {
+                Ok(playlists) => source_playlists.set(playlists),
+                Err(e) => error.set(Some(format!("Failed to load playlists: {}", e))),
+            }
+        });
+    });
+
+    // Load the target account once it is connected
+    use_effect(move || {
+        let Some(client) = target_client.read().clone() else {
+            target_user.set(None);
+            return;
+        };
+
+        spawn(async move {
+            match client.get_current_user().await {
+                Ok(user) => target_user.set(Some(user)),
+                Err(e) => error.set(Some(format!("Failed to load target account: {}", e))),
+            }
+        });
+    });
+
+    let connect_target = move |_| {
+        let (client_id, client_secret, redirect_uri) = oauth::credentials_from_env();
+        connecting.set(true);
+        error.set(None);
+
+        spawn(async move {
+            match oauth::login(client_id, client_secret, redirect_uri, true).await {
+                Ok(client) => {
+                    let client = Some(Rc::new(client));
+                    secondary_client.set(client.clone());
+                    target_client.set(client);
+                }
+                Err(e) => {
+                    error.set(Some(e));
+                }
+            }
+            connecting.set(false);
+        });
+    };
+
+    let swap_accounts = move |_| {
+        let source = source_client.read().clone();
+        let target = target_client.read().clone();
+        if target.is_some() {
+            source_client.set(target);
+            target_client.set(source);
+            selected_playlists.write().clear();
+            source_playlists.set(Vec::new());
+        }
+    };
+
+    let start_transfer = {
+        let context = context.clone();
+        move |_| {
+            let source = source_client.read().clone();
+            let target = target_client.read().clone();
+            let (Some(source), Some(target)) = (source, target) else {
+                return;
+            };
+
+            let selected = selected_playlists();
+            let playlists: Vec<Playlist> = source_playlists()
+                .into_iter()
+                .filter(|p| selected.contains(&p.id))
+                .collect();
+            let saved_tracks = copy_saved_tracks();
+            let followed_artists = copy_followed_artists();
+            let ctx = context.clone();
+
+            running.set(true);
+            error.set(None);
+
+            spawn(async move {
+                match plan_transfer(&source, &target, playlists, saved_tracks, followed_artists).await {
+                    Ok(state) => {
+                        save_transfer_state(&state);
+                        transfer_state.set(Some(state.clone()));
+                        execute_transfer(source, target, state, transfer_state, ctx).await;
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to prepare transfer: {}", e);
+                        error.set(Some(format!("Failed to prepare transfer: {}", e)));
+                    }
+                }
+                running.set(false);
+            });
+        }
+    };
+
+    let resume_transfer = {
+        let context = context.clone();
+        move |_| {
+            let source = source_client.read().clone();
+            let target = target_client.read().clone();
+            let (Some(source), Some(target), Some(state)) = (source, target, transfer_state()) else {
+                return;
+            };
+
+            // The stored transfer belongs to a specific pair of accounts
+            let source_matches = source_user().map(|u| u.id == state.source_user_id).unwrap_or(false);
+            let target_matches = target_user().map(|u| u.id == state.target_user_id).unwrap_or(false);
+            if !source_matches || !target_matches {
+                error.set(Some(format!(
+                    "This transfer goes from {} to {}. Connect those accounts to resume it.",
+                    state.source_user_name, state.target_user_name
+                )));
+                return;
+            }
+
+            let ctx = context.clone();
+            running.set(true);
+            error.set(None);
+
+            spawn(async move {
+                execute_transfer(source, target, state, transfer_state, ctx).await;
+                running.set(false);
+            });
+        }
+    };
+
+    let discard_transfer = move |_| {
+        clear_transfer_state();
+        transfer_state.set(None);
+    };
+
+    let has_target = target_client.read().is_some();
+    let selected_count = selected_playlists().len();
+    let can_start = has_target && !running() && (selected_count > 0 || copy_saved_tracks() || copy_followed_artists());
+
+    rsx! {
+		document::Link {
+			rel: "stylesheet",
+			href: asset!("assets/compiled/playlist_detail.css"),
+		}
+		document::Link { rel: "stylesheet", href: asset!("assets/compiled/tools.css") }
+		div { class: "tool-container",
+			header { class: "tool-header",
+				button {
+					class: "back-button",
+					onclick: move |_| {
+					    nav.push(Route::Dashboard {});
+					},
+					Icon { icon: FaArrowLeft, width: 42, height: 42 }
+				}
+				h1 { class: "tool-title", "Transfer Between Accounts" }
+			}
+
+			if let Some(err) = error() {
+				div { class: "error-message",
+					p { "Error: {err}" }
+				}
+			}
+
+			// Accounts
+			div { class: "tool-section",
+				h2 { class: "tool-section-title", "Accounts" }
+				div { class: "tool-row",
+					div { class: "account-card",
+						div { class: "account-label", "From (source)" }
+						div { class: "account-name",
+							{source_user().and_then(|u| u.display_name.or(Some(u.id))).unwrap_or_else(|| "Loading...".to_string())}
+						}
+					}
+					button {
+						class: "back-button",
+						title: "Swap accounts",
+						disabled: !has_target || running(),
+						onclick: swap_accounts,
+						Icon { icon: FaRightLeft, width: 28, height: 28 }
+					}
+					div { class: "account-card",
+						div { class: "account-label", "To (target)" }
+						if let Some(user) = target_user() {
+							div { class: "account-name",
+								{user.display_name.clone().unwrap_or(user.id.clone())}
+							}
+							button {
+								class: "button outline",
+								disabled: running(),
+								onclick: move |_| {
+								    let disconnected = target_client.write().take();
+								    let is_secondary = disconnected
+								        .zip(secondary_client.read().clone())
+								        .is_some_and(|(disconnected, secondary)| Rc::ptr_eq(&disconnected, &secondary));
+								    if is_secondary {
+								        secondary_client.set(None);
+								    }
+								},
+								"Disconnect"
+							}
+						} else {
+							button {
+								class: "button",
+								disabled: connecting(),
+								onclick: connect_target,
+								if connecting() {
+									"Waiting for login..."
+								} else {
+									"Connect Account"
+								}
+							}
+						}
+					}
+				}
+				p { class: "tool-hint",
+					"Spotify will ask which account to use, log in with the account you want to copy to."
+				}
+			}
+
+			if let Some(state) = transfer_state() {
+				// Progress of the current or last transfer
+				div { class: "tool-section",
+					h2 { class: "tool-section-title",
+						"Transfer from {state.source_user_name} to {state.target_user_name}"
+					}
+					p { class: "tool-hint",
+						"{state.completed_jobs()} of {state.jobs.len()} playlist(s) copied"
+					}
+					div { class: "tool-list",
+						for job in state.jobs.iter() {
+							div {
+								class: "tool-list-item",
+								key: "{job.source_playlist_id}",
+								span { class: "item-name", "{job.name}" }
+								span { class: "item-meta", "{job.tracks_added}/{job.uris.len()} tracks" }
+								TransferStatusBadge { status: job.status }
+							}
+							if let Some(job_error) = &job.error {
+								p { class: "tool-hint", "{job_error}" }
+							}
+						}
+						if state.copy_saved_tracks {
+							div { class: "tool-list-item",
+								span { class: "item-name", "Saved tracks" }
+								TransferStatusBadge {
+									status: if state.saved_tracks_done { TransferStatus::Done } else { TransferStatus::Pending },
+								}
+							}
+						}
+						if state.copy_followed_artists {
+							div { class: "tool-list-item",
+								span { class: "item-name", "Followed artists" }
+								TransferStatusBadge {
+									status: if state.followed_artists_done { TransferStatus::Done } else { TransferStatus::Pending },
+								}
+							}
+						}
+					}
+
+					if !state.unavailable.is_empty() {
+						h3 { class: "tool-section-title", style: "margin-top: var(--gap-small);",
+							"{state.unavailable.len()} track(s) unavailable in the target account's market"
+						}
+						div { class: "tool-list",
+							for (index , track) in state.unavailable.iter().enumerate() {
+								div { class: "tool-list-item", key: "{track.uri}-{index}",
+									span { class: "item-name", "{track.track_name} – {track.artists}" }
+									span { class: "item-meta", "{track.playlist_name}" }
+								}
+							}
+						}
+					}
+
+					div { class: "tool-row", style: "margin-top: var(--gap-small);",
+						if !state.is_complete() {
+							button {
+								class: "button secondary",
+								disabled: running() || !has_target,
+								onclick: resume_transfer,
+								if running() {
+									"Transferring..."
+								} else {
+									"Resume Transfer"
+								}
+							}
+						}
+						button {
+							class: "button outline",
+							disabled: running(),
+							onclick: discard_transfer,
+							if state.is_complete() {
+								"New Transfer"
+							} else {
+								"Discard"
+							}
+						}
+					}
+				}
+			} else {
+				// Selection of what to copy
+				div { class: "tool-section",
+					h2 { class: "tool-section-title", "What to copy" }
+					div { class: "tool-row",
+						label { class: "tool-option",
+							input {
+								r#type: "checkbox",
+								checked: copy_saved_tracks(),
+								onchange: move |e| copy_saved_tracks.set(e.checked()),
+							}
+							"Saved tracks"
+						}
+						label { class: "tool-option",
+							input {
+								r#type: "checkbox",
+								checked: copy_followed_artists(),
+								onchange: move |e| copy_followed_artists.set(e.checked()),
+							}
+							"Followed artists"
+						}
+						span { class: "tool-hint", "{selected_count} playlist(s) selected" }
+					}
+					div { class: "tool-list",
+						for playlist in source_playlists().iter() {
+							label {
+								class: "tool-list-item tool-option",
+								key: "{playlist.id}",
+								input {
+									r#type: "checkbox",
+									checked: selected_playlists().contains(&playlist.id),
+									onchange: {
+									    let playlist_id = playlist.id.clone();
+									    move |e: FormEvent| {
+									        if e.checked() {
+									            selected_playlists.write().insert(playlist_id.clone());
+									        } else {
+									            selected_playlists.write().remove(&playlist_id);
+									        }
+									    }
+									},
+								}
+								span { class: "item-name", "{playlist.name}" }
+								span { class: "item-meta", "{playlist.tracks.total} tracks" }
+							}
+						}
+					}
+					div { class: "tool-row", style: "margin-top: var(--gap-small);",
+						button {
+							class: "button secondary",
+							disabled: !can_start,
+							onclick: start_transfer,
+							if running() {
+								"Preparing..."
+							} else {
+								"Start Transfer"
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+#[component]
+fn TransferStatusBadge(status: TransferStatus) -> Element {
+    let (class_name, label) = match status {
+        TransferStatus::Pending => ("status-badge", "Pending"),
+        TransferStatus::InProgress => ("status-badge in-progress", "In progress"),
+        TransferStatus::Done => ("status-badge done", "Done"),
+        TransferStatus::Failed => ("status-badge failed", "Failed"),
+    };
+
+    rsx! {
+		span { class: "{class_name}", "{label}" }
+	}
+}
+
+/// Run the transfer, keeping the UI and the persisted state in sync after every step
+async fn execute_transfer(
+    source: Rc<SpotifyClient>,
+    target: Rc<SpotifyClient>,
+    mut state: TransferState,
+    mut transfer_state: Signal<Option<TransferState>>,
+    context: AppContext,
+) {
+    let mut on_progress = move |state: &TransferState| {
+        save_transfer_state(state);
+        transfer_state.set(Some(state.clone()));
+    };
+
+    match run_transfer(&source, &target, &mut state, &mut on_progress).await {
+        Ok(_) => {
+            show_success(&context, format!("Transferred {} playlist(s)", state.completed_jobs()));
+        }
+        Err(e) => {
+            eprintln!("Transfer failed: {}", e);
+            show_error(&context, format!("Transfer stopped: {}. You can resume it later.", e));
+        }
+    }
+}
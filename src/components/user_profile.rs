@@ -1,49 +1,42 @@
+use crate::components::{GenreBreakdown, TasteMetricsPanel};
 use crate::models::User;
-use crate::utils::format_country;
+use crate::utils::*;
 use crate::{Route, AppContext};
 use dioxus::prelude::*;
-use dioxus_free_icons::icons::fa_solid_icons::{FaFileArrowUp, FaRightFromBracket, FaArrowUpRightFromSquare};
+use dioxus_free_icons::icons::fa_solid_icons::{
+    FaFileArrowUp, FaRightFromBracket, FaArrowUpRightFromSquare, FaBoxArchive, FaClockRotateLeft,
+};
 use dioxus_free_icons::Icon;
 
 #[component]
 pub fn UserProfile(
     user: ReadSignal<Option<User>>,
     on_import: EventHandler<()>,
+    on_backup: EventHandler<()>,
+    on_restore: EventHandler<()>,
     time_range: ReadSignal<String>,
 ) -> Element {
     let context = use_context::<AppContext>();
     let nav = navigator();
     let mut position = use_signal(|| (0.0, 0.0));
-    let mut top_genres = use_signal(|| Vec::<String>::new());
+    // Top lists of every time range, shared by the genre and taste sections
+    let mut top_ranges = use_signal(|| None::<Vec<RangeGenres>>);
 
-    // Fetch top genres when component mounts or time range changes
-    use_effect(move || {
-        spawn(async move {
-            if let Some(client) = context.spotify_client.read().as_ref() {
-                // Get top 50 artists for better genre coverage
-                if let Ok(artists) = client.get_top_artists(50, &time_range()).await {
-                    // Extract and count genres
-                    let mut genre_count: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
-
-                    for artist in artists {
-                        if let Some(genres) = artist.genres {
-                            for genre in genres {
-                                *genre_count.entry(genre).or_insert(0) += 1;
-                            }
-                        }
-                    }
-
-                    // Sort by count and take top 10
-                    let mut genres: Vec<(String, usize)> = genre_count.into_iter().collect();
-                    genres.sort_by(|a, b| b.1.cmp(&a.1));
-
-                    let top_10: Vec<String> = genres.into_iter()
-                        .take(10)
-                        .map(|(genre, _)| genre)
-                        .collect();
+    use_hook(move || {
+        let Some(client) = context.spotify_client.read().clone() else {
+            // Demo mode has a single set of top lists for every range
+            let lists = TIME_RANGES
+                .iter()
+                .map(|range| (range.to_string(), get_mock_top_artists(), get_mock_top_tracks()))
+                .collect();
+            top_ranges.set(Some(range_genres(lists, &Default::default())));
+            return;
+        };
 
-                    top_genres.set(top_10);
-                }
+        spawn(async move {
+            match fetch_range_genres(&client).await {
+                Ok(fetched) => top_ranges.set(Some(fetched)),
+                Err(e) => eprintln!("Failed to load top lists: {}", e),
             }
         });
     });
@@ -105,6 +98,18 @@ rsx! {
 						Icon { icon: FaFileArrowUp, width: 18, height: 18 }
 						"Import Playlist"
 					}
+					button {
+						class: "backup-button button",
+						onclick: move |_| on_backup.call(()),
+						Icon { icon: FaBoxArchive, width: 18, height: 18 }
+						"Backup Library"
+					}
+					button {
+						class: "restore-button button",
+						onclick: move |_| on_restore.call(()),
+						Icon { icon: FaClockRotateLeft, width: 18, height: 18 }
+						"Restore Backup"
+					}
 					button {
 						class: "spotify-button button",
 						onclick: move |_| {
@@ -126,6 +131,7 @@ rsx! {
 						    move |_| {
 						        // Clear the authenticated session
 						        ctx.spotify_client.set(None);
+						        ctx.secondary_client.set(None);
 						        // Reset demo mode flag
 						        ctx.demo_mode.set(false);
 						        // Navigate back to login screen
@@ -138,23 +144,8 @@ rsx! {
 				}
 			}
 
-			// Top Genres Section
-			div { class: "top-genres-section",
-				h3 { class: "genres-title", "Top Genres" }
-
-				if top_genres().is_empty() {
-					div { class: "genres-loading", "Loading genres..." }
-				} else {
-					div { class: "genres-list",
-						for (index , genre) in top_genres().iter().enumerate() {
-							div { class: "genre-tag", key: "{genre}-{index}",
-								span { class: "genre-rank", "#{index + 1}" }
-								span { class: "genre-name", "{genre}" }
-							}
-						}
-					}
-				}
-			}
+			GenreBreakdown { ranges: top_ranges, time_range }
+			TasteMetricsPanel { ranges: top_ranges, time_range }
 		} else {
 			div { class: "loading", "Loading user data..." }
 		}
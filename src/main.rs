@@ -5,7 +5,8 @@ mod oauth;
 mod utils;
 
 use crate::api::SpotifyClient;
-use crate::components::{Toast, ToastContainer, Callback, Home, Dashboard, PlaylistDetail};
+use crate::components::{Toast, ToastContainer, Callback, Home, Dashboard, PlaylistDetail, Transfer, History, SmartPlaylists, TasteCompare, LinkPasteLayout};
+use crate::models::JournalEntry;
 use dioxus::prelude::*;
 use std::rc::Rc;
 
@@ -13,9 +14,13 @@ use std::rc::Rc;
 #[derive(Clone)]
 struct AppContext {
     spotify_client: Signal<Option<Rc<SpotifyClient>>>,
+    // Second logged in account, used as target for transfers
+    secondary_client: Signal<Option<Rc<SpotifyClient>>>,
     demo_mode: Signal<bool>,
     toasts: Signal<Vec<Toast>>,
     toast_counter: Signal<usize>,
+    // Undoable playlist operations, persisted in the data directory
+    operation_journal: Signal<Vec<JournalEntry>>,
 }
 
 fn main() {
@@ -30,9 +35,11 @@ fn App() -> Element {
     // Initialize global Spotify client context
     use_context_provider(|| AppContext {
         spotify_client: Signal::new(None),
+        secondary_client: Signal::new(None),
         demo_mode: Signal::new(false),
         toasts: Signal::new(Vec::new()),
         toast_counter: Signal::new(0),
+        operation_journal: Signal::new(utils::load_journal()),
     });
 
     let context = use_context::<AppContext>();
@@ -50,8 +57,18 @@ enum Route {
     Home {},
     #[route("/callback")]
     Callback {},
-    #[route("/dashboard")]
-    Dashboard {},
-    #[route("/playlist/:id")]
-    PlaylistDetail { id: String },
+    // Pages that need a logged in client share the link paste field
+    #[layout(LinkPasteLayout)]
+        #[route("/dashboard")]
+        Dashboard {},
+        #[route("/playlist/:id")]
+        PlaylistDetail { id: String },
+        #[route("/transfer")]
+        Transfer {},
+        #[route("/history")]
+        History {},
+        #[route("/smart")]
+        SmartPlaylists {},
+        #[route("/compare")]
+        TasteCompare {},
 }
\ No newline at end of file
@@ -0,0 +1,64 @@
+use crate::models::User;
+use serde::{Deserialize, Serialize};
+
+/// Version written into every library backup, bumped when the format changes
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Snapshot of a whole Spotify library as written by "Backup Library"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryBackup {
+    pub version: u32,
+    pub created_at: String,
+    pub profile: User,
+    pub playlists: Vec<PlaylistBackup>,
+    pub followed_artists: Vec<BackupItem>,
+    pub saved_tracks: Vec<BackupItem>,
+    pub saved_albums: Vec<BackupItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaylistBackup {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub owner_id: String,
+    #[serde(default)]
+    pub owner_name: Option<String>,
+    #[serde(default)]
+    pub public: bool,
+    #[serde(default)]
+    pub collaborative: bool,
+    /// Tracks in playlist order
+    pub tracks: Vec<BackupTrack>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupTrack {
+    pub uri: String,
+    pub name: String,
+    #[serde(default)]
+    pub artists: String,
+    #[serde(default)]
+    pub added_at: String,
+}
+
+/// Artist, track or album saved in the library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupItem {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub added_at: Option<String>,
+}
+
+impl LibraryBackup {
+    /// Playlists created by the backed up account (as opposed to followed ones)
+    pub fn owned_playlists(&self) -> impl DoubleEndedIterator<Item = &PlaylistBackup> {
+        self.playlists.iter().filter(move |p| p.owner_id == self.profile.id)
+    }
+
+    pub fn followed_playlists(&self) -> impl DoubleEndedIterator<Item = &PlaylistBackup> {
+        self.playlists.iter().filter(move |p| p.owner_id != self.profile.id)
+    }
+}
@@ -0,0 +1,463 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// Length of the base62 IDs Spotify uses for everything except users
+const BASE62_ID_LENGTH: usize = 22;
+
+/// Host of the links the Spotify apps share
+const SPOTIFY_LINK_HOST: &str = "open.spotify.com";
+
+/// Kind of Spotify object an ID refers to, as it appears in URIs and URLs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdKind {
+    Track,
+    Album,
+    Artist,
+    Playlist,
+    Episode,
+    User,
+}
+
+impl IdKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Track => "track",
+            Self::Album => "album",
+            Self::Artist => "artist",
+            Self::Playlist => "playlist",
+            Self::Episode => "episode",
+            Self::User => "user",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<IdKind> {
+        match name {
+            "track" => Some(Self::Track),
+            "album" => Some(Self::Album),
+            "artist" => Some(Self::Artist),
+            "playlist" => Some(Self::Playlist),
+            "episode" => Some(Self::Episode),
+            "user" => Some(Self::User),
+            _ => None,
+        }
+    }
+
+    /// Whether `id` is well-formed for this kind. User IDs are usernames, all others base62.
+    fn is_valid_id(&self, id: &str) -> bool {
+        match self {
+            Self::User => !id.is_empty() && !id.contains(|c: char| c.is_whitespace() || c == ':' || c == '/'),
+            _ => id.len() == BASE62_ID_LENGTH && id.chars().all(|c| c.is_ascii_alphanumeric()),
+        }
+    }
+}
+
+impl fmt::Display for IdKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Why a string could not be read as a Spotify ID
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdError {
+    Empty,
+    /// Not a valid ID of the expected kind, e.g. the wrong length
+    InvalidId { kind: IdKind, id: String },
+    /// A URI or URL of another kind, e.g. an episode where a track was expected
+    WrongKind { expected: IdKind, found: String },
+    /// Neither a `spotify:` URI, an open.spotify.com URL nor a bare ID
+    Unrecognized(String),
+    /// A bare ID where the kind of object can't be told
+    MissingKind(String),
+    /// A link to something other than the supported kinds, e.g. a show
+    UnsupportedKind(String),
+}
+
+impl fmt::Display for IdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "No Spotify link or ID given"),
+            Self::InvalidId { kind, id } => write!(f, "\"{}\" is not a valid Spotify {} ID", id, kind),
+            Self::WrongKind { expected, found } => write!(f, "Expected a {} link but got a {} link", expected, found),
+            Self::Unrecognized(input) => write!(f, "\"{}\" is not a Spotify link, URI or ID", input),
+            Self::MissingKind(id) => write!(f, "\"{}\" is a bare ID, paste the full link instead", id),
+            Self::UnsupportedKind(kind) => write!(f, "Spotify {} links are not supported", kind),
+        }
+    }
+}
+
+impl std::error::Error for IdError {}
+
+/// Object kind of a [`SpotifyId`]
+pub trait IdType {
+    const KIND: IdKind;
+}
+
+/// Kind markers, never constructed
+pub enum TrackType {}
+pub enum AlbumType {}
+pub enum ArtistType {}
+pub enum PlaylistType {}
+pub enum EpisodeType {}
+pub enum UserType {}
+
+impl IdType for TrackType {
+    const KIND: IdKind = IdKind::Track;
+}
+
+impl IdType for AlbumType {
+    const KIND: IdKind = IdKind::Album;
+}
+
+impl IdType for ArtistType {
+    const KIND: IdKind = IdKind::Artist;
+}
+
+impl IdType for PlaylistType {
+    const KIND: IdKind = IdKind::Playlist;
+}
+
+impl IdType for EpisodeType {
+    const KIND: IdKind = IdKind::Episode;
+}
+
+impl IdType for UserType {
+    const KIND: IdKind = IdKind::User;
+}
+
+pub type TrackId = SpotifyId<TrackType>;
+pub type AlbumId = SpotifyId<AlbumType>;
+pub type ArtistId = SpotifyId<ArtistType>;
+pub type PlaylistId = SpotifyId<PlaylistType>;
+pub type EpisodeId = SpotifyId<EpisodeType>;
+pub type UserId = SpotifyId<UserType>;
+
+/// Validated ID of a Spotify object of kind `T`. `Display` prints the bare ID, so it can be
+/// formatted straight into API URLs.
+pub struct SpotifyId<T: IdType> {
+    id: String,
+    kind: PhantomData<T>,
+}
+
+impl<T: IdType> SpotifyId<T> {
+    /// Read a `spotify:` URI, an open.spotify.com URL or a bare ID
+    pub fn parse(input: &str) -> Result<Self, IdError> {
+        let (kind, id) = split_reference(input)?;
+        match kind {
+            Some(kind) if kind != T::KIND.as_str() => Err(IdError::WrongKind { expected: T::KIND, found: kind }),
+            _ => Self::from_id(&id),
+        }
+    }
+
+    /// Validate a bare ID
+    pub fn from_id(id: &str) -> Result<Self, IdError> {
+        if id.is_empty() {
+            return Err(IdError::Empty);
+        }
+        if !T::KIND.is_valid_id(id) {
+            return Err(IdError::InvalidId { kind: T::KIND, id: id.to_string() });
+        }
+        Ok(Self::new_unchecked(id))
+    }
+
+    /// Wrap an ID that came from the API itself and needs no validation
+    pub fn new_unchecked(id: impl Into<String>) -> Self {
+        Self { id: id.into(), kind: PhantomData }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.id
+    }
+
+    pub fn uri(&self) -> String {
+        format!("spotify:{}:{}", T::KIND.as_str(), self.id)
+    }
+}
+
+// Implemented by hand, derives would require the kind markers to implement the traits too
+
+impl<T: IdType> Clone for SpotifyId<T> {
+    fn clone(&self) -> Self {
+        Self::new_unchecked(self.id.clone())
+    }
+}
+
+impl<T: IdType> PartialEq for SpotifyId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T: IdType> Eq for SpotifyId<T> {}
+
+impl<T: IdType> Hash for SpotifyId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<T: IdType> fmt::Debug for SpotifyId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.uri())
+    }
+}
+
+impl<T: IdType> fmt::Display for SpotifyId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.id)
+    }
+}
+
+impl<T: IdType> FromStr for SpotifyId<T> {
+    type Err = IdError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse(input)
+    }
+}
+
+/// Any Spotify object a URI or link can point to
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpotifyUri {
+    Track(TrackId),
+    Album(AlbumId),
+    Artist(ArtistId),
+    Playlist(PlaylistId),
+    Episode(EpisodeId),
+    User(UserId),
+}
+
+impl SpotifyUri {
+    /// Read a `spotify:` URI or open.spotify.com URL. Bare IDs are rejected, their kind is unknown.
+    pub fn parse(input: &str) -> Result<Self, IdError> {
+        let (kind, id) = split_reference(input)?;
+        let Some(kind) = kind else {
+            return Err(IdError::MissingKind(id));
+        };
+
+        match IdKind::from_name(&kind) {
+            Some(IdKind::Track) => Ok(Self::Track(TrackId::from_id(&id)?)),
+            Some(IdKind::Album) => Ok(Self::Album(AlbumId::from_id(&id)?)),
+            Some(IdKind::Artist) => Ok(Self::Artist(ArtistId::from_id(&id)?)),
+            Some(IdKind::Playlist) => Ok(Self::Playlist(PlaylistId::from_id(&id)?)),
+            Some(IdKind::Episode) => Ok(Self::Episode(EpisodeId::from_id(&id)?)),
+            Some(IdKind::User) => Ok(Self::User(UserId::from_id(&id)?)),
+            None => Err(IdError::UnsupportedKind(kind)),
+        }
+    }
+
+    pub fn kind(&self) -> IdKind {
+        match self {
+            Self::Track(_) => IdKind::Track,
+            Self::Album(_) => IdKind::Album,
+            Self::Artist(_) => IdKind::Artist,
+            Self::Playlist(_) => IdKind::Playlist,
+            Self::Episode(_) => IdKind::Episode,
+            Self::User(_) => IdKind::User,
+        }
+    }
+
+    /// Read every link in a pasted text. Links may be separated by whitespace or commas, or not
+    /// at all when several were pasted into a single-line field.
+    pub fn parse_all(text: &str) -> Vec<Result<Self, IdError>> {
+        let separated = text.replace("https://", " https://").replace("spotify:", " spotify:");
+        separated
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|part| !part.is_empty())
+            .map(Self::parse)
+            .collect()
+    }
+
+    pub fn uri(&self) -> String {
+        match self {
+            Self::Track(id) => id.uri(),
+            Self::Album(id) => id.uri(),
+            Self::Artist(id) => id.uri(),
+            Self::Playlist(id) => id.uri(),
+            Self::Episode(id) => id.uri(),
+            Self::User(id) => id.uri(),
+        }
+    }
+}
+
+/// Playlist entry as the playlist item endpoints take it. Local files can be removed, but not
+/// added through the API.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PlayableUri {
+    Track(TrackId),
+    Episode(EpisodeId),
+    /// `spotify:local:artist:album:title:duration`, which has no ID to validate
+    Local(String),
+}
+
+impl PlayableUri {
+    /// Read a track, episode or local file URI, e.g. one stored in a backup or the journal
+    pub fn parse(input: &str) -> Result<Self, IdError> {
+        let input = input.trim();
+        if input.starts_with("spotify:local:") {
+            if input.contains(char::is_whitespace) {
+                return Err(IdError::Unrecognized(input.to_string()));
+            }
+            return Ok(Self::Local(input.to_string()));
+        }
+
+        match SpotifyUri::parse(input)? {
+            SpotifyUri::Track(id) => Ok(Self::Track(id)),
+            SpotifyUri::Episode(id) => Ok(Self::Episode(id)),
+            other => Err(IdError::UnsupportedKind(other.kind().to_string())),
+        }
+    }
+
+    /// Whether the entry can be added to a playlist
+    pub fn is_addable(&self) -> bool {
+        !matches!(self, Self::Local(_))
+    }
+
+    pub fn uri(&self) -> String {
+        match self {
+            Self::Track(id) => id.uri(),
+            Self::Episode(id) => id.uri(),
+            Self::Local(uri) => uri.clone(),
+        }
+    }
+}
+
+impl fmt::Display for PlayableUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.uri())
+    }
+}
+
+/// Split a URI or URL into its kind and ID. Bare IDs have no kind.
+///
+/// Accepts `spotify:track:ID`, the legacy `spotify:user:NAME:playlist:ID` and links like
+/// `https://open.spotify.com/intl-de/track/ID?si=...`. Links to any other host are rejected.
+fn split_reference(input: &str) -> Result<(Option<String>, String), IdError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(IdError::Empty);
+    }
+    let unrecognized = || IdError::Unrecognized(input.to_string());
+
+    if let Some(rest) = input.strip_prefix("spotify:") {
+        let parts: Vec<&str> = rest.split(':').collect();
+        return find_reference(&parts).map(|(kind, id)| (Some(kind), id)).ok_or_else(unrecognized);
+    }
+
+    // Links are often copied without the scheme
+    let link = if input.starts_with(SPOTIFY_LINK_HOST) { format!("https://{}", input) } else { input.to_string() };
+    if let Ok(url) = url::Url::parse(&link) {
+        if !matches!(url.scheme(), "http" | "https") || url.host_str() != Some(SPOTIFY_LINK_HOST) {
+            return Err(unrecognized());
+        }
+        let segments: Vec<&str> = url
+            .path_segments()
+            .map(|segments| {
+                segments
+                    .filter(|segment| !segment.is_empty() && *segment != "embed" && !segment.starts_with("intl-"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        return find_reference(&segments).map(|(kind, id)| (Some(kind), id)).ok_or_else(unrecognized);
+    }
+
+    if input.contains([':', '/', ' ']) {
+        return Err(unrecognized());
+    }
+    Ok((None, input.to_string()))
+}
+
+/// Kind and ID in the parts of a URI or link path. The first known kind and the ID after it win,
+/// unless another known kind follows directly, so `user/NAME/playlist/ID` is the playlist and
+/// `artist/ID/discography/all` is the artist. Without a known kind the first pair is returned, to
+/// be reported as unsupported.
+fn find_reference(parts: &[&str]) -> Option<(String, String)> {
+    let mut found = None;
+    let mut rest = parts;
+    while let [kind, id, remaining @ ..] = rest {
+        if IdKind::from_name(kind).is_some() {
+            found = Some((kind.to_string(), id.to_string()));
+            rest = remaining;
+        } else if found.is_some() {
+            break;
+        } else {
+            rest = &rest[1..];
+        }
+    }
+
+    match parts {
+        [kind, id, ..] if found.is_none() => Some((kind.to_string(), id.to_string())),
+        _ => found,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "4uLU6hMCjMI75M1A2tKUQC";
+
+    #[test]
+    fn parses_uris() {
+        assert_eq!(
+            SpotifyUri::parse(&format!("spotify:track:{}", ID)),
+            Ok(SpotifyUri::Track(TrackId::new_unchecked(ID)))
+        );
+        assert_eq!(
+            SpotifyUri::parse(&format!("spotify:user:someone:playlist:{}", ID)),
+            Ok(SpotifyUri::Playlist(PlaylistId::new_unchecked(ID)))
+        );
+        assert_eq!(
+            TrackId::parse(&format!("spotify:episode:{}", ID)),
+            Err(IdError::WrongKind { expected: IdKind::Track, found: "episode".to_string() })
+        );
+    }
+
+    #[test]
+    fn parses_links() {
+        for link in [
+            format!("https://open.spotify.com/track/{}", ID),
+            format!("https://open.spotify.com/intl-de/track/{}?si=abc123", ID),
+            format!("https://open.spotify.com/embed/track/{}", ID),
+            format!("open.spotify.com/track/{}?si=abc123", ID),
+        ] {
+            assert_eq!(SpotifyUri::parse(&link), Ok(SpotifyUri::Track(TrackId::new_unchecked(ID))), "{}", link);
+        }
+    }
+
+    #[test]
+    fn ignores_path_after_the_id() {
+        assert_eq!(
+            SpotifyUri::parse(&format!("https://open.spotify.com/artist/{}/discography/all", ID)),
+            Ok(SpotifyUri::Artist(ArtistId::new_unchecked(ID)))
+        );
+        assert_eq!(
+            SpotifyUri::parse(&format!("https://open.spotify.com/user/someone/playlist/{}/foo", ID)),
+            Ok(SpotifyUri::Playlist(PlaylistId::new_unchecked(ID)))
+        );
+        assert_eq!(
+            SpotifyUri::parse(&format!("https://open.spotify.com/show/{}", ID)),
+            Err(IdError::UnsupportedKind("show".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_bare_ids() {
+        assert_eq!(TrackId::parse(ID), Ok(TrackId::new_unchecked(ID)));
+        assert_eq!(SpotifyUri::parse(ID), Err(IdError::MissingKind(ID.to_string())));
+        assert!(matches!(TrackId::parse("too-short"), Err(IdError::InvalidId { .. })));
+    }
+
+    #[test]
+    fn rejects_other_hosts() {
+        for link in [
+            format!("https://example.com/track/{}", ID),
+            format!("https://open.spotify.com.example.com/track/{}", ID),
+            format!("ftp://open.spotify.com/track/{}", ID),
+        ] {
+            assert!(matches!(SpotifyUri::parse(&link), Err(IdError::Unrecognized(_))), "{}", link);
+        }
+    }
+}
\ No newline at end of file
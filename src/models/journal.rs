@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// Track removed or added by an operation, with its position in the playlist at the time
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JournalTrack {
+    pub uri: String,
+    pub position: usize,
+    pub name: String,
+}
+
+/// Destructive playlist operation recorded so it can be undone
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JournalEntry {
+    pub id: String,
+    pub created_at: String,
+    /// What the user did, e.g. "Removed duplicates"
+    pub description: String,
+    pub playlist_id: String,
+    pub playlist_name: String,
+    /// Snapshot before the operation, if it was known
+    pub snapshot_before: Option<String>,
+    /// Snapshot returned by the operation, used to detect later edits
+    pub snapshot_after: Option<String>,
+    pub removed: Vec<JournalTrack>,
+    /// Tracks inserted by the operation, removed again on undo
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<JournalTrack>,
+    /// Track URIs before a reorder, restored on undo
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_uris: Vec<String>,
+    #[serde(default)]
+    pub undone: bool,
+}
\ No newline at end of file
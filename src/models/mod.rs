@@ -1,3 +1,19 @@
+pub mod backup;
+pub mod ids;
+pub mod journal;
+pub mod playlist_history;
+pub mod smart_playlist;
 pub mod spotify;
+pub mod taste_profile;
+pub mod top_history;
+pub mod transfer;
 
+pub use backup::*;
+pub use ids::*;
+pub use journal::*;
+pub use playlist_history::*;
+pub use smart_playlist::*;
 pub use spotify::*;
+pub use taste_profile::*;
+pub use top_history::*;
+pub use transfer::*;
\ No newline at end of file
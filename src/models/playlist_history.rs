@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// Track as stored in a playlist snapshot
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotTrack {
+    pub uri: String,
+    pub name: String,
+    pub artists: String,
+    pub added_at: String,
+    /// User ID from `added_by`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added_by: Option<String>,
+}
+
+/// Track list of a playlist at one `snapshot_id`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlaylistSnapshot {
+    pub snapshot_id: String,
+    /// When InfoSpot first saw this snapshot
+    pub captured_at: String,
+    pub tracks: Vec<SnapshotTrack>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrackChangeKind {
+    Added,
+    Removed,
+    Moved { from: usize },
+}
+
+/// One difference between two consecutive snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackChange {
+    pub kind: TrackChangeKind,
+    pub track: SnapshotTrack,
+    /// Position in the newer snapshot, or in the older one for removed tracks
+    pub position: usize,
+}
+
+/// Changes between a snapshot and the one before it
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistChangeSet {
+    pub snapshot_id: String,
+    pub captured_at: String,
+    pub changes: Vec<TrackChange>,
+}
+
+impl PlaylistChangeSet {
+    /// Number of added, removed and moved tracks
+    pub fn counts(&self) -> (usize, usize, usize) {
+        self.changes.iter().fold((0, 0, 0), |(added, removed, moved), change| match change.kind {
+            TrackChangeKind::Added => (added + 1, removed, moved),
+            TrackChangeKind::Removed => (added, removed + 1, moved),
+            TrackChangeKind::Moved { .. } => (added, removed, moved + 1),
+        })
+    }
+}
\ No newline at end of file
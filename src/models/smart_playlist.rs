@@ -0,0 +1,264 @@
+use serde::{Deserialize, Serialize};
+
+/// Where a smart playlist takes its candidate tracks from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum RuleSource {
+    /// Top tracks of "short_term", "medium_term" or "long_term"
+    TopTracks { time_range: String },
+    SavedTracks,
+    RecentlyPlayed,
+    Playlist { id: String, name: String },
+}
+
+impl RuleSource {
+    /// Time ranges offered for the top tracks source
+    pub fn time_ranges() -> [&'static str; 3] {
+        ["short_term", "medium_term", "long_term"]
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::TopTracks { time_range } => format!("Top tracks ({})", time_range_label(time_range)),
+            Self::SavedTracks => "Saved tracks".to_string(),
+            Self::RecentlyPlayed => "Recently played".to_string(),
+            Self::Playlist { name, .. } => format!("Playlist \"{}\"", name),
+        }
+    }
+}
+
+pub fn time_range_label(time_range: &str) -> &'static str {
+    match time_range {
+        "short_term" => "last 4 weeks",
+        "long_term" => "all time",
+        _ => "last 6 months",
+    }
+}
+
+/// Track, artist, album or history value a condition looks at
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum RuleField {
+    TrackName,
+    ArtistName,
+    AlbumName,
+    Genre,
+    ReleaseYear,
+    Popularity,
+    DurationSeconds,
+    Explicit,
+    /// Year the track was added to a playlist or the library
+    AddedYear,
+    /// Rank in the top tracks source, 1 is the most played
+    TopRank,
+    /// Plays recorded in the local listening history
+    PlayCount,
+}
+
+impl RuleField {
+    pub fn all() -> [RuleField; 11] {
+        [
+            Self::TrackName,
+            Self::ArtistName,
+            Self::AlbumName,
+            Self::Genre,
+            Self::ReleaseYear,
+            Self::Popularity,
+            Self::DurationSeconds,
+            Self::Explicit,
+            Self::AddedYear,
+            Self::TopRank,
+            Self::PlayCount,
+        ]
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::TrackName => "track_name",
+            Self::ArtistName => "artist_name",
+            Self::AlbumName => "album_name",
+            Self::Genre => "genre",
+            Self::ReleaseYear => "release_year",
+            Self::Popularity => "popularity",
+            Self::DurationSeconds => "duration",
+            Self::Explicit => "explicit",
+            Self::AddedYear => "added_year",
+            Self::TopRank => "top_rank",
+            Self::PlayCount => "play_count",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<RuleField> {
+        Self::all().into_iter().find(|field| field.key() == key)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::TrackName => "Track name",
+            Self::ArtistName => "Artist",
+            Self::AlbumName => "Album",
+            Self::Genre => "Genre",
+            Self::ReleaseYear => "Release year",
+            Self::Popularity => "Popularity",
+            Self::DurationSeconds => "Duration (seconds)",
+            Self::Explicit => "Explicit",
+            Self::AddedYear => "Year added",
+            Self::TopRank => "Top rank",
+            Self::PlayCount => "Play count",
+        }
+    }
+
+    pub fn kind(&self) -> FieldKind {
+        match self {
+            Self::TrackName | Self::ArtistName | Self::AlbumName | Self::Genre => FieldKind::Text,
+            Self::Explicit => FieldKind::Flag,
+            _ => FieldKind::Number,
+        }
+    }
+
+    pub fn operators(&self) -> &'static [RuleOperator] {
+        match self.kind() {
+            FieldKind::Text => &[
+                RuleOperator::Contains,
+                RuleOperator::NotContains,
+                RuleOperator::Equals,
+                RuleOperator::NotEquals,
+            ],
+            FieldKind::Flag => &[RuleOperator::Equals],
+            FieldKind::Number => &[
+                RuleOperator::LessThan,
+                RuleOperator::GreaterThan,
+                RuleOperator::Equals,
+                RuleOperator::NotEquals,
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldKind {
+    Text,
+    Number,
+    Flag,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum RuleOperator {
+    Equals,
+    NotEquals,
+    LessThan,
+    GreaterThan,
+    Contains,
+    NotContains,
+}
+
+impl RuleOperator {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Equals => "eq",
+            Self::NotEquals => "ne",
+            Self::LessThan => "lt",
+            Self::GreaterThan => "gt",
+            Self::Contains => "contains",
+            Self::NotContains => "not_contains",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<RuleOperator> {
+        [
+            Self::Equals,
+            Self::NotEquals,
+            Self::LessThan,
+            Self::GreaterThan,
+            Self::Contains,
+            Self::NotContains,
+        ]
+        .into_iter()
+        .find(|operator| operator.key() == key)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Equals => "is",
+            Self::NotEquals => "is not",
+            Self::LessThan => "less than",
+            Self::GreaterThan => "greater than",
+            Self::Contains => "contains",
+            Self::NotContains => "does not contain",
+        }
+    }
+}
+
+/// A single filter such as "release year less than 2000"
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RuleCondition {
+    pub field: RuleField,
+    pub operator: RuleOperator,
+    pub value: String,
+}
+
+/// Saved definition of a rule-based playlist
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SmartPlaylist {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub sources: Vec<RuleSource>,
+    pub conditions: Vec<RuleCondition>,
+    /// Every condition has to match when set, otherwise any of them
+    pub match_all: bool,
+    /// Maximum number of tracks, in source order
+    pub limit: Option<usize>,
+    /// Spotify playlist created by the last materialization
+    #[serde(default)]
+    pub target_playlist_id: Option<String>,
+    #[serde(default)]
+    pub last_materialized: Option<String>,
+}
+
+impl SmartPlaylist {
+    pub fn new(id: String) -> Self {
+        Self {
+            id,
+            name: "New Smart Playlist".to_string(),
+            description: String::new(),
+            sources: vec![RuleSource::TopTracks {
+                time_range: "medium_term".to_string(),
+            }],
+            conditions: Vec::new(),
+            match_all: true,
+            limit: Some(50),
+            target_playlist_id: None,
+            last_materialized: None,
+        }
+    }
+
+    /// Short human readable form, e.g. "Top tracks (last 6 months) · release year less than 2000"
+    pub fn summary(&self) -> String {
+        let sources = self.sources.iter().map(|s| s.label()).collect::<Vec<_>>().join(" + ");
+        let joiner = if self.match_all { " and " } else { " or " };
+        let conditions = self
+            .conditions
+            .iter()
+            .map(|c| format!("{} {} {}", c.field.label().to_lowercase(), c.operator.label(), c.value))
+            .collect::<Vec<_>>()
+            .join(joiner);
+
+        let mut summary = sources;
+        if !conditions.is_empty() {
+            summary.push_str(&format!(" · {}", conditions));
+        }
+        if let Some(limit) = self.limit {
+            summary.push_str(&format!(" · max {}", limit));
+        }
+        summary
+    }
+}
+
+/// One play from the recently played history
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlayRecord {
+    pub track_id: String,
+    pub played_at: String,
+    /// Empty for plays recorded before artists were stored
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artist_ids: Vec<String>,
+}
\ No newline at end of file
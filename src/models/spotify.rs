@@ -1,3 +1,4 @@
+use crate::models::{ArtistId, EpisodeId, PlayableUri, PlaylistId, TrackId};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -10,6 +11,15 @@ pub struct User {
     pub country: Option<String>,
 }
 
+/// Profile of another user, as returned by the users endpoint
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PublicUser {
+    pub id: String,
+    pub display_name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub images: Vec<Image>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Image {
     pub url: String,
@@ -25,6 +35,8 @@ pub struct Followers {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Track {
     pub id: String,
+    #[serde(default)]
+    pub uri: String,
     pub name: String,
     pub artists: Vec<Artist>,
     pub album: Album,
@@ -32,6 +44,81 @@ pub struct Track {
     pub external_urls: ExternalUrls,
     pub popularity: Option<u32>,
     pub explicit: bool,
+    #[serde(default)]
+    pub external_ids: ExternalIds,
+    /// Only present when the track was requested with a `market`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_playable: Option<bool>,
+    /// Track that was requested when Spotify relinked it to a version available in the market
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linked_from: Option<LinkedTrack>,
+    /// Why the track can't be played in the market
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Restrictions>,
+    #[serde(default)]
+    pub track_number: u32,
+    #[serde(default)]
+    pub disc_number: u32,
+    #[serde(default)]
+    pub is_local: bool,
+    /// 30 second MP3 preview, missing for many tracks
+    #[serde(default)]
+    pub preview_url: Option<String>,
+    /// Only present when the track was requested without a `market`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub available_markets: Vec<String>,
+}
+
+impl Track {
+    pub fn track_id(&self) -> TrackId {
+        TrackId::new_unchecked(self.id.as_str())
+    }
+
+    /// `spotify:track:` URI of the track. Data stored before the URI was kept has none, which
+    /// falls back to the ID so those tracks don't all share an empty URI.
+    pub fn spotify_uri(&self) -> String {
+        if self.uri.is_empty() {
+            self.track_id().uri()
+        } else {
+            self.uri.clone()
+        }
+    }
+
+    /// URI for adding the track to a playlist
+    pub fn playable_uri(&self) -> PlayableUri {
+        PlayableUri::Track(self.track_id())
+    }
+
+    /// Whether Spotify reported the track as not playable in the requested market
+    pub fn is_unplayable(&self) -> bool {
+        self.is_playable == Some(false)
+    }
+
+    /// ID the track has in playlists, which differs from `id` for relinked tracks
+    pub fn original_id(&self) -> &str {
+        self.linked_from.as_ref().map(|linked| linked.id.as_str()).unwrap_or(&self.id)
+    }
+
+    pub fn restriction_label(&self) -> &'static str {
+        match self.restrictions.as_ref().map(|r| r.reason.as_str()) {
+            Some("market") => "Not available in your country",
+            Some("product") => "Not available with your subscription",
+            Some("explicit") => "Blocked by your explicit content setting",
+            _ => "Not playable",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LinkedTrack {
+    pub id: String,
+    #[serde(default)]
+    pub uri: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Restrictions {
+    pub reason: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -45,6 +132,12 @@ pub struct Artist {
     pub popularity: Option<u32>,
 }
 
+impl Artist {
+    pub fn artist_id(&self) -> ArtistId {
+        ArtistId::new_unchecked(self.id.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Album {
     pub id: String,
@@ -58,6 +151,13 @@ pub struct ExternalUrls {
     pub spotify: String,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ExternalIds {
+    /// International Standard Recording Code, shared by every release of the same recording
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isrc: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default)]
 pub struct Playlist {
@@ -84,6 +184,12 @@ pub struct Playlist {
     pub uri: Option<String>,
 }
 
+impl Playlist {
+    pub fn playlist_id(&self) -> PlaylistId {
+        PlaylistId::new_unchecked(self.id.as_str())
+    }
+}
+
 // Custom deserializer to convert null to empty Vec
 fn deserialize_null_default<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
 where
@@ -170,21 +276,299 @@ pub struct TopArtistsResponse {
     pub items: Vec<Artist>,
 }
 
+/// Body of a "change playlist details" request
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlaylistDetailsUpdate {
+    pub name: String,
+    pub description: String,
+    pub public: bool,
+    pub collaborative: bool,
+}
+
+impl PlaylistDetailsUpdate {
+    pub fn from_playlist(playlist: &Playlist) -> Self {
+        Self {
+            name: playlist.name.clone(),
+            description: unescape_html(playlist.description.as_deref().unwrap_or_default()),
+            public: playlist.public.unwrap_or(false),
+            collaborative: playlist.collaborative.unwrap_or(false),
+        }
+    }
+}
+
+/// Spotify returns descriptions HTML-escaped, sending them back as-is would double escape them
+pub(crate) fn unescape_html(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#x27;", "'")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&#x2F;", "/")
+        .replace("&amp;", "&")
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlaylistsResponse {
     pub items: Vec<Playlist>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PlaylistTracksResponse {
-    pub items: Vec<PlaylistTrackItem>,
-    pub total: u32,
+pub struct SnapshotResponse {
+    pub snapshot_id: String,
 }
 
+/// Generic paging object returned by list endpoints
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Paging<T> {
+    pub items: Vec<T>,
+    #[serde(default)]
+    pub next: Option<String>,
+    #[serde(default)]
+    pub total: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PlaylistTrackItem {
     pub added_at: String,
-    pub track: Track,
+    /// User who added the track, missing for very old playlists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added_by: Option<PlaylistOwner>,
+    #[serde(default)]
+    pub track: PlaylistItem,
+}
+
+impl PlaylistTrackItem {
+    /// The Spotify track, if this entry is one
+    pub fn as_track(&self) -> Option<&Track> {
+        self.track.as_track()
+    }
+}
+
+/// What a playlist entry holds. Besides tracks, playlists can contain podcast episodes,
+/// local files (no Spotify ID) and entries whose track was removed from Spotify (`track: null`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum PlaylistItem {
+    /// Boxed as full track objects are much larger than the other variants
+    Track(Box<Track>),
+    Episode(Episode),
+    Local(LocalTrack),
+    #[default]
+    Unavailable,
+    /// Entry with an ID that didn't match any model, kept so the rest of the playlist still loads
+    Invalid { id: String, error: String },
+}
+
+impl PlaylistItem {
+    pub fn as_track(&self) -> Option<&Track> {
+        match self {
+            Self::Track(track) => Some(track.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// URI used to add or remove the entry, unavailable entries have none
+    pub fn uri(&self) -> Option<String> {
+        self.playable_uri().map(|uri| uri.uri())
+    }
+
+    /// Typed URI of the entry as stored in the playlist, for removing or re-adding it
+    pub fn playable_uri(&self) -> Option<PlayableUri> {
+        match self {
+            Self::Track(track) => Some(PlayableUri::Track(TrackId::new_unchecked(track.original_id()))),
+            Self::Episode(episode) => Some(PlayableUri::Episode(EpisodeId::new_unchecked(episode.id.as_str()))),
+            Self::Local(local) => Some(PlayableUri::Local(local.uri.clone())),
+            Self::Unavailable | Self::Invalid { .. } => None,
+        }
+    }
+
+    /// URI for adding the entry to a playlist; local files can't be added through the API
+    pub fn addable_uri(&self) -> Option<PlayableUri> {
+        self.playable_uri().filter(PlayableUri::is_addable)
+    }
+
+    /// Key that is unique per entry kind, for lists and grouping
+    pub fn key(&self) -> String {
+        match self {
+            Self::Track(track) => track.id.clone(),
+            Self::Episode(episode) => episode.id.clone(),
+            Self::Local(local) => local.uri.clone(),
+            Self::Unavailable => "unavailable".to_string(),
+            Self::Invalid { id, .. } => format!("invalid:{}", id),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Track(track) => &track.name,
+            Self::Episode(episode) => &episode.name,
+            Self::Local(local) => &local.name,
+            Self::Unavailable => "Unavailable track",
+            Self::Invalid { .. } => "Unreadable entry",
+        }
+    }
+
+    /// Artists of a track or local file, the show of an episode
+    pub fn artist_names(&self) -> String {
+        match self {
+            Self::Track(track) => track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", "),
+            Self::Episode(episode) => episode.show.as_ref().map(|show| show.name.clone()).unwrap_or_default(),
+            Self::Local(local) => local.artists.join(", "),
+            Self::Unavailable => String::new(),
+            Self::Invalid { error, .. } => error.clone(),
+        }
+    }
+
+    pub fn first_artist_name(&self) -> String {
+        match self {
+            Self::Track(track) => track.artists.first().map(|a| a.name.clone()).unwrap_or_default(),
+            Self::Local(local) => local.artists.first().cloned().unwrap_or_default(),
+            _ => self.artist_names(),
+        }
+    }
+
+    pub fn album_name(&self) -> &str {
+        match self {
+            Self::Track(track) => &track.album.name,
+            Self::Episode(episode) => episode.show.as_ref().map(|show| show.name.as_str()).unwrap_or_default(),
+            Self::Local(local) => &local.album,
+            Self::Unavailable | Self::Invalid { .. } => "",
+        }
+    }
+
+    pub fn duration_ms(&self) -> u32 {
+        match self {
+            Self::Track(track) => track.duration_ms,
+            Self::Episode(episode) => episode.duration_ms,
+            Self::Local(local) => local.duration_ms,
+            Self::Unavailable | Self::Invalid { .. } => 0,
+        }
+    }
+
+    pub fn image_url(&self) -> Option<&str> {
+        match self {
+            Self::Track(track) => track.album.images.first().map(|image| image.url.as_str()),
+            Self::Episode(episode) => episode.images.first().map(|image| image.url.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Badge shown for entries that are not regular tracks
+    pub fn kind_label(&self) -> Option<&'static str> {
+        match self {
+            Self::Track(_) => None,
+            Self::Episode(_) => Some("Episode"),
+            Self::Local(_) => Some("Local file"),
+            Self::Unavailable => Some("Unavailable"),
+            Self::Invalid { .. } => Some("Unreadable"),
+        }
+    }
+}
+
+/// Type written for `PlaylistItem::Invalid`, so stored entries read back with their error
+const INVALID_ITEM_TYPE: &str = "invalid";
+
+impl<'de> Deserialize<'de> for PlaylistItem {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Some(value) = Option::<serde_json::Value>::deserialize(deserializer)? else {
+            return Ok(Self::Unavailable);
+        };
+
+        if value.get("is_local").and_then(|l| l.as_bool()).unwrap_or(false) {
+            return Ok(LocalTrack::from_value(&value).map(Self::Local).unwrap_or(Self::Unavailable));
+        }
+        // Removed or region locked entries come without an ID and cannot be played or referenced
+        let Some(id) = value.get("id").and_then(|id| id.as_str()).map(str::to_string) else {
+            return Ok(Self::Unavailable);
+        };
+
+        // Anything else that doesn't parse is a model mismatch. It must not pass as unavailable,
+        // nor fail the whole playlist, so the entry keeps the error to be shown in its place.
+        let item = match value.get("type").and_then(|t| t.as_str()) {
+            Some(INVALID_ITEM_TYPE) => {
+                let error = value.get("error").and_then(|e| e.as_str()).unwrap_or_default().to_string();
+                return Ok(Self::Invalid { id, error });
+            }
+            Some("episode") => serde_json::from_value(value).map(Self::Episode),
+            _ => serde_json::from_value(value).map(|track| Self::Track(Box::new(track))),
+        };
+        Ok(item.unwrap_or_else(|e| Self::Invalid { id, error: e.to_string() }))
+    }
+}
+
+impl Serialize for PlaylistItem {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Track(track) => track.serialize(serializer),
+            Self::Episode(episode) => {
+                let mut value = serde_json::to_value(episode).map_err(serde::ser::Error::custom)?;
+                value["type"] = serde_json::Value::from("episode");
+                value.serialize(serializer)
+            }
+            Self::Local(local) => local.to_value().serialize(serializer),
+            Self::Unavailable => serializer.serialize_none(),
+            Self::Invalid { id, error } => {
+                serde_json::json!({ "type": INVALID_ITEM_TYPE, "id": id, "error": error }).serialize(serializer)
+            }
+        }
+    }
+}
+
+/// Podcast episode in a playlist
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Episode {
+    pub id: String,
+    pub name: String,
+    pub duration_ms: u32,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub images: Vec<Image>,
+    #[serde(default)]
+    pub release_date: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub show: Option<ShowSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShowSummary {
+    pub id: String,
+    pub name: String,
+}
+
+/// File from the user's computer added to a playlist, it has a URI but no Spotify ID
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalTrack {
+    pub uri: String,
+    pub name: String,
+    pub artists: Vec<String>,
+    pub album: String,
+    pub duration_ms: u32,
+}
+
+impl LocalTrack {
+    /// Read the fields of a track object with `is_local: true`, whose IDs are all null
+    fn from_value(value: &serde_json::Value) -> Option<Self> {
+        let text = |value: &serde_json::Value| value.as_str().unwrap_or_default().to_string();
+        Some(Self {
+            uri: value.get("uri")?.as_str()?.to_string(),
+            name: text(&value["name"]),
+            artists: value["artists"]
+                .as_array()
+                .map(|artists| artists.iter().map(|artist| text(&artist["name"])).collect())
+                .unwrap_or_default(),
+            album: text(&value["album"]["name"]),
+            duration_ms: value["duration_ms"].as_u64().unwrap_or(0) as u32,
+        })
+    }
+
+    fn to_value(&self) -> serde_json::Value {
+        serde_json::json!({
+            "is_local": true,
+            "uri": self.uri,
+            "name": self.name,
+            "artists": self.artists.iter().map(|name| serde_json::json!({ "name": name })).collect::<Vec<_>>(),
+            "album": { "name": self.album },
+            "duration_ms": self.duration_ms,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -196,4 +580,21 @@ pub struct RecentlyPlayedResponse {
 pub struct RecentlyPlayedItem {
     pub track: Track,
     pub played_at: String,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedTrack {
+    pub added_at: String,
+    pub track: Track,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedAlbum {
+    pub added_at: String,
+    pub album: Album,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowedArtistsResponse {
+    pub artists: Paging<Artist>,
+}
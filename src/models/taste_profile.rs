@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Version written into every taste profile, bumped when the format changes
+pub const TASTE_PROFILE_VERSION: u32 = 1;
+
+/// Track or artist in a taste profile
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TasteProfileItem {
+    pub rank: usize,
+    pub id: String,
+    pub name: String,
+    /// Artists of a track, or the main genres of an artist
+    #[serde(default)]
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TasteProfileGenre {
+    pub genre: String,
+    /// Fraction of the range's genre weight, between 0 and 1
+    pub share: f64,
+}
+
+/// Top lists of one time range, in rank order
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TasteProfileRange {
+    pub time_range: String,
+    pub artists: Vec<TasteProfileItem>,
+    pub tracks: Vec<TasteProfileItem>,
+    pub genres: Vec<TasteProfileGenre>,
+}
+
+/// Shareable summary of someone's listening, as written by "Export Taste Profile"
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TasteProfile {
+    pub version: u32,
+    pub exported_at: String,
+    pub user_id: String,
+    pub display_name: String,
+    pub ranges: Vec<TasteProfileRange>,
+}
+
+impl TasteProfile {
+    pub fn range(&self, time_range: &str) -> Option<&TasteProfileRange> {
+        self.ranges.iter().find(|r| r.time_range == time_range)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+/// Track as stored in a top list snapshot
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TopSnapshotTrack {
+    pub id: String,
+    pub name: String,
+    pub artists: String,
+    #[serde(default)]
+    pub artist_ids: Vec<String>,
+}
+
+/// Artist as stored in a top list snapshot
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TopSnapshotArtist {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub genres: Vec<String>,
+}
+
+/// Top tracks and artists of one time range, in rank order
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TopRangeSnapshot {
+    pub time_range: String,
+    pub tracks: Vec<TopSnapshotTrack>,
+    pub artists: Vec<TopSnapshotArtist>,
+}
+
+impl TopRangeSnapshot {
+    /// 1-based rank of a track
+    pub fn track_rank(&self, id: &str) -> Option<usize> {
+        self.tracks.iter().position(|t| t.id == id).map(|index| index + 1)
+    }
+
+    /// 1-based rank of an artist
+    pub fn artist_rank(&self, id: &str) -> Option<usize> {
+        self.artists.iter().position(|a| a.id == id).map(|index| index + 1)
+    }
+}
+
+/// Top lists of every time range on one day
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TopSnapshot {
+    /// Local date as `YYYY-MM-DD`
+    pub date: String,
+    pub captured_at: String,
+    pub ranges: Vec<TopRangeSnapshot>,
+}
+
+impl TopSnapshot {
+    pub fn range(&self, time_range: &str) -> Option<&TopRangeSnapshot> {
+        self.ranges.iter().find(|r| r.time_range == time_range)
+    }
+}
+
+/// Ranks of one item on one snapshot day
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankPoint {
+    pub date: String,
+    /// Rank per time range, from the shortest to the longest. `None` when not in that list.
+    pub ranks: [Option<usize>; 3],
+}
+
+/// How a track's or artist's rank changed over the stored snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankTimeline {
+    /// One point per snapshot day, oldest first
+    pub points: Vec<RankPoint>,
+    /// First and last day the item was in any of the lists
+    pub first_appeared: Option<String>,
+    pub last_seen: Option<String>,
+}
+
+impl RankTimeline {
+    /// Best rank ever reached in any time range
+    pub fn peak(&self) -> Option<usize> {
+        self.points.iter().flat_map(|p| p.ranks.iter().flatten()).min().copied()
+    }
+}
\ No newline at end of file
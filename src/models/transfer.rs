@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum TransferStatus {
+    Pending,
+    InProgress,
+    Done,
+    Failed,
+}
+
+/// Copy of one source playlist into the target account
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TransferJob {
+    pub source_playlist_id: String,
+    pub name: String,
+    pub description: String,
+    pub public: bool,
+    pub collaborative: bool,
+    /// Track URIs in source order, captured when the transfer was planned
+    pub uris: Vec<String>,
+    /// Set once the playlist has been created in the target account
+    pub target_playlist_id: Option<String>,
+    /// Number of URIs already added, used to resume after a failure
+    pub tracks_added: usize,
+    pub status: TransferStatus,
+    pub error: Option<String>,
+}
+
+/// Track that will be copied but cannot be played in the target account's market
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UnavailableTrack {
+    pub playlist_name: String,
+    pub track_name: String,
+    pub artists: String,
+    pub uri: String,
+}
+
+/// Persisted state of a cross-account transfer so it can be resumed after failures
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TransferState {
+    pub source_user_id: String,
+    pub source_user_name: String,
+    pub target_user_id: String,
+    pub target_user_name: String,
+    pub created_at: String,
+    pub jobs: Vec<TransferJob>,
+    pub copy_saved_tracks: bool,
+    pub copy_followed_artists: bool,
+    #[serde(default)]
+    pub saved_tracks_done: bool,
+    #[serde(default)]
+    pub followed_artists_done: bool,
+    #[serde(default)]
+    pub unavailable: Vec<UnavailableTrack>,
+}
+
+impl TransferState {
+    pub fn is_complete(&self) -> bool {
+        self.jobs.iter().all(|job| job.status == TransferStatus::Done)
+            && (!self.copy_saved_tracks || self.saved_tracks_done)
+            && (!self.copy_followed_artists || self.followed_artists_done)
+    }
+
+    pub fn completed_jobs(&self) -> usize {
+        self.jobs.iter().filter(|job| job.status == TransferStatus::Done).count()
+    }
+}
@@ -1,3 +1,5 @@
+use crate::api::SpotifyClient;
+use crate::utils::generate_random_string;
 use std::net::TcpListener;
 use std::io::{Read, Write};
 use std::sync::mpsc;
@@ -45,6 +47,50 @@ pub fn start_callback_server() -> Result<String, Box<dyn std::error::Error>> {
     }
 }
 
+/// Read the Spotify app credentials (client id, client secret, redirect URI) from the environment
+pub fn credentials_from_env() -> (String, String, String) {
+    let client_id = std::env::var("SPOTIFY_CLIENT_ID")
+        .unwrap_or_else(|_| "".to_string());
+    let client_secret = std::env::var("SPOTIFY_CLIENT_SECRET")
+        .unwrap_or_else(|_| "".to_string());
+    let redirect_uri = std::env::var("SPOTIFY_REDIRECT_URI")
+        .unwrap_or_else(|_| "http://127.0.0.1:8888/callback".to_string());
+
+    (client_id, client_secret, redirect_uri)
+}
+
+/// Run the browser login flow and return an authenticated client.
+/// `show_dialog` lets the user pick a different account than the one logged in on spotify.com.
+pub async fn login(
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+    show_dialog: bool,
+) -> Result<SpotifyClient, String> {
+    let spotify_client = SpotifyClient::new(client_id, client_secret, redirect_uri);
+
+    let state = generate_random_string(16);
+    let auth_url = spotify_client.get_auth_url(&state, show_dialog);
+
+    // Open browser
+    if let Err(e) = open::that(&auth_url) {
+        eprintln!("Failed to open browser: {}", e);
+        return Err(format!("Failed to open browser: {}", e));
+    }
+
+    // Wait for callback
+    let code = start_callback_server().map_err(|e| format!("Callback error: {}", e))?;
+
+    // Exchange code for token
+    let token_response = spotify_client
+        .exchange_code(&code)
+        .await
+        .map_err(|e| format!("Authentication failed: {}", e))?;
+    spotify_client.set_token(token_response.access_token).await;
+
+    Ok(spotify_client)
+}
+
 fn extract_code_from_request(request: &str) -> Option<String> {
     // Parse the request line to get the URL
     let first_line = request.lines().next()?;
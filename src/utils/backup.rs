@@ -0,0 +1,316 @@
+use crate::api::SpotifyClient;
+use crate::models::*;
+use crate::utils::{create_zip_from_directory, sanitize_filename};
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::io::Read;
+
+/// Name of the manifest inside a backup archive
+const LIBRARY_FILE: &str = "library.json";
+
+/// Which parts of a backup should be restored
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RestoreOptions {
+    pub playlists: bool,
+    /// Also skip backed up playlists named like one the account already owns. Playlists that
+    /// still exist are always skipped by ID.
+    pub skip_same_name: bool,
+    pub followed_artists: bool,
+    pub saved_tracks: bool,
+    pub saved_albums: bool,
+}
+
+impl Default for RestoreOptions {
+    fn default() -> Self {
+        Self {
+            playlists: true,
+            skip_same_name: false,
+            followed_artists: true,
+            saved_tracks: true,
+            saved_albums: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RestoreReport {
+    pub created_playlists: usize,
+    pub followed_playlists: usize,
+    pub skipped_playlists: usize,
+    /// Backed up playlists named like one the account owns, skipped only with `skip_same_name`
+    pub same_name_playlists: Vec<String>,
+    pub followed_artists: usize,
+    pub saved_tracks: usize,
+    pub saved_albums: usize,
+    pub errors: Vec<String>,
+}
+
+impl RestoreReport {
+    pub fn summary(&self) -> String {
+        let summary = format!(
+            "Created {} playlist(s), followed {} playlist(s), skipped {} existing, followed {} artist(s), saved {} track(s) and {} album(s)",
+            self.created_playlists,
+            self.followed_playlists,
+            self.skipped_playlists,
+            self.followed_artists,
+            self.saved_tracks,
+            self.saved_albums
+        );
+        if self.same_name_playlists.is_empty() {
+            summary
+        } else {
+            format!("{}. Same name as an existing playlist: {}", summary, self.same_name_playlists.join(", "))
+        }
+    }
+}
+
+/// Snapshot the profile, playlists, followed artists and saved tracks/albums of the current user
+pub async fn create_backup(client: &SpotifyClient) -> Result<LibraryBackup> {
+    let profile = client.get_current_user().await?;
+    let playlists = client.get_all_playlists().await?;
+
+    let mut playlist_backups = Vec::new();
+    for playlist in playlists.iter() {
+        let items = client
+            .get_playlist_tracks(&playlist.playlist_id(), None)
+            .await
+            .context(format!("Failed to back up playlist '{}'", playlist.name))?;
+
+        playlist_backups.push(PlaylistBackup {
+            id: playlist.id.clone(),
+            name: playlist.name.clone(),
+            description: playlist.description.clone().unwrap_or_default(),
+            owner_id: playlist.owner.id.clone(),
+            owner_name: playlist.owner.display_name.clone(),
+            public: playlist.public.unwrap_or(false),
+            collaborative: playlist.collaborative.unwrap_or(false),
+            // Local files and unavailable tracks are skipped, they could not be restored
+            tracks: items
+                .iter()
+                .filter_map(|item| {
+                    Some(BackupTrack {
+                        uri: item.track.addable_uri()?.uri(),
+                        name: item.track.name().to_string(),
+                        artists: item.track.artist_names(),
+                        added_at: item.added_at.clone(),
+                    })
+                })
+                .collect(),
+        });
+    }
+
+    let followed_artists = client
+        .get_followed_artists()
+        .await?
+        .into_iter()
+        .map(|artist| BackupItem {
+            id: artist.id,
+            name: artist.name,
+            added_at: None,
+        })
+        .collect();
+
+    let saved_tracks = client
+        .get_saved_tracks()
+        .await?
+        .into_iter()
+        .map(|saved| BackupItem {
+            id: saved.track.id,
+            name: saved.track.name,
+            added_at: Some(saved.added_at),
+        })
+        .collect();
+
+    let saved_albums = client
+        .get_saved_albums()
+        .await?
+        .into_iter()
+        .map(|saved| BackupItem {
+            id: saved.album.id,
+            name: saved.album.name,
+            added_at: Some(saved.added_at),
+        })
+        .collect();
+
+    Ok(LibraryBackup {
+        version: BACKUP_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        profile,
+        playlists: playlist_backups,
+        followed_artists,
+        saved_tracks,
+        saved_albums,
+    })
+}
+
+/// Write a backup as ZIP containing `library.json` plus one importable JSON file per playlist
+pub fn write_backup_archive(backup: &LibraryBackup, dest_zip: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = std::env::temp_dir().join("spotify_backup");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir)?;
+
+    std::fs::write(temp_dir.join(LIBRARY_FILE), serde_json::to_string_pretty(backup)?)?;
+
+    // Same format as the playlist export so single playlists can still be imported
+    for playlist in backup.playlists.iter() {
+        let json_data = serde_json::json!({
+            "info": {
+                "name": playlist.name,
+                "id": playlist.id,
+                "author": playlist.owner_name.as_deref().unwrap_or("Unknown"),
+                "description": playlist.description,
+            },
+            "tracks": playlist.tracks.iter().map(|t| t.uri.clone()).collect::<Vec<_>>(),
+        });
+        let file_name = format!("{} ({}).json", sanitize_filename(&playlist.name), playlist.id);
+        std::fs::write(temp_dir.join(file_name), serde_json::to_string_pretty(&json_data)?)?;
+    }
+
+    let result = create_zip_from_directory(&temp_dir, dest_zip);
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    result
+}
+
+/// Read the library manifest back out of a backup archive
+pub fn read_backup_archive(path: &str) -> Result<LibraryBackup, Box<dyn std::error::Error>> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut library_file = archive.by_name(LIBRARY_FILE)?;
+
+    let mut content = String::new();
+    library_file.read_to_string(&mut content)?;
+
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Restore a backup into the account `client` is logged in with, which may differ from the backed up one.
+/// Only missing items are recreated, so running a restore twice is harmless.
+pub async fn restore_backup(
+    client: &SpotifyClient,
+    backup: &LibraryBackup,
+    options: RestoreOptions,
+) -> Result<RestoreReport> {
+    let mut report = RestoreReport::default();
+
+    if options.playlists {
+        let target_user = client.get_current_user().await?;
+        let existing = client.get_all_playlists().await?;
+        let existing_ids: HashSet<&str> = existing.iter().map(|p| p.id.as_str()).collect();
+        let owned_names: HashSet<String> = existing
+            .iter()
+            .filter(|p| p.owner.id == target_user.id)
+            .map(|p| p.name.to_lowercase())
+            .collect();
+
+        // The library lists playlists newest first, so the oldest is created or followed first
+        for playlist in backup.owned_playlists().rev() {
+            if existing_ids.contains(playlist.id.as_str()) {
+                report.skipped_playlists += 1;
+                continue;
+            }
+            // Distinct playlists can share a name, so a name match is only reported unless asked to skip it
+            if owned_names.contains(&playlist.name.to_lowercase()) {
+                report.same_name_playlists.push(playlist.name.clone());
+                if options.skip_same_name {
+                    report.skipped_playlists += 1;
+                    continue;
+                }
+            }
+
+            // Collaborative playlists cannot be public
+            let public = playlist.public && !playlist.collaborative;
+            // Backups hold the description as the API returned it, HTML-escaped
+            let description = unescape_html(&playlist.description);
+            match client.create_playlist(&playlist.name, &description, public, playlist.collaborative).await
+            {
+                Ok(created) => {
+                    // URIs come from a file, anything malformed is skipped
+                    let uris: Vec<PlayableUri> =
+                        playlist.tracks.iter().filter_map(|t| PlayableUri::parse(&t.uri).ok()).collect();
+                    match client.add_tracks_to_playlist(&created.playlist_id(), uris).await {
+                        Ok(_) => report.created_playlists += 1,
+                        Err(e) => report.errors.push(format!("{}: {}", playlist.name, e)),
+                    }
+                }
+                Err(e) => report.errors.push(format!("{}: {}", playlist.name, e)),
+            }
+        }
+
+        for playlist in backup.followed_playlists().rev() {
+            if existing_ids.contains(playlist.id.as_str()) {
+                report.skipped_playlists += 1;
+                continue;
+            }
+
+            // Spotify doesn't report whether a playlist was followed publicly, so restored follows
+            // stay private instead of showing up on the profile
+            let result = match PlaylistId::from_id(&playlist.id) {
+                Ok(playlist_id) => client.follow_playlist(&playlist_id, false).await,
+                Err(e) => Err(e.into()),
+            };
+            match result {
+                Ok(_) => report.followed_playlists += 1,
+                Err(e) => report.errors.push(format!("{}: {}", playlist.name, e)),
+            }
+        }
+    }
+
+    if options.followed_artists {
+        let followed: HashSet<String> = client.get_followed_artists().await?.into_iter().map(|a| a.id).collect();
+        // IDs come from a file, anything malformed is skipped
+        let missing: Vec<ArtistId> = backup
+            .followed_artists
+            .iter()
+            .filter(|a| !followed.contains(&a.id))
+            .filter_map(|a| ArtistId::from_id(&a.id).ok())
+            .collect();
+
+        if !missing.is_empty() {
+            match client.follow_artists(missing.clone()).await {
+                Ok(_) => report.followed_artists = missing.len(),
+                Err(e) => report.errors.push(format!("Followed artists: {}", e)),
+            }
+        }
+    }
+
+    if options.saved_tracks {
+        let saved: HashSet<String> = client.get_saved_tracks().await?.into_iter().map(|s| s.track.id).collect();
+        let missing: Vec<(TrackId, String)> = backup
+            .saved_tracks
+            .iter()
+            .filter(|t| !saved.contains(&t.id))
+            .filter_map(|t| {
+                let added_at = t.added_at.clone().unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+                Some((TrackId::from_id(&t.id).ok()?, added_at))
+            })
+            .collect();
+
+        if !missing.is_empty() {
+            match client.save_tracks(missing.clone()).await {
+                Ok(_) => report.saved_tracks = missing.len(),
+                Err(e) => report.errors.push(format!("Saved tracks: {}", e)),
+            }
+        }
+    }
+
+    if options.saved_albums {
+        let saved: HashSet<String> = client.get_saved_albums().await?.into_iter().map(|s| s.album.id).collect();
+        // Saved albums are listed newest first, so save the oldest first to keep the order
+        let missing: Vec<AlbumId> = backup
+            .saved_albums
+            .iter()
+            .rev()
+            .filter(|a| !saved.contains(&a.id))
+            .filter_map(|a| AlbumId::from_id(&a.id).ok())
+            .collect();
+
+        if !missing.is_empty() {
+            match client.save_albums(missing.clone()).await {
+                Ok(_) => report.saved_albums = missing.len(),
+                Err(e) => report.errors.push(format!("Saved albums: {}", e)),
+            }
+        }
+    }
+
+    Ok(report)
+}
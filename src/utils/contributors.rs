@@ -0,0 +1,152 @@
+use crate::api::SpotifyClient;
+use crate::models::*;
+use crate::utils::{load_data, month_range, save_data};
+use std::collections::HashMap;
+
+/// File in the data directory caching user ID → display name
+const USER_NAMES_FILE: &str = "user_names.json";
+
+/// Genres listed per contributor in the genre mix
+const GENRE_MIX_SIZE: usize = 5;
+
+/// What one person added to a playlist
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contributor {
+    /// `None` for tracks without `added_by` (old playlists)
+    pub user_id: Option<String>,
+    pub display_name: String,
+    pub track_count: usize,
+    pub duration_ms: u64,
+    /// Tracks added per month ("YYYY-MM"), oldest first
+    pub timeline: Vec<(String, usize)>,
+    /// Most common genres of the added tracks with their track counts
+    pub genres: Vec<(String, usize)>,
+}
+
+impl Contributor {
+    pub fn first_added(&self) -> Option<&str> {
+        self.timeline.first().map(|(month, _)| month.as_str())
+    }
+
+    pub fn last_added(&self) -> Option<&str> {
+        self.timeline.last().map(|(month, _)| month.as_str())
+    }
+}
+
+/// Display names resolved earlier
+pub fn cached_user_names() -> HashMap<String, String> {
+    load_data(USER_NAMES_FILE).unwrap_or_default()
+}
+
+/// Look up the display names of the given users, asking Spotify only for unknown ones
+pub async fn resolve_user_names(client: &SpotifyClient, user_ids: &[String]) -> HashMap<String, String> {
+    let mut names = cached_user_names();
+    let mut changed = false;
+
+    for user_id in user_ids {
+        if names.contains_key(user_id) {
+            continue;
+        }
+        match client.get_user(&UserId::new_unchecked(user_id.as_str())).await {
+            Ok(user) => {
+                names.insert(user_id.clone(), user.display_name.unwrap_or_else(|| user.id.clone()));
+                changed = true;
+            }
+            Err(e) => eprintln!("Failed to resolve user {}: {}", user_id, e),
+        }
+    }
+
+    if changed {
+        if let Err(e) = save_data(USER_NAMES_FILE, &names) {
+            eprintln!("Failed to save user names: {}", e);
+        }
+    }
+    names
+}
+
+/// IDs of everyone who added tracks to the playlist
+pub fn contributor_ids(items: &[PlaylistTrackItem]) -> Vec<String> {
+    let mut ids: Vec<String> = items
+        .iter()
+        .filter_map(|item| item.added_by.as_ref().map(|user| user.id.clone()))
+        .filter(|id| !id.is_empty())
+        .collect();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+/// Group the tracks by who added them, most active contributor first.
+/// `genres` maps artist IDs to their genres.
+pub fn contributor_stats(
+    items: &[PlaylistTrackItem],
+    names: &HashMap<String, String>,
+    genres: &HashMap<String, Vec<String>>,
+) -> Vec<Contributor> {
+    let mut by_user: Vec<(Option<String>, Vec<&PlaylistTrackItem>)> = Vec::new();
+    for item in items {
+        let user_id = item.added_by.as_ref().map(|user| user.id.clone()).filter(|id| !id.is_empty());
+        match by_user.iter_mut().find(|(id, _)| *id == user_id) {
+            Some((_, tracks)) => tracks.push(item),
+            None => by_user.push((user_id, vec![item])),
+        }
+    }
+
+    let mut contributors: Vec<Contributor> = by_user
+        .into_iter()
+        .map(|(user_id, tracks)| {
+            let display_name = match &user_id {
+                Some(id) => names.get(id).cloned().unwrap_or_else(|| id.clone()),
+                None => "Unknown".to_string(),
+            };
+
+            let mut months: HashMap<String, usize> = HashMap::new();
+            let mut genre_counts: HashMap<String, usize> = HashMap::new();
+            for item in &tracks {
+                if let Some(month) = item.added_at.get(..7) {
+                    *months.entry(month.to_string()).or_insert(0) += 1;
+                }
+                let mut track_genres: Vec<&String> = item
+                    .as_track()
+                    .into_iter()
+                    .flat_map(|track| &track.artists)
+                    .filter_map(|artist| genres.get(&artist.id))
+                    .flatten()
+                    .collect();
+                track_genres.sort();
+                track_genres.dedup();
+                for genre in track_genres {
+                    *genre_counts.entry(genre.clone()).or_insert(0) += 1;
+                }
+            }
+
+            let mut timeline: Vec<(String, usize)> = months.into_iter().collect();
+            timeline.sort();
+            let mut genres: Vec<(String, usize)> = genre_counts.into_iter().collect();
+            genres.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            genres.truncate(GENRE_MIX_SIZE);
+
+            Contributor {
+                user_id,
+                display_name,
+                track_count: tracks.len(),
+                duration_ms: tracks.iter().map(|item| u64::from(item.track.duration_ms())).sum(),
+                timeline,
+                genres,
+            }
+        })
+        .collect();
+
+    contributors.sort_by_key(|c| std::cmp::Reverse(c.track_count));
+    contributors
+}
+
+/// Every month from the first to the last addition of any contributor, for a shared timeline axis
+pub fn timeline_months(contributors: &[Contributor]) -> Vec<String> {
+    let first = contributors.iter().filter_map(|c| c.first_added()).min();
+    let last = contributors.iter().filter_map(|c| c.last_added()).max();
+    match (first, last) {
+        (Some(first), Some(last)) => month_range(first, last),
+        _ => Vec::new(),
+    }
+}
\ No newline at end of file
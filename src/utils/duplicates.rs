@@ -0,0 +1,461 @@
+use crate::models::{PlayableUri, PlaylistItem, PlaylistTrackItem};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Maximum duration difference for tracks matched by title and artist
+pub const DURATION_TOLERANCE_MS: u32 = 5000;
+
+/// Words a bracketed or dashed version note is made of, e.g. "(2011 Remaster)" or "- Mono Version".
+/// A note counts only if all its words are these or years, so "(Clean Bandit Remix)" stays part
+/// of the title.
+const VERSION_WORDS: &[&str] = &[
+    "remaster",
+    "remastered",
+    "mono",
+    "stereo",
+    "single",
+    "album",
+    "original",
+    "deluxe",
+    "bonus",
+    "explicit",
+    "clean",
+    "digital",
+    "version",
+    "edition",
+    "track",
+];
+
+/// First words of a featured artist credit like "(feat. Someone)". "with" is left out as it
+/// starts real title text just as often.
+const CREDIT_WORDS: &[&str] = &["feat", "ft", "featuring"];
+
+/// How aggressively tracks are considered duplicates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplicateStrictness {
+    /// Same Spotify track only
+    Exact,
+    /// Same track or same ISRC, e.g. a single and its album release
+    Recording,
+    /// Additionally same normalized title and primary artist with a similar duration
+    Similar,
+}
+
+impl DuplicateStrictness {
+    pub fn all() -> [DuplicateStrictness; 3] {
+        [Self::Exact, Self::Recording, Self::Similar]
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Exact => "exact",
+            Self::Recording => "recording",
+            Self::Similar => "similar",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Exact => "Identical tracks",
+            Self::Recording => "Same recording (ISRC)",
+            Self::Similar => "Similar title, artist & length",
+        }
+    }
+}
+
+/// Why the tracks of a group were matched, ordered from strongest to loosest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchReason {
+    SameTrack,
+    SameIsrc,
+    SimilarMetadata,
+}
+
+impl MatchReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::SameTrack => "Identical track",
+            Self::SameIsrc => "Same recording (ISRC)",
+            Self::SimilarMetadata => "Same title & artist, similar length",
+        }
+    }
+}
+
+/// Which copy of a duplicate group stays in the playlist
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeepStrategy {
+    /// First occurrence in playlist order
+    First,
+    EarliestAdded,
+    LatestAdded,
+    HighestPopularity,
+    /// Explicit version if there is one, otherwise the first occurrence
+    PreferExplicit,
+    /// Clean version if there is one, otherwise the first occurrence
+    PreferClean,
+    /// A specific playlist position chosen by the user
+    Position(usize),
+}
+
+impl KeepStrategy {
+    /// Strategies that can be applied to every group at once
+    pub fn all() -> [KeepStrategy; 6] {
+        [
+            Self::First,
+            Self::EarliestAdded,
+            Self::LatestAdded,
+            Self::HighestPopularity,
+            Self::PreferExplicit,
+            Self::PreferClean,
+        ]
+    }
+
+    pub fn key(&self) -> String {
+        match self {
+            Self::First => "first".to_string(),
+            Self::EarliestAdded => "earliest".to_string(),
+            Self::LatestAdded => "latest".to_string(),
+            Self::HighestPopularity => "popular".to_string(),
+            Self::PreferExplicit => "explicit".to_string(),
+            Self::PreferClean => "clean".to_string(),
+            Self::Position(position) => format!("position-{}", position),
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<KeepStrategy> {
+        match key {
+            "first" => Some(Self::First),
+            "earliest" => Some(Self::EarliestAdded),
+            "latest" => Some(Self::LatestAdded),
+            "popular" => Some(Self::HighestPopularity),
+            "explicit" => Some(Self::PreferExplicit),
+            "clean" => Some(Self::PreferClean),
+            _ => key.strip_prefix("position-")?.parse().ok().map(Self::Position),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::First => "First in playlist".to_string(),
+            Self::EarliestAdded => "Earliest added".to_string(),
+            Self::LatestAdded => "Latest added".to_string(),
+            Self::HighestPopularity => "Most popular".to_string(),
+            Self::PreferExplicit => "Explicit version".to_string(),
+            Self::PreferClean => "Clean version".to_string(),
+            Self::Position(position) => format!("Keep #{}", position + 1),
+        }
+    }
+}
+
+/// Playlist positions that hold the same song
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateGroup {
+    /// Positions in playlist order
+    pub positions: Vec<usize>,
+    /// Loosest match that was needed to join the group
+    pub reason: MatchReason,
+    /// Which copy is kept when the duplicates are removed
+    pub keep: KeepStrategy,
+}
+
+impl DuplicateGroup {
+    /// Position of the copy that stays, ties are resolved in favour of the earlier position
+    pub fn kept_position(&self, items: &[PlaylistTrackItem]) -> usize {
+        let first = self.positions[0];
+        let mut positions = self.positions.iter().copied();
+
+        match self.keep {
+            KeepStrategy::First => first,
+            KeepStrategy::EarliestAdded => positions.min_by_key(|&p| &items[p].added_at).unwrap_or(first),
+            KeepStrategy::LatestAdded => positions.min_by_key(|&p| Reverse(&items[p].added_at)).unwrap_or(first),
+            KeepStrategy::HighestPopularity => positions
+                .min_by_key(|&p| Reverse(items[p].as_track().and_then(|t| t.popularity).unwrap_or(0)))
+                .unwrap_or(first),
+            KeepStrategy::PreferExplicit => positions.find(|&p| is_explicit(&items[p])).unwrap_or(first),
+            KeepStrategy::PreferClean => positions.find(|&p| !is_explicit(&items[p])).unwrap_or(first),
+            KeepStrategy::Position(position) if self.positions.contains(&position) => position,
+            KeepStrategy::Position(_) => first,
+        }
+    }
+}
+
+/// Use the same keep strategy for every group
+pub fn set_keep_strategy(groups: &mut [DuplicateGroup], keep: KeepStrategy) {
+    for group in groups.iter_mut() {
+        group.keep = keep;
+    }
+}
+
+fn is_explicit(item: &PlaylistTrackItem) -> bool {
+    item.as_track().is_some_and(|track| track.explicit)
+}
+
+/// Find groups of duplicate tracks in a playlist. Episodes and local files only match
+/// copies with the same URI, unavailable entries never match.
+pub fn find_duplicate_groups(items: &[PlaylistTrackItem], strictness: DuplicateStrictness) -> Vec<DuplicateGroup> {
+    let entries: Vec<&PlaylistItem> = items.iter().map(|item| &item.track).collect();
+    let mut groups = DisjointSets::new(entries.len());
+
+    link_by_key(&mut groups, &entries, MatchReason::SameTrack, |entry| entry.uri());
+
+    if strictness != DuplicateStrictness::Exact {
+        link_by_key(&mut groups, &entries, MatchReason::SameIsrc, |entry| {
+            entry.as_track()?.external_ids.isrc.as_ref().map(|isrc| isrc.to_uppercase())
+        });
+    }
+
+    if strictness == DuplicateStrictness::Similar {
+        let mut by_title: HashMap<(String, String), Vec<usize>> = HashMap::new();
+        for (index, entry) in entries.iter().enumerate() {
+            if let Some(track) = entry.as_track() {
+                let artist = track.artists.first().map(|a| normalize_text(&a.name)).unwrap_or_default();
+                by_title.entry((normalize_title(&track.name), artist)).or_default().push(index);
+            }
+        }
+
+        for indices in by_title.values() {
+            for (i, &a) in indices.iter().enumerate() {
+                for &b in indices.iter().skip(i + 1) {
+                    if entries[a].duration_ms().abs_diff(entries[b].duration_ms()) <= DURATION_TOLERANCE_MS {
+                        groups.union(a, b, MatchReason::SimilarMetadata);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut by_root: HashMap<usize, Vec<usize>> = HashMap::new();
+    for index in 0..entries.len() {
+        by_root.entry(groups.find(index)).or_default().push(index);
+    }
+
+    let mut result: Vec<DuplicateGroup> = by_root
+        .into_iter()
+        .filter(|(_, positions)| positions.len() > 1)
+        .map(|(root, positions)| DuplicateGroup {
+            positions,
+            reason: groups.reason[root],
+            keep: KeepStrategy::First,
+        })
+        .collect();
+    result.sort_by_key(|group| group.positions[0]);
+    result
+}
+
+/// (URI, position) pairs that remove every copy but the kept one of each group
+pub fn duplicate_removals(items: &[PlaylistTrackItem], groups: &[DuplicateGroup]) -> Vec<(PlayableUri, usize)> {
+    groups
+        .iter()
+        .flat_map(|group| {
+            let kept = group.kept_position(items);
+            group.positions.iter().copied().filter(move |&position| position != kept)
+        })
+        .filter_map(|position| Some((items[position].track.playable_uri()?, position)))
+        .collect()
+}
+
+/// A song found in more than one playlist
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedTrack {
+    /// First copy of the song
+    pub track: PlaylistItem,
+    /// (playlist index, position) of every copy
+    pub occurrences: Vec<(usize, usize)>,
+    pub reason: MatchReason,
+}
+
+impl SharedTrack {
+    /// Number of copies in the playlist at `playlist` index
+    pub fn count_in(&self, playlist: usize) -> usize {
+        self.occurrences.iter().filter(|(p, _)| *p == playlist).count()
+    }
+
+    /// Indices of the playlists that contain the song
+    pub fn playlists(&self) -> Vec<usize> {
+        let mut playlists: Vec<usize> = self.occurrences.iter().map(|(p, _)| *p).collect();
+        playlists.dedup();
+        playlists
+    }
+}
+
+/// Find songs that appear in several playlists, using the same matching as within a playlist
+pub fn find_shared_tracks(playlists: &[Vec<PlaylistTrackItem>], strictness: DuplicateStrictness) -> Vec<SharedTrack> {
+    // Match over all playlists at once and map the combined positions back
+    let mut combined = Vec::new();
+    let mut origin = Vec::new();
+    for (playlist, items) in playlists.iter().enumerate() {
+        for (position, item) in items.iter().enumerate() {
+            combined.push(item.clone());
+            origin.push((playlist, position));
+        }
+    }
+
+    find_duplicate_groups(&combined, strictness)
+        .into_iter()
+        .filter_map(|group| {
+            let occurrences: Vec<(usize, usize)> = group.positions.iter().map(|&index| origin[index]).collect();
+            let first_playlist = occurrences[0].0;
+            if occurrences.iter().all(|(p, _)| *p == first_playlist) {
+                return None;
+            }
+
+            Some(SharedTrack {
+                track: combined[group.positions[0]].track.clone(),
+                occurrences,
+                reason: group.reason,
+            })
+        })
+        .collect()
+}
+
+/// Number of shared songs for every pair of playlists
+pub fn shared_counts(shared: &[SharedTrack], playlist_count: usize) -> Vec<Vec<usize>> {
+    let mut counts = vec![vec![0; playlist_count]; playlist_count];
+    for track in shared {
+        let playlists = track.playlists();
+        for &a in &playlists {
+            for &b in &playlists {
+                counts[a][b] += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// (URI, position) pairs per playlist index that remove a shared song from every playlist but `keep_in`
+pub fn shared_track_removals(
+    playlists: &[Vec<PlaylistTrackItem>],
+    shared: &SharedTrack,
+    keep_in: usize,
+) -> Vec<(usize, Vec<(PlayableUri, usize)>)> {
+    let mut removals: Vec<(usize, Vec<(PlayableUri, usize)>)> = Vec::new();
+    for &(playlist, position) in shared.occurrences.iter().filter(|(p, _)| *p != keep_in) {
+        let Some(uri) = playlists[playlist][position].track.playable_uri() else {
+            continue;
+        };
+        match removals.iter_mut().find(|(p, _)| *p == playlist) {
+            Some((_, tracks)) => tracks.push((uri, position)),
+            None => removals.push((playlist, vec![(uri, position)])),
+        }
+    }
+    removals
+}
+
+/// Lowercase a title and strip remaster/version notes such as "(Remastered 2011)" or "- Mono"
+pub fn normalize_title(title: &str) -> String {
+    let lower = title.to_lowercase();
+
+    // Cut " - Remastered 2011" style suffixes
+    let base = match lower.find(" - ") {
+        Some(pos) if is_version_note(&lower[pos + 3..]) => &lower[..pos],
+        _ => lower.as_str(),
+    };
+
+    // Drop bracketed notes like "(Remastered)" or "[feat. Someone]" but keep other brackets
+    let mut cleaned = String::new();
+    let mut bracket = String::new();
+    let mut depth = 0;
+    for c in base.chars() {
+        match c {
+            '(' | '[' => {
+                depth += 1;
+                bracket.push(c);
+            }
+            ')' | ']' if depth > 0 => {
+                depth -= 1;
+                bracket.push(c);
+                if depth == 0 {
+                    if !is_version_note(&bracket) {
+                        cleaned.push_str(&bracket);
+                    }
+                    bracket.clear();
+                }
+            }
+            _ if depth > 0 => bracket.push(c),
+            _ => cleaned.push(c),
+        }
+    }
+    cleaned.push_str(&bracket);
+
+    normalize_text(&cleaned)
+}
+
+/// Lowercase and keep only letters and digits separated by single spaces
+fn normalize_text(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn is_version_note(note: &str) -> bool {
+    let note = normalize_text(note);
+    let words: Vec<&str> = note.split(' ').filter(|w| !w.is_empty()).collect();
+    let is_year = |word: &&str| word.chars().all(|c| c.is_ascii_digit());
+
+    match words.first() {
+        None => false,
+        Some(first) if CREDIT_WORDS.contains(first) => true,
+        // A bare year like "(1999)" is more likely part of the title
+        _ => words.iter().all(|w| VERSION_WORDS.contains(w) || is_year(w)) && !words.iter().all(is_year),
+    }
+}
+
+fn link_by_key(
+    groups: &mut DisjointSets,
+    entries: &[&PlaylistItem],
+    reason: MatchReason,
+    key: impl Fn(&PlaylistItem) -> Option<String>,
+) {
+    let mut first_index: HashMap<String, usize> = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        if let Some(key) = key(entry) {
+            match first_index.get(&key) {
+                Some(&first) => groups.union(first, index, reason),
+                None => {
+                    first_index.insert(key, index);
+                }
+            }
+        }
+    }
+}
+
+/// Union-find over playlist positions that remembers the loosest reason of each set
+struct DisjointSets {
+    parent: Vec<usize>,
+    reason: Vec<MatchReason>,
+}
+
+impl DisjointSets {
+    fn new(size: usize) -> Self {
+        Self {
+            parent: (0..size).collect(),
+            reason: vec![MatchReason::SameTrack; size],
+        }
+    }
+
+    fn find(&mut self, index: usize) -> usize {
+        let parent = self.parent[index];
+        if parent == index {
+            return index;
+        }
+        let root = self.find(parent);
+        self.parent[index] = root;
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize, reason: MatchReason) {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return;
+        }
+
+        // Keep the lower position as root so groups are anchored at their first occurrence
+        let (root, child) = if root_a < root_b { (root_a, root_b) } else { (root_b, root_a) };
+        self.parent[child] = root;
+        self.reason[root] = self.reason[root].max(self.reason[child]).max(reason);
+    }
+}
@@ -0,0 +1,285 @@
+use crate::api::SpotifyClient;
+use crate::models::*;
+use crate::utils::{artist_play_counts, TIME_RANGES, TOP_LIST_LIMIT};
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// Family of the genres that match none of [`GENRE_FAMILIES`]
+pub const OTHER_FAMILY: &str = "Other";
+
+/// Genre families and the words that put a micro-genre into them. Keywords match whole words,
+/// so "emo" doesn't match "demo". The first match wins, so the more specific families come
+/// before broad ones like rock and pop, and Electronic comes before Punk so its hardcore styles
+/// aren't taken for hardcore punk.
+const GENRE_FAMILIES: &[(&str, &[&str])] = &[
+    ("Hip Hop", &["hip hop", "rap", "trap", "drill", "grime", "boom bap"]),
+    ("Metal", &["metal", "metalcore", "deathcore", "grindcore", "djent", "thrash", "doom"]),
+    (
+        "Electronic",
+        &[
+            "edm", "house", "techno", "trance", "psytrance", "electro", "electronic", "electronica", "dubstep",
+            "drum and bass", "dnb", "uk garage", "bass music", "idm", "synthwave", "hardstyle", "big room",
+            "happy hardcore", "uk hardcore", "gabber",
+        ],
+    ),
+    ("Punk", &["punk", "emo", "hardcore"]),
+    ("R&B & Soul", &["r&b", "soul", "funk", "motown", "neo soul", "quiet storm"]),
+    ("Jazz", &["jazz", "bebop", "swing", "big band"]),
+    ("Classical", &["classical", "neoclassical", "orchestra", "baroque", "opera", "romantic era", "compositional"]),
+    ("Country & Folk", &["country", "folk", "bluegrass", "americana", "singer songwriter"]),
+    (
+        "Latin",
+        &[
+            "latin", "reggaeton", "salsa", "bachata", "cumbia", "sertanejo", "mpb", "samba", "bossa", "corrido",
+            "urbano",
+        ],
+    ),
+    ("Reggae", &["reggae", "dancehall", "ska", "dub"]),
+    ("Blues", &["blues"]),
+    ("Soundtrack", &["soundtrack", "score", "anime", "video game", "musical", "show tunes"]),
+    ("Ambient & Chill", &["ambient", "chill", "chillhop", "chillwave", "lo fi", "lofi", "new age", "sleep"]),
+    ("Indie & Alternative", &["indie", "alternative", "shoegaze", "dream pop", "bedroom", "new wave", "britpop"]),
+    ("Rock", &["rock", "rockabilly", "krautrock", "grunge", "psychedelic"]),
+    ("Pop", &["pop", "synthpop", "electropop", "europop", "boy band", "girl group", "idol"]),
+];
+
+/// Words of a genre or keyword. Hyphens separate words too, so "k-pop" contains "pop".
+fn genre_words(text: &str) -> Vec<&str> {
+    text.split(|c: char| c.is_whitespace() || c == '-').filter(|word| !word.is_empty()).collect()
+}
+
+/// Family a micro-genre like "melodic drill" belongs to
+pub fn genre_family(genre: &str) -> &'static str {
+    let genre = genre.to_lowercase();
+    let words = genre_words(&genre);
+    GENRE_FAMILIES
+        .iter()
+        .find(|(_, keywords)| {
+            keywords.iter().any(|keyword| {
+                let keyword = genre_words(keyword);
+                words.windows(keyword.len()).any(|window| window == keyword.as_slice())
+            })
+        })
+        .map(|(family, _)| *family)
+        .unwrap_or(OTHER_FAMILY)
+}
+
+/// A genre's part of one time range with the artists and tracks behind it
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenreShare {
+    pub genre: String,
+    pub family: &'static str,
+    /// Fraction of the range's total weight, between 0 and 1
+    pub share: f64,
+    /// Top artists with the genre, by rank
+    pub artist_ids: Vec<String>,
+    /// Top tracks by an artist with the genre, by rank
+    pub track_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FamilyShare {
+    pub family: &'static str,
+    pub share: f64,
+}
+
+/// Genres and genre families of one time range, largest share first
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenreProfile {
+    pub time_range: String,
+    pub genres: Vec<GenreShare>,
+    pub families: Vec<FamilyShare>,
+}
+
+impl GenreProfile {
+    pub fn genre(&self, name: &str) -> Option<&GenreShare> {
+        self.genres.iter().find(|g| g.genre == name)
+    }
+}
+
+/// Top lists of one time range with the genre profile computed from them
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeGenres {
+    pub profile: GenreProfile,
+    pub artists: Vec<Artist>,
+    pub tracks: Vec<Track>,
+}
+
+/// Family shares on one snapshot day
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenreDriftPoint {
+    pub date: String,
+    pub families: Vec<FamilyShare>,
+}
+
+/// Weight of every genre and the artists contributing to it.
+///
+/// Each artist weighs `(count + 1 - rank) / count`, so the top artist counts 1 and the last
+/// nearly nothing, plus its share of the most played artist's recorded plays. The weight is
+/// split evenly over the artist's genres so artists with many micro-genres don't dominate.
+fn weigh_genres(
+    ranked: &[(&str, &[String])],
+    artist_plays: &HashMap<String, usize>,
+) -> Vec<(String, f64, Vec<String>)> {
+    let count = ranked.len() as f64;
+    let max_plays = ranked.iter().filter_map(|(id, _)| artist_plays.get(*id)).max().copied().unwrap_or(0);
+
+    let mut weights: Vec<(String, f64, Vec<String>)> = Vec::new();
+    let mut index_by_genre: HashMap<&str, usize> = HashMap::new();
+
+    for (rank, (artist_id, genres)) in ranked.iter().enumerate() {
+        if genres.is_empty() {
+            continue;
+        }
+        let mut weight = (count - rank as f64) / count;
+        if max_plays > 0 {
+            weight += *artist_plays.get(*artist_id).unwrap_or(&0) as f64 / max_plays as f64;
+        }
+        let per_genre = weight / genres.len() as f64;
+
+        for genre in genres.iter() {
+            let index = *index_by_genre.entry(genre.as_str()).or_insert_with(|| {
+                weights.push((genre.clone(), 0.0, Vec::new()));
+                weights.len() - 1
+            });
+            weights[index].1 += per_genre;
+            weights[index].2.push(artist_id.to_string());
+        }
+    }
+
+    weights
+}
+
+/// Sum the genre shares per family, largest first
+fn family_shares<'a>(genres: impl Iterator<Item = (&'a str, f64)>) -> Vec<FamilyShare> {
+    let mut totals: HashMap<&'static str, f64> = HashMap::new();
+    for (genre, share) in genres {
+        *totals.entry(genre_family(genre)).or_insert(0.0) += share;
+    }
+
+    let mut families: Vec<FamilyShare> =
+        totals.into_iter().map(|(family, share)| FamilyShare { family, share }).collect();
+    families.sort_by(|a, b| b.share.total_cmp(&a.share).then_with(|| a.family.cmp(b.family)));
+    families
+}
+
+/// Genre profile of one time range.
+///
+/// `artists` and `tracks` are the range's top lists in rank order, `genres` maps artist IDs to
+/// their genres for the track artists that are not top artists.
+pub fn genre_profile(
+    time_range: &str,
+    artists: &[Artist],
+    tracks: &[Track],
+    genres: &HashMap<String, Vec<String>>,
+    artist_plays: &HashMap<String, usize>,
+) -> GenreProfile {
+    let ranked: Vec<(&str, &[String])> =
+        artists.iter().map(|a| (a.id.as_str(), a.genres.as_deref().unwrap_or_default())).collect();
+    let weights = weigh_genres(&ranked, artist_plays);
+    let total: f64 = weights.iter().map(|(_, weight, _)| weight).sum();
+
+    let mut genre_shares: Vec<GenreShare> = weights
+        .into_iter()
+        .map(|(genre, weight, artist_ids)| {
+            let track_ids = tracks
+                .iter()
+                .filter(|track| {
+                    track.artists.iter().any(|artist| {
+                        artists
+                            .iter()
+                            .find(|a| a.id == artist.id)
+                            .and_then(|a| a.genres.as_ref())
+                            .or_else(|| genres.get(&artist.id))
+                            .is_some_and(|artist_genres| artist_genres.contains(&genre))
+                    })
+                })
+                .map(|track| track.id.clone())
+                .collect();
+
+            GenreShare {
+                family: genre_family(&genre),
+                share: if total > 0.0 { weight / total } else { 0.0 },
+                genre,
+                artist_ids,
+                track_ids,
+            }
+        })
+        .collect();
+    genre_shares.sort_by(|a, b| b.share.total_cmp(&a.share).then_with(|| a.genre.cmp(&b.genre)));
+
+    let families = family_shares(genre_shares.iter().map(|g| (g.genre.as_str(), g.share)));
+    GenreProfile { time_range: time_range.to_string(), genres: genre_shares, families }
+}
+
+/// Family shares of a time range in every stored top list snapshot, oldest first. Plays aren't
+/// dated per snapshot, so only the artist ranks count here.
+pub fn genre_drift(history: &[TopSnapshot], time_range: &str) -> Vec<GenreDriftPoint> {
+    history
+        .iter()
+        .filter_map(|snapshot| {
+            let range = snapshot.range(time_range)?;
+            let ranked: Vec<(&str, &[String])> =
+                range.artists.iter().map(|a| (a.id.as_str(), a.genres.as_slice())).collect();
+            let weights = weigh_genres(&ranked, &HashMap::new());
+            let total: f64 = weights.iter().map(|(_, weight, _)| weight).sum();
+            if total <= 0.0 {
+                return None;
+            }
+
+            Some(GenreDriftPoint {
+                date: snapshot.date.clone(),
+                families: family_shares(weights.iter().map(|(genre, weight, _)| (genre.as_str(), weight / total))),
+            })
+        })
+        .collect()
+}
+
+/// Genre profiles of the given `(time range, top artists, top tracks)` lists
+pub fn range_genres(
+    lists: Vec<(String, Vec<Artist>, Vec<Track>)>,
+    genres: &HashMap<String, Vec<String>>,
+) -> Vec<RangeGenres> {
+    let artist_plays = artist_play_counts();
+    lists
+        .into_iter()
+        .map(|(time_range, artists, tracks)| RangeGenres {
+            profile: genre_profile(&time_range, &artists, &tracks, genres, &artist_plays),
+            artists,
+            tracks,
+        })
+        .collect()
+}
+
+/// Genres of every artist of `tracks`, by artist ID. Each artist is looked up once.
+pub async fn fetch_artist_genres<'a>(
+    client: &SpotifyClient,
+    tracks: impl IntoIterator<Item = &'a Track>,
+) -> Result<HashMap<String, Vec<String>>> {
+    let mut artist_ids: Vec<String> =
+        tracks.into_iter().flat_map(|track| track.artists.iter().map(|a| a.id.clone())).collect();
+    artist_ids.sort();
+    artist_ids.dedup();
+
+    Ok(client
+        .get_artists(artist_ids.into_iter().map(ArtistId::new_unchecked).collect())
+        .await?
+        .into_iter()
+        .map(|artist| (artist.id, artist.genres.unwrap_or_default()))
+        .collect())
+}
+
+/// Fetch the top artists and tracks of every time range and compute their genre profiles
+pub async fn fetch_range_genres(client: &SpotifyClient) -> Result<Vec<RangeGenres>> {
+    let mut lists = Vec::new();
+    for time_range in TIME_RANGES {
+        let artists = client.get_top_artists(TOP_LIST_LIMIT, time_range).await?;
+        let tracks = client.get_top_tracks(TOP_LIST_LIMIT, time_range).await?;
+        lists.push((time_range.to_string(), artists, tracks));
+    }
+
+    // Genres of the track artists, the top artists come with theirs
+    let genres = fetch_artist_genres(client, lists.iter().flat_map(|(_, _, tracks)| tracks)).await?;
+
+    Ok(range_genres(lists, &genres))
+}
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::components::{Toast, ToastType};
+use crate::components::{Toast, ToastAction, ToastType};
 use crate::AppContext;
 use dioxus::prelude::*;
 
@@ -22,14 +22,29 @@ pub fn sanitize_filename(name: &str) -> String {
 
 /// Pick a JSON file using native file picker
 pub fn pick_json_file() -> Option<String> {
+    pick_file("Select a playlist JSON file", &["json"])
+}
+
+/// Pick a file with one of the given extensions using native file picker
+pub fn pick_file(prompt: &str, extensions: &[&str]) -> Option<String> {
     use std::process::Command;
 
     // Use native file picker based on OS
     #[cfg(target_os = "macos")]
     {
+        let types: Vec<String> = extensions
+            .iter()
+            .map(|extension| format!("\"{}\"", if *extension == "json" { "public.json" } else { *extension }))
+            .collect();
+        let script = format!(
+            r#"POSIX path of (choose file with prompt "{}" of type {{{}}})"#,
+            prompt,
+            types.join(", ")
+        );
+
         let output = Command::new("osascript")
             .arg("-e")
-            .arg(r#"POSIX path of (choose file with prompt "Select a playlist JSON file" of type {"public.json"})"#)
+            .arg(&script)
             .output()
             .ok()?;
 
@@ -44,8 +59,14 @@ pub fn pick_json_file() -> Option<String> {
 
     #[cfg(target_os = "linux")]
     {
+        // One filter with every pattern, e.g. "*.jpg *.jpeg"
+        let patterns: Vec<String> = extensions.iter().map(|extension| format!("*.{}", extension)).collect();
         let output = Command::new("zenity")
-            .args(&["--file-selection", "--file-filter=*.json"])
+            .args(&[
+                "--file-selection",
+                &format!("--title={}", prompt),
+                &format!("--file-filter={}", patterns.join(" ")),
+            ])
             .output()
             .ok()?;
 
@@ -58,7 +79,7 @@ pub fn pick_json_file() -> Option<String> {
     #[cfg(target_os = "windows")]
     {
         // For Windows, we'll use a simple dialog
-        println!("Please enter the full path to the JSON file:");
+        println!("{} (full path to the .{} file):", prompt, extensions.join("/."));
         let mut input = String::new();
         std::io::stdin().read_line(&mut input).ok()?;
         return Some(input.trim().to_string());
@@ -148,6 +169,10 @@ pub fn generate_random_string(length: usize) -> String {
 
 // Toast helper functions
 pub fn show_toast(context: &AppContext, message: String, toast_type: ToastType) {
+    push_toast(context, message, toast_type, None);
+}
+
+fn push_toast(context: &AppContext, message: String, toast_type: ToastType, action: Option<ToastAction>) {
     let mut toasts = context.toasts;
     let mut counter = context.toast_counter;
 
@@ -158,15 +183,17 @@ pub fn show_toast(context: &AppContext, message: String, toast_type: ToastType)
         message,
         toast_type,
         id,
+        action,
     };
+    let duration_ms = toast.duration_ms();
 
     let mut current_toasts = toasts();
     current_toasts.push(toast.clone());
     toasts.set(current_toasts);
 
-    // Auto-remove toast after 3 seconds
+    // Auto-remove toast once its time is up
     spawn(async move {
-        tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+        tokio::time::sleep(tokio::time::Duration::from_millis(duration_ms)).await;
         let mut current_toasts = toasts();
         current_toasts.retain(|t| t.id != id);
         toasts.set(current_toasts);
@@ -185,6 +212,11 @@ pub fn show_info(context: &AppContext, message: String) {
     show_toast(context, message, ToastType::Info);
 }
 
+/// Success toast with an "Undo" button for the given journal entries
+pub fn show_success_with_undo(context: &AppContext, message: String, operation_ids: Vec<String>) {
+    push_toast(context, message, ToastType::Success, Some(ToastAction::Undo(operation_ids)));
+}
+
 /// Create a ZIP file from a directory
 pub fn create_zip_from_directory(source_dir: &std::path::Path, dest_zip: &str) -> Result<(), Box<dyn std::error::Error>> {
     use std::fs::File;
@@ -215,6 +247,13 @@ pub fn create_zip_from_directory(source_dir: &std::path::Path, dest_zip: &str) -
     Ok(())
 }
 
+/// Format an RFC 3339 timestamp as local "YYYY-MM-DD HH:MM"
+pub fn format_timestamp(timestamp: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|date| date.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| timestamp.to_string())
+}
+
 /// Format release date from "YYYY-MM-DD" to "Month DD, YYYY"
 pub fn format_release_date(date: &str) -> String {
     let parts: Vec<&str> = date.split('-').collect();
@@ -0,0 +1,238 @@
+use crate::api::SpotifyClient;
+use crate::models::*;
+use crate::utils::{
+    apply_order, generate_random_string, load_data, order_from_uris, save_data, show_error, show_success, ApplyMethod,
+};
+use crate::AppContext;
+use anyhow::Result;
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+/// File in the data directory holding the operation journal
+const JOURNAL_FILE: &str = "journal.json";
+
+/// Older entries are dropped once the journal grows beyond this
+const MAX_JOURNAL_ENTRIES: usize = 100;
+
+pub fn load_journal() -> Vec<JournalEntry> {
+    load_data(JOURNAL_FILE).unwrap_or_default()
+}
+
+fn save_journal(journal: &[JournalEntry]) {
+    if let Err(e) = save_data(JOURNAL_FILE, journal) {
+        eprintln!("Failed to save operation journal: {}", e);
+    }
+}
+
+/// Journal entry for the removal of `removals` ((URI, position) pairs) from `playlist`
+pub fn removal_entry(
+    description: &str,
+    playlist: &Playlist,
+    items: &[PlaylistTrackItem],
+    removals: &[(PlayableUri, usize)],
+    snapshot_after: Option<String>,
+) -> JournalEntry {
+    JournalEntry {
+        id: generate_random_string(16),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        description: description.to_string(),
+        playlist_id: playlist.id.clone(),
+        playlist_name: playlist.name.clone(),
+        snapshot_before: playlist.snapshot_id.clone(),
+        snapshot_after,
+        removed: removals
+            .iter()
+            .map(|(uri, position)| JournalTrack {
+                uri: uri.uri(),
+                position: *position,
+                name: items.get(*position).map(|item| item.track.name().to_string()).unwrap_or_default(),
+            })
+            .collect(),
+        added: Vec::new(),
+        previous_uris: Vec::new(),
+        undone: false,
+    }
+}
+
+/// Journal entry for the insertion of `additions` (track, position) into `playlist`
+pub fn insertion_entry(
+    description: &str,
+    playlist: &Playlist,
+    additions: &[(PlayableUri, usize, String)],
+    snapshot_after: Option<String>,
+) -> JournalEntry {
+    JournalEntry {
+        id: generate_random_string(16),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        description: description.to_string(),
+        playlist_id: playlist.id.clone(),
+        playlist_name: playlist.name.clone(),
+        snapshot_before: playlist.snapshot_id.clone(),
+        snapshot_after,
+        removed: Vec::new(),
+        added: additions
+            .iter()
+            .map(|(uri, position, name)| JournalTrack { uri: uri.uri(), position: *position, name: name.clone() })
+            .collect(),
+        previous_uris: Vec::new(),
+        undone: false,
+    }
+}
+
+/// Journal entry for rewriting `playlist`, whose tracks were `items` before, with `new_tracks`
+/// (URI, name). Undo removes the new tracks and puts the previous ones back.
+pub fn replacement_entry(
+    description: &str,
+    playlist: &Playlist,
+    items: &[PlaylistTrackItem],
+    new_tracks: &[(PlayableUri, String)],
+    snapshot_after: Option<String>,
+) -> JournalEntry {
+    let removals: Vec<(PlayableUri, usize)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(position, item)| Some((item.track.playable_uri()?, position)))
+        .collect();
+    let additions: Vec<(PlayableUri, usize, String)> = new_tracks
+        .iter()
+        .enumerate()
+        .map(|(position, (uri, name))| (uri.clone(), position, name.clone()))
+        .collect();
+
+    JournalEntry {
+        added: insertion_entry(description, playlist, &additions, None).added,
+        ..removal_entry(description, playlist, items, &removals, snapshot_after)
+    }
+}
+
+/// Journal entry for a reorder of `playlist` whose tracks were in the order of `items` before
+pub fn reorder_entry(
+    description: &str,
+    playlist: &Playlist,
+    items: &[PlaylistTrackItem],
+    snapshot_after: Option<String>,
+) -> JournalEntry {
+    JournalEntry {
+        id: generate_random_string(16),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        description: description.to_string(),
+        playlist_id: playlist.id.clone(),
+        playlist_name: playlist.name.clone(),
+        snapshot_before: playlist.snapshot_id.clone(),
+        snapshot_after,
+        removed: Vec::new(),
+        added: Vec::new(),
+        previous_uris: items.iter().map(|item| item.track.uri().unwrap_or_default()).collect(),
+        undone: false,
+    }
+}
+
+/// Append entries to the journal, returning their IDs for an undo action
+pub fn record_operations(context: &AppContext, entries: Vec<JournalEntry>) -> Vec<String> {
+    let mut journal_signal = context.operation_journal;
+    let ids = entries.iter().map(|entry| entry.id.clone()).collect();
+
+    let mut journal = journal_signal();
+    journal.extend(entries);
+    if journal.len() > MAX_JOURNAL_ENTRIES {
+        journal.drain(..journal.len() - MAX_JOURNAL_ENTRIES);
+    }
+
+    save_journal(&journal);
+    journal_signal.set(journal);
+    ids
+}
+
+/// Re-insert the removed tracks at their original positions, remove the added ones, or restore
+/// the previous order of a reorder. Unless `force` is set this fails when the playlist was edited after the operation,
+/// as the positions would no longer match.
+pub async fn undo_operation(client: &SpotifyClient, entry: &JournalEntry, force: bool) -> Result<()> {
+    let playlist_id = PlaylistId::from_id(&entry.playlist_id)?;
+
+    if !force {
+        if let Some(snapshot_after) = &entry.snapshot_after {
+            client.check_snapshot(&playlist_id, snapshot_after).await?;
+        }
+    }
+
+    if !entry.previous_uris.is_empty() {
+        let items = client.get_playlist_tracks(&playlist_id, None).await?;
+        let current: Vec<String> = items.iter().map(|item| item.track.uri().unwrap_or_default()).collect();
+        let order = order_from_uris(&current, &entry.previous_uris)
+            .ok_or_else(|| anyhow::anyhow!("tracks were added or removed since the reorder"))?;
+
+        apply_order(client, &playlist_id, &items, &order, None, ApplyMethod::Reorder).await?;
+        return Ok(());
+    }
+
+    // A rewrite is undone by removing the new tracks before the old ones are inserted again
+    if !entry.added.is_empty() {
+        let added = entry.added.iter().map(|track| Ok((PlayableUri::parse(&track.uri)?, track.position)));
+        client.remove_tracks_from_playlist(&playlist_id, added.collect::<Result<Vec<_>>>()?, None).await?;
+    }
+
+    // Inserting in ascending order puts every track back exactly where it was
+    let mut removed = entry.removed.clone();
+    removed.sort_by_key(|track| track.position);
+
+    // Consecutive positions are restored with a single request
+    let mut runs: Vec<(usize, Vec<PlayableUri>)> = Vec::new();
+    for track in removed {
+        // The journal is read from a file, so its URIs are validated again
+        let uri = PlayableUri::parse(&track.uri)?;
+        match runs.last_mut() {
+            Some((start, uris)) if *start + uris.len() == track.position => uris.push(uri),
+            _ => runs.push((track.position, vec![uri])),
+        }
+    }
+
+    for (position, uris) in runs {
+        client.insert_tracks_at(&playlist_id, uris, position).await?;
+    }
+
+    Ok(())
+}
+
+/// Undo the given operations newest first and report the result with a toast
+pub async fn undo_operations(context: AppContext, ids: Vec<String>, force: bool) {
+    let Some(client) = context.spotify_client.read().clone() else {
+        return;
+    };
+    let mut journal_signal = context.operation_journal;
+
+    let entries: Vec<JournalEntry> = journal_signal()
+        .into_iter()
+        .rev()
+        .filter(|entry| !entry.undone && ids.contains(&entry.id))
+        .collect();
+
+    let mut restored = 0;
+    let mut undone = 0;
+    // Only the newest operation of a playlist can be checked against its current snapshot
+    let mut touched = HashSet::new();
+
+    for entry in entries.iter() {
+        let skip_check = force || touched.contains(&entry.playlist_id);
+
+        if let Err(e) = undo_operation(&client, entry, skip_check).await {
+            eprintln!("Failed to undo operation on {}: {}", entry.playlist_name, e);
+            show_error(&context, format!("Could not undo \"{}\" on {}: {}", entry.description, entry.playlist_name, e));
+            break;
+        }
+
+        touched.insert(entry.playlist_id.clone());
+        restored += entry.removed.len().max(entry.added.len()).max(entry.previous_uris.len());
+        undone += 1;
+
+        let mut journal = journal_signal();
+        if let Some(stored) = journal.iter_mut().find(|stored| stored.id == entry.id) {
+            stored.undone = true;
+        }
+        save_journal(&journal);
+        journal_signal.set(journal);
+    }
+
+    if undone > 0 {
+        show_success(&context, format!("Restored {} track(s) from {} operation(s)", restored, undone));
+    }
+}
\ No newline at end of file
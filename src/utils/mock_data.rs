@@ -17,6 +17,7 @@ pub fn get_mock_top_tracks() -> Vec<Track> {
     vec![
         Track {
             id: "track1".to_string(),
+            uri: "spotify:track:track1".to_string(),
             name: "Bohemian Rhapsody".to_string(),
             artists: vec![Artist {
                 id: "artist1".to_string(),
@@ -41,9 +42,19 @@ popularity: None,
             },
             popularity: Some(92),
             explicit: false,
+            external_ids: ExternalIds::default(),
+            is_playable: None,
+            linked_from: None,
+            restrictions: None,
+            track_number: 11,
+            disc_number: 1,
+            is_local: false,
+            preview_url: None,
+            available_markets: vec![],
         },
         Track {
             id: "track2".to_string(),
+            uri: "spotify:track:track2".to_string(),
             name: "Stairway to Heaven".to_string(),
             artists: vec![Artist {
                 id: "artist2".to_string(),
@@ -68,9 +79,19 @@ popularity: None,
             },
             popularity: Some(88),
             explicit: false,
+            external_ids: ExternalIds::default(),
+            is_playable: None,
+            linked_from: None,
+            restrictions: None,
+            track_number: 4,
+            disc_number: 1,
+            is_local: false,
+            preview_url: None,
+            available_markets: vec![],
         },
         Track {
             id: "track3".to_string(),
+            uri: "spotify:track:track3".to_string(),
             name: "Hotel California".to_string(),
             artists: vec![Artist {
                 id: "artist3".to_string(),
@@ -95,6 +116,15 @@ popularity: None,
             },
             popularity: Some(90),
             explicit: false,
+            external_ids: ExternalIds::default(),
+            is_playable: None,
+            linked_from: None,
+            restrictions: None,
+            track_number: 1,
+            disc_number: 1,
+            is_local: false,
+            preview_url: None,
+            available_markets: vec![],
         },
     ]
 }
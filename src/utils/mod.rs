@@ -1,5 +1,45 @@
+pub mod backup;
+pub mod contributors;
+pub mod duplicates;
+pub mod genres;
 pub mod helpers;
+pub mod journal;
 pub mod mock_data;
+pub mod playlist_details;
+pub mod playlist_history;
+pub mod playlist_stats;
+pub mod repair;
+pub mod set_operations;
+pub mod smart_playlist;
+pub mod sorting;
+pub mod split;
+pub mod storage;
+pub mod taste;
+pub mod taste_profile;
+pub mod top_comparison;
+pub mod top_history;
+pub mod top_playlist;
+pub mod transfer;
 
+pub use backup::*;
+pub use contributors::*;
+pub use duplicates::*;
+pub use genres::*;
 pub use helpers::*;
-pub use mock_data::*;
\ No newline at end of file
+pub use journal::*;
+pub use mock_data::*;
+pub use playlist_details::*;
+pub use playlist_history::*;
+pub use playlist_stats::*;
+pub use repair::*;
+pub use set_operations::*;
+pub use smart_playlist::*;
+pub use sorting::*;
+pub use split::*;
+pub use storage::*;
+pub use taste::*;
+pub use taste_profile::*;
+pub use top_comparison::*;
+pub use top_history::*;
+pub use top_playlist::*;
+pub use transfer::*;
\ No newline at end of file
@@ -0,0 +1,59 @@
+use crate::models::PlaylistDetailsUpdate;
+use anyhow::{bail, Result};
+use base64::{engine::general_purpose, Engine};
+
+/// Longest playlist name Spotify accepts
+pub const MAX_PLAYLIST_NAME_LENGTH: usize = 100;
+
+/// Longest playlist description Spotify accepts
+pub const MAX_PLAYLIST_DESCRIPTION_LENGTH: usize = 300;
+
+/// Spotify rejects cover payloads over 256 KB, measured on the base64 text
+pub const MAX_COVER_BASE64_BYTES: usize = 256 * 1024;
+
+/// Check the details against Spotify's limits before sending them
+pub fn validate_playlist_details(details: &PlaylistDetailsUpdate) -> Result<()> {
+    let name_length = details.name.trim().chars().count();
+    if name_length == 0 {
+        bail!("The name can't be empty");
+    }
+    if name_length > MAX_PLAYLIST_NAME_LENGTH {
+        bail!("The name is {} characters long, the limit is {}", name_length, MAX_PLAYLIST_NAME_LENGTH);
+    }
+
+    let description_length = details.description.chars().count();
+    if description_length > MAX_PLAYLIST_DESCRIPTION_LENGTH {
+        bail!(
+            "The description is {} characters long, the limit is {}",
+            description_length,
+            MAX_PLAYLIST_DESCRIPTION_LENGTH
+        );
+    }
+    if details.description.contains(['\n', '\r']) {
+        bail!("The description can't contain line breaks");
+    }
+
+    if details.collaborative && details.public {
+        bail!("Collaborative playlists can't be public");
+    }
+
+    Ok(())
+}
+
+/// Turn the raw bytes of a JPEG file into the base64 payload of a cover upload
+pub fn encode_cover_image(bytes: &[u8]) -> Result<String> {
+    if !bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        bail!("The cover has to be a JPEG image");
+    }
+
+    let encoded = general_purpose::STANDARD.encode(bytes);
+    if encoded.len() > MAX_COVER_BASE64_BYTES {
+        bail!(
+            "The image is {} KB after encoding, the limit is {} KB",
+            encoded.len() / 1024,
+            MAX_COVER_BASE64_BYTES / 1024
+        );
+    }
+
+    Ok(encoded)
+}
\ No newline at end of file
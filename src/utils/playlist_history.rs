@@ -0,0 +1,140 @@
+use crate::models::*;
+use crate::utils::{load_data, save_data};
+use std::collections::HashMap;
+
+/// Snapshots kept per playlist, older ones are dropped
+const MAX_SNAPSHOTS_PER_PLAYLIST: usize = 50;
+
+fn history_file(playlist_id: &str) -> String {
+    format!("playlist_history/{}.json", playlist_id)
+}
+
+/// Stored snapshots of a playlist, oldest first
+pub fn load_playlist_history(playlist_id: &str) -> Vec<PlaylistSnapshot> {
+    load_data(&history_file(playlist_id)).unwrap_or_default()
+}
+
+/// Store the loaded track list if the playlist's `snapshot_id` differs from the last stored one.
+/// Returns whether a new snapshot was recorded.
+pub fn record_playlist_snapshot(playlist: &Playlist, items: &[PlaylistTrackItem]) -> bool {
+    let Some(snapshot_id) = &playlist.snapshot_id else {
+        return false;
+    };
+
+    let mut history = load_playlist_history(&playlist.id);
+    if history.last().is_some_and(|last| &last.snapshot_id == snapshot_id) {
+        return false;
+    }
+
+    history.push(PlaylistSnapshot {
+        snapshot_id: snapshot_id.clone(),
+        captured_at: chrono::Utc::now().to_rfc3339(),
+        tracks: items.iter().map(snapshot_track).collect(),
+    });
+    if history.len() > MAX_SNAPSHOTS_PER_PLAYLIST {
+        history.drain(..history.len() - MAX_SNAPSHOTS_PER_PLAYLIST);
+    }
+
+    if let Err(e) = save_data(&history_file(&playlist.id), &history) {
+        eprintln!("Failed to save history of {}: {}", playlist.name, e);
+        return false;
+    }
+    true
+}
+
+fn snapshot_track(item: &PlaylistTrackItem) -> SnapshotTrack {
+    SnapshotTrack {
+        uri: item.track.uri().unwrap_or_default(),
+        name: item.track.name().to_string(),
+        artists: item.track.artist_names(),
+        added_at: item.added_at.clone(),
+        added_by: item.added_by.as_ref().map(|user| user.id.clone()),
+    }
+}
+
+/// Changes between every pair of consecutive snapshots, newest first
+pub fn playlist_changelog(history: &[PlaylistSnapshot]) -> Vec<PlaylistChangeSet> {
+    history
+        .windows(2)
+        .rev()
+        .map(|pair| PlaylistChangeSet {
+            snapshot_id: pair[1].snapshot_id.clone(),
+            captured_at: pair[1].captured_at.clone(),
+            changes: diff_snapshots(&pair[0].tracks, &pair[1].tracks),
+        })
+        .filter(|set| !set.changes.is_empty())
+        .collect()
+}
+
+/// Added, removed and moved tracks between two track lists.
+///
+/// Copies of the same URI are matched in order. Among the tracks present in both lists, the
+/// longest run that kept its relative order counts as unmoved and everything else as moved.
+pub fn diff_snapshots(old: &[SnapshotTrack], new: &[SnapshotTrack]) -> Vec<TrackChange> {
+    let mut unmatched: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, track) in old.iter().enumerate().rev() {
+        unmatched.entry(track.uri.as_str()).or_default().push(index);
+    }
+
+    let mut changes = Vec::new();
+    // (old position, new position) of tracks present in both lists
+    let mut kept = Vec::new();
+    for (position, track) in new.iter().enumerate() {
+        match unmatched.get_mut(track.uri.as_str()).and_then(|indexes| indexes.pop()) {
+            Some(from) => kept.push((from, position)),
+            None => changes.push(TrackChange {
+                kind: TrackChangeKind::Added,
+                track: track.clone(),
+                position,
+            }),
+        }
+    }
+
+    let mut removed: Vec<usize> = unmatched.into_values().flatten().collect();
+    removed.sort_unstable();
+    changes.extend(removed.into_iter().map(|position| TrackChange {
+        kind: TrackChangeKind::Removed,
+        track: old[position].clone(),
+        position,
+    }));
+
+    let in_order = longest_increasing_run(&kept.iter().map(|&(from, _)| from).collect::<Vec<_>>());
+    for (index, &(from, position)) in kept.iter().enumerate() {
+        if !in_order[index] {
+            changes.push(TrackChange {
+                kind: TrackChangeKind::Moved { from },
+                track: new[position].clone(),
+                position,
+            });
+        }
+    }
+
+    changes
+}
+
+/// Mark the elements of the longest strictly increasing subsequence
+fn longest_increasing_run(values: &[usize]) -> Vec<bool> {
+    // tails[k] = index of the smallest tail of an increasing subsequence of length k + 1
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous = vec![None; values.len()];
+
+    for (index, &value) in values.iter().enumerate() {
+        let length = tails.partition_point(|&tail| values[tail] < value);
+        if length > 0 {
+            previous[index] = Some(tails[length - 1]);
+        }
+        if length == tails.len() {
+            tails.push(index);
+        } else {
+            tails[length] = index;
+        }
+    }
+
+    let mut marked = vec![false; values.len()];
+    let mut current = tails.last().copied();
+    while let Some(index) = current {
+        marked[index] = true;
+        current = previous[index];
+    }
+    marked
+}
\ No newline at end of file
@@ -0,0 +1,144 @@
+use crate::models::PlaylistTrackItem;
+use std::collections::HashMap;
+
+/// Entries listed in the top artists and top genres
+const TOP_LIST_SIZE: usize = 10;
+
+/// Aggregates over the tracks of a playlist
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistStats {
+    pub track_count: usize,
+    pub total_duration_ms: u64,
+    pub average_popularity: Option<f64>,
+    pub median_popularity: Option<f64>,
+    /// Fraction of explicit tracks, 0.0 to 1.0
+    pub explicit_share: f64,
+    /// Tracks per release year, every year from the oldest to the newest
+    pub release_years: Vec<(i32, usize)>,
+    pub top_artists: Vec<(String, usize)>,
+    pub top_genres: Vec<(String, usize)>,
+    /// Tracks added per month ("YYYY-MM"), every month from the first to the last addition
+    pub added_timeline: Vec<(String, usize)>,
+    /// `added_at` of the most recent addition
+    pub last_added: Option<String>,
+    pub added_last_30_days: usize,
+}
+
+impl PlaylistStats {
+    /// Whole days since the last track was added
+    pub fn days_since_last_added(&self) -> Option<i64> {
+        let last = chrono::DateTime::parse_from_rfc3339(self.last_added.as_deref()?).ok()?;
+        Some((chrono::Utc::now() - last.with_timezone(&chrono::Utc)).num_days())
+    }
+
+    pub fn freshness_label(&self) -> String {
+        match self.days_since_last_added() {
+            None => "Never updated".to_string(),
+            Some(0) => "Updated today".to_string(),
+            Some(1) => "Updated yesterday".to_string(),
+            Some(days) if days < 60 => format!("Updated {} days ago", days),
+            Some(days) if days < 730 => format!("Updated {} months ago", days / 30),
+            Some(days) => format!("Updated {} years ago", days / 365),
+        }
+    }
+}
+
+/// Compute the stats of a playlist. `genres` maps artist IDs to their genres.
+pub fn playlist_stats(items: &[PlaylistTrackItem], genres: &HashMap<String, Vec<String>>) -> PlaylistStats {
+    let mut popularity: Vec<u32> = items.iter().filter_map(|item| item.as_track()?.popularity).collect();
+    popularity.sort_unstable();
+    let average_popularity = (!popularity.is_empty())
+        .then(|| popularity.iter().map(|&p| f64::from(p)).sum::<f64>() / popularity.len() as f64);
+    let median_popularity = match popularity.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(f64::from(popularity[n / 2])),
+        n => Some(f64::from(popularity[n / 2 - 1] + popularity[n / 2]) / 2.0),
+    };
+
+    let explicit = items.iter().filter(|item| item.as_track().is_some_and(|track| track.explicit)).count();
+
+    let mut years: HashMap<i32, usize> = HashMap::new();
+    let mut artists: HashMap<String, usize> = HashMap::new();
+    let mut genre_counts: HashMap<String, usize> = HashMap::new();
+    let mut months: HashMap<String, usize> = HashMap::new();
+    for item in items {
+        if let Some(month) = item.added_at.get(..7) {
+            *months.entry(month.to_string()).or_insert(0) += 1;
+        }
+        let Some(track) = item.as_track() else {
+            continue;
+        };
+        if let Some(year) = track.album.release_date.get(..4).and_then(|year| year.parse().ok()) {
+            *years.entry(year).or_insert(0) += 1;
+        }
+        for artist in &track.artists {
+            *artists.entry(artist.name.clone()).or_insert(0) += 1;
+        }
+        let mut track_genres: Vec<&String> =
+            track.artists.iter().filter_map(|artist| genres.get(&artist.id)).flatten().collect();
+        track_genres.sort();
+        track_genres.dedup();
+        for genre in track_genres {
+            *genre_counts.entry(genre.clone()).or_insert(0) += 1;
+        }
+    }
+
+    let release_years = match (years.keys().min(), years.keys().max()) {
+        (Some(&first), Some(&last)) => (first..=last).map(|year| (year, years.get(&year).copied().unwrap_or(0))).collect(),
+        _ => Vec::new(),
+    };
+
+    let added_timeline = match (months.keys().min(), months.keys().max()) {
+        (Some(first), Some(last)) => month_range(first, last)
+            .into_iter()
+            .map(|month| {
+                let count = months.get(&month).copied().unwrap_or(0);
+                (month, count)
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let last_added = items.iter().map(|item| &item.added_at).filter(|added| !added.is_empty()).max().cloned();
+    let month_ago = (chrono::Utc::now() - chrono::Duration::days(30)).to_rfc3339();
+    let added_last_30_days = items.iter().filter(|item| item.added_at > month_ago).count();
+
+    PlaylistStats {
+        track_count: items.len(),
+        total_duration_ms: items.iter().map(|item| u64::from(item.track.duration_ms())).sum(),
+        average_popularity,
+        median_popularity,
+        explicit_share: if items.is_empty() { 0.0 } else { explicit as f64 / items.len() as f64 },
+        release_years,
+        top_artists: top_entries(artists),
+        top_genres: top_entries(genre_counts),
+        added_timeline,
+        last_added,
+        added_last_30_days,
+    }
+}
+
+/// Every month from `first` to `last` (both "YYYY-MM"), inclusive
+pub fn month_range(first: &str, last: &str) -> Vec<String> {
+    let parse = |month: &str| -> Option<(i32, u32)> {
+        let (year, month) = month.split_once('-')?;
+        Some((year.parse().ok()?, month.parse().ok()?))
+    };
+    let (Some(mut current), Some(last)) = (parse(first), parse(last)) else {
+        return Vec::new();
+    };
+
+    let mut months = Vec::new();
+    while current <= last {
+        months.push(format!("{}-{:02}", current.0, current.1));
+        current = if current.1 == 12 { (current.0 + 1, 1) } else { (current.0, current.1 + 1) };
+    }
+    months
+}
+
+fn top_entries(counts: HashMap<String, usize>) -> Vec<(String, usize)> {
+    let mut entries: Vec<(String, usize)> = counts.into_iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    entries.truncate(TOP_LIST_SIZE);
+    entries
+}
\ No newline at end of file
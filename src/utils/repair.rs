@@ -0,0 +1,197 @@
+use crate::api::SpotifyClient;
+use crate::models::*;
+use crate::utils::{insertion_entry, normalize_title, record_operations, removal_entry};
+use crate::AppContext;
+use anyhow::Result;
+
+/// Results requested per search when looking for a replacement
+const SEARCH_LIMIT: u32 = 10;
+
+/// Largest duration difference (ms) for a search result to count as the same recording
+const MAX_DURATION_DIFFERENCE_MS: u32 = 10_000;
+
+/// How the replacement for a track was found
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RepairMethod {
+    /// Spotify already plays another version in this market, the playlist just points at the old one
+    Relinked,
+    /// Another release of the same recording, matched by ISRC
+    Isrc,
+    /// A release with the same title and artist and a similar duration
+    Search,
+}
+
+impl RepairMethod {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Relinked => "Relinked by Spotify",
+            Self::Isrc => "Same recording (ISRC)",
+            Self::Search => "Title and artist match",
+        }
+    }
+}
+
+/// A playlist entry that can be swapped for a playable version
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairCandidate {
+    pub position: usize,
+    /// URI stored in the playlist
+    pub original_uri: PlayableUri,
+    pub original: Track,
+    pub replacement: Option<Track>,
+    pub method: Option<RepairMethod>,
+}
+
+impl RepairCandidate {
+    pub fn replacement_uri(&self) -> Option<PlayableUri> {
+        self.replacement.as_ref().map(Track::playable_uri)
+    }
+}
+
+/// Positions and tracks of entries Spotify reported as not playable in the requested market
+pub fn unplayable_tracks(items: &[PlaylistTrackItem]) -> Vec<(usize, Track)> {
+    items
+        .iter()
+        .enumerate()
+        .filter_map(|(position, item)| item.as_track().filter(|t| t.is_unplayable()).map(|t| (position, t.clone())))
+        .collect()
+}
+
+/// Look up a playable replacement for every unplayable entry, and list relinked entries whose
+/// playable version can be stored in the playlist directly
+pub async fn find_repair_candidates(
+    client: &SpotifyClient,
+    items: &[PlaylistTrackItem],
+    market: &str,
+) -> Vec<RepairCandidate> {
+    let mut candidates = Vec::new();
+
+    for (position, item) in items.iter().enumerate() {
+        let Some(track) = item.as_track() else {
+            continue;
+        };
+        let original_uri = PlayableUri::Track(TrackId::new_unchecked(track.original_id()));
+
+        if track.is_unplayable() {
+            let found = match find_replacement(client, track, market).await {
+                Ok(found) => found,
+                Err(e) => {
+                    eprintln!("Failed to find a replacement for {}: {}", track.name, e);
+                    None
+                }
+            };
+            let (replacement, method) = match found {
+                Some((replacement, method)) => (Some(replacement), Some(method)),
+                None => (None, None),
+            };
+            candidates.push(RepairCandidate { position, original_uri, original: track.clone(), replacement, method });
+        } else if track.linked_from.is_some() {
+            candidates.push(RepairCandidate {
+                position,
+                original_uri,
+                original: track.clone(),
+                replacement: Some(track.clone()),
+                method: Some(RepairMethod::Relinked),
+            });
+        }
+    }
+
+    candidates
+}
+
+/// Find a playable version of `track`, first by ISRC and then by title and artist
+pub async fn find_replacement(
+    client: &SpotifyClient,
+    track: &Track,
+    market: &str,
+) -> Result<Option<(Track, RepairMethod)>> {
+    let is_other_playable = |candidate: &Track| {
+        !candidate.is_unplayable() && candidate.id != track.id && candidate.id != track.original_id()
+    };
+
+    if let Some(isrc) = track.external_ids.isrc.as_deref().filter(|isrc| !isrc.is_empty()) {
+        let results = client.search_tracks(&format!("isrc:{}", isrc), Some(market), SEARCH_LIMIT).await?;
+        if let Some(found) = results.into_iter().find(|candidate| is_other_playable(candidate)) {
+            return Ok(Some((found, RepairMethod::Isrc)));
+        }
+    }
+
+    let Some(artist) = track.artists.first() else {
+        return Ok(None);
+    };
+    let query = format!("track:{} artist:{}", track.name, artist.name);
+    let title = normalize_title(&track.name);
+    let artist_name = artist.name.to_lowercase();
+
+    let found = client
+        .search_tracks(&query, Some(market), SEARCH_LIMIT)
+        .await?
+        .into_iter()
+        .filter(|candidate| is_other_playable(candidate))
+        .filter(|candidate| normalize_title(&candidate.name) == title)
+        .filter(|candidate| candidate.artists.iter().any(|a| a.name.to_lowercase() == artist_name))
+        .filter(|candidate| candidate.duration_ms.abs_diff(track.duration_ms) <= MAX_DURATION_DIFFERENCE_MS)
+        .min_by_key(|candidate| candidate.duration_ms.abs_diff(track.duration_ms));
+
+    Ok(found.map(|found| (found, RepairMethod::Search)))
+}
+
+/// Swap every candidate's replacement in at the candidate's position of `playlist`, whose
+/// entries were `items`. Fails if the playlist was changed since it was loaded. Every step is
+/// recorded in the operation journal as it succeeds, so swaps made before a failure can still be
+/// undone. Returns the IDs of the recorded operations.
+pub async fn apply_repairs(
+    client: &SpotifyClient,
+    context: &AppContext,
+    playlist: &Playlist,
+    items: &[PlaylistTrackItem],
+    candidates: &[RepairCandidate],
+) -> Result<Vec<String>> {
+    let playlist_id = playlist.playlist_id();
+    if let Some(snapshot_id) = &playlist.snapshot_id {
+        client.check_snapshot(&playlist_id, snapshot_id).await?;
+    }
+
+    // Working from the end keeps the positions of the remaining candidates valid
+    let mut swaps: Vec<&RepairCandidate> = candidates.iter().collect();
+    swaps.sort_by_key(|c| std::cmp::Reverse(c.position));
+
+    let failed = |e: anyhow::Error, done: &[String]| {
+        if done.is_empty() {
+            e
+        } else {
+            anyhow::anyhow!("{} (the tracks swapped before can be undone from the history)", e)
+        }
+    };
+
+    // Each entry's "before" snapshot is the one the previous step left
+    let mut current = playlist.clone();
+    let mut operation_ids = Vec::new();
+    for candidate in swaps {
+        let (Some(replacement), Some(replacement_uri)) = (&candidate.replacement, candidate.replacement_uri()) else {
+            continue;
+        };
+        let position = candidate.position;
+
+        // The old version is removed first, so the journal positions match the loaded playlist
+        let removals = vec![(candidate.original_uri.clone(), position)];
+        let snapshot = client
+            .remove_tracks_from_playlist(&playlist_id, removals.clone(), current.snapshot_id.as_deref())
+            .await
+            .map_err(|e| failed(e, &operation_ids))?;
+        let entry = removal_entry("Repaired unplayable tracks", &current, items, &removals, Some(snapshot.clone()));
+        operation_ids.extend(record_operations(context, vec![entry]));
+        current.snapshot_id = Some(snapshot);
+
+        let additions = vec![(replacement_uri.clone(), position, replacement.name.clone())];
+        let snapshot = client
+            .insert_tracks_at(&playlist_id, vec![replacement_uri], position)
+            .await
+            .map_err(|e| failed(e, &operation_ids))?;
+        let entry = insertion_entry("Repaired unplayable tracks", &current, &additions, Some(snapshot.clone()));
+        operation_ids.extend(record_operations(context, vec![entry]));
+        current.snapshot_id = Some(snapshot);
+    }
+
+    Ok(operation_ids)
+}
\ No newline at end of file
@@ -0,0 +1,106 @@
+use crate::models::{PlaylistTrackItem, Track};
+use std::collections::HashSet;
+
+/// How the selected playlists are combined
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetOperation {
+    /// Every track of every playlist, once
+    Union,
+    /// Tracks found in all playlists
+    Intersection,
+    /// Tracks of the first playlist that are in none of the others
+    Difference,
+}
+
+impl SetOperation {
+    pub fn all() -> [SetOperation; 3] {
+        [Self::Union, Self::Intersection, Self::Difference]
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Union => "union",
+            Self::Intersection => "intersection",
+            Self::Difference => "difference",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Union => "Merge (union)",
+            Self::Intersection => "Common tracks (intersection)",
+            Self::Difference => "Subtract (A minus others)",
+        }
+    }
+
+    /// Symbol used between playlist names in the generated title
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Union => "+",
+            Self::Intersection => "∩",
+            Self::Difference => "−",
+        }
+    }
+}
+
+/// Order of the combined tracks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CombineOrder {
+    /// All tracks of the first playlist, then the second and so on
+    Preserve,
+    /// One track of each playlist in turn
+    Interleave,
+}
+
+/// Combine playlists, de-duplicating by track and by ISRC so different releases of a song count once.
+/// Only Spotify tracks take part, episodes, local files and unavailable entries are left out.
+pub fn combine_playlists(lists: &[Vec<PlaylistTrackItem>], operation: SetOperation, order: CombineOrder) -> Vec<Track> {
+    let memberships: Vec<HashSet<String>> = lists
+        .iter()
+        .map(|items| items.iter().filter_map(|item| item.as_track()).flat_map(track_keys).collect())
+        .collect();
+    let contains = |list: usize, track: &Track| track_keys(track).iter().any(|key| memberships[list].contains(key));
+
+    // Only the first playlist provides tracks for a difference
+    let sources = match operation {
+        SetOperation::Difference => &lists[..lists.len().min(1)],
+        _ => lists,
+    };
+
+    let candidates: Vec<&Track> = match order {
+        CombineOrder::Preserve => sources.iter().flatten().filter_map(|item| item.as_track()).collect(),
+        CombineOrder::Interleave => {
+            let longest = sources.iter().map(|items| items.len()).max().unwrap_or(0);
+            (0..longest)
+                .flat_map(|index| sources.iter().filter_map(move |items| items.get(index)))
+                .filter_map(|item| item.as_track())
+                .collect()
+        }
+    };
+
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|track| match operation {
+            SetOperation::Union => true,
+            SetOperation::Intersection => (0..lists.len()).all(|list| contains(list, track)),
+            SetOperation::Difference => (1..lists.len()).all(|list| !contains(list, track)),
+        })
+        .filter(|track| {
+            // Keep a track only if none of its keys was seen before
+            let keys = track_keys(track);
+            let new = keys.iter().all(|key| !seen.contains(key));
+            seen.extend(keys);
+            new
+        })
+        .cloned()
+        .collect()
+}
+
+fn track_keys(track: &Track) -> Vec<String> {
+    let mut keys = vec![track.id.clone()];
+    if let Some(isrc) = &track.external_ids.isrc {
+        keys.push(format!("isrc:{}", isrc.to_uppercase()));
+    }
+    keys
+}
\ No newline at end of file
@@ -0,0 +1,281 @@
+use crate::api::SpotifyClient;
+use crate::models::*;
+use crate::utils::{fetch_artist_genres, load_data, replacement_entry, save_data};
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+/// File in the data directory holding the smart playlist definitions
+const SMART_PLAYLISTS_FILE: &str = "smart_playlists.json";
+
+/// File in the data directory accumulating recently played tracks
+const PLAY_HISTORY_FILE: &str = "play_history.json";
+
+/// Spotify returns at most 50 top tracks per time range
+const TOP_TRACKS_LIMIT: u32 = 50;
+
+pub fn load_smart_playlists() -> Vec<SmartPlaylist> {
+    load_data(SMART_PLAYLISTS_FILE).unwrap_or_default()
+}
+
+pub fn save_smart_playlists(playlists: &[SmartPlaylist]) {
+    if let Err(e) = save_data(SMART_PLAYLISTS_FILE, playlists) {
+        eprintln!("Failed to save smart playlists: {}", e);
+    }
+}
+
+/// Add recently played items to the local history, which only keeps the last 50 plays on Spotify
+pub fn record_plays(items: &[RecentlyPlayedItem]) {
+    let mut history: Vec<PlayRecord> = load_data(PLAY_HISTORY_FILE).unwrap_or_default();
+    let known: HashSet<(String, String)> = history
+        .iter()
+        .map(|play| (play.track_id.clone(), play.played_at.clone()))
+        .collect();
+
+    let new_plays: Vec<PlayRecord> = items
+        .iter()
+        .filter(|item| !known.contains(&(item.track.id.clone(), item.played_at.clone())))
+        .map(|item| PlayRecord {
+            track_id: item.track.id.clone(),
+            played_at: item.played_at.clone(),
+            artist_ids: item.track.artists.iter().map(|a| a.id.clone()).collect(),
+        })
+        .collect();
+
+    if new_plays.is_empty() {
+        return;
+    }
+
+    history.extend(new_plays);
+    if let Err(e) = save_data(PLAY_HISTORY_FILE, &history) {
+        eprintln!("Failed to save play history: {}", e);
+    }
+}
+
+/// Number of recorded plays per track ID
+pub fn play_counts() -> HashMap<String, usize> {
+    let history: Vec<PlayRecord> = load_data(PLAY_HISTORY_FILE).unwrap_or_default();
+    let mut counts = HashMap::new();
+    for play in history {
+        *counts.entry(play.track_id).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Number of recorded plays per artist ID
+pub fn artist_play_counts() -> HashMap<String, usize> {
+    let history: Vec<PlayRecord> = load_data(PLAY_HISTORY_FILE).unwrap_or_default();
+    let mut counts = HashMap::new();
+    for artist_id in history.into_iter().flat_map(|play| play.artist_ids) {
+        *counts.entry(artist_id).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Track from one of the sources with the values that are not part of the track itself
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleCandidate {
+    pub track: Track,
+    pub added_at: Option<String>,
+    pub top_rank: Option<usize>,
+    pub genres: Vec<String>,
+    pub play_count: usize,
+}
+
+/// Collect the tracks of all sources (first occurrence wins) and keep those matching the rules
+pub async fn evaluate_smart_playlist(client: &SpotifyClient, rule: &SmartPlaylist) -> Result<Vec<RuleCandidate>> {
+    let mut candidates: Vec<RuleCandidate> = Vec::new();
+    let mut seen = HashSet::new();
+
+    for source in &rule.sources {
+        let tracks: Vec<(Track, Option<String>, Option<usize>)> = match source {
+            RuleSource::TopTracks { time_range } => client
+                .get_top_tracks(TOP_TRACKS_LIMIT, time_range)
+                .await?
+                .into_iter()
+                .enumerate()
+                .map(|(rank, track)| (track, None, Some(rank + 1)))
+                .collect(),
+            RuleSource::SavedTracks => client
+                .get_saved_tracks()
+                .await?
+                .into_iter()
+                .map(|saved| (saved.track, Some(saved.added_at), None))
+                .collect(),
+            RuleSource::RecentlyPlayed => {
+                let recent = client.get_recently_played(50).await?;
+                record_plays(&recent);
+                recent.into_iter().map(|item| (item.track, None, None)).collect()
+            }
+            RuleSource::Playlist { id, .. } => client
+                .get_playlist_tracks(&PlaylistId::from_id(id)?, None)
+                .await?
+                .into_iter()
+                .filter_map(|item| match item.track {
+                    PlaylistItem::Track(track) => Some((*track, Some(item.added_at), None)),
+                    _ => None,
+                })
+                .collect(),
+        };
+
+        for (track, added_at, top_rank) in tracks {
+            if seen.insert(track.id.clone()) {
+                candidates.push(RuleCandidate {
+                    track,
+                    added_at,
+                    top_rank,
+                    genres: Vec::new(),
+                    play_count: 0,
+                });
+            }
+        }
+    }
+
+    let fields: HashSet<RuleField> = rule.conditions.iter().map(|c| c.field).collect();
+
+    // Genres belong to artists, only look them up when a condition needs them
+    if fields.contains(&RuleField::Genre) {
+        let genres = fetch_artist_genres(client, candidates.iter().map(|c| &c.track)).await?;
+
+        for candidate in candidates.iter_mut() {
+            candidate.genres = candidate
+                .track
+                .artists
+                .iter()
+                .flat_map(|a| genres.get(&a.id).cloned().unwrap_or_default())
+                .collect();
+        }
+    }
+
+    if fields.contains(&RuleField::PlayCount) {
+        let counts = play_counts();
+        for candidate in candidates.iter_mut() {
+            candidate.play_count = counts.get(&candidate.track.id).copied().unwrap_or(0);
+        }
+    }
+
+    let mut matching: Vec<RuleCandidate> = candidates
+        .into_iter()
+        .filter(|candidate| matches_rules(candidate, &rule.conditions, rule.match_all))
+        .collect();
+
+    if let Some(limit) = rule.limit {
+        matching.truncate(limit);
+    }
+
+    Ok(matching)
+}
+
+/// Create the Spotify playlist on the first run and replace its tracks on later runs.
+/// Returns the updated definition, the number of tracks written and, when an existing playlist
+/// was rewritten, the journal entry to undo that.
+pub async fn materialize_smart_playlist(
+    client: &SpotifyClient,
+    rule: &SmartPlaylist,
+) -> Result<(SmartPlaylist, usize, Option<JournalEntry>)> {
+    let tracks = evaluate_smart_playlist(client, rule).await?;
+    let uris: Vec<PlayableUri> = tracks.iter().map(|candidate| candidate.track.playable_uri()).collect();
+
+    // The previous playlist may have been deleted (unfollowed) in the meantime. Any other error
+    // fails the refresh rather than creating a second copy.
+    let existing = match rule.target_playlist_id.as_deref().map(PlaylistId::from_id) {
+        Some(Ok(id)) => {
+            let user = client.get_current_user().await?;
+            let following = client.is_following_playlist(&id, &UserId::new_unchecked(user.id)).await?;
+            following.then_some(id)
+        }
+        _ => None,
+    };
+
+    let (playlist_id, entry) = match existing {
+        Some(id) => {
+            // The previous contents are kept in the journal, so a refresh can be undone
+            let playlist = client.get_playlist(&id).await?;
+            let previous = client.get_playlist_tracks(&id, None).await?;
+            let new_tracks: Vec<(PlayableUri, String)> =
+                uris.iter().cloned().zip(tracks.iter().map(|candidate| candidate.track.name.clone())).collect();
+
+            let snapshot = client.replace_playlist_items(&id, uris).await?;
+            let entry =
+                replacement_entry("Refreshed smart playlist", &playlist, &previous, &new_tracks, Some(snapshot));
+            (id.to_string(), Some(entry))
+        }
+        None => {
+            let created = client.create_playlist(&rule.name, &rule.description, false, false).await?;
+            if !uris.is_empty() {
+                client.add_tracks_to_playlist(&created.playlist_id(), uris).await?;
+            }
+            (created.id, None)
+        }
+    };
+
+    let mut updated = rule.clone();
+    updated.target_playlist_id = Some(playlist_id);
+    updated.last_materialized = Some(chrono::Utc::now().to_rfc3339());
+
+    Ok((updated, tracks.len(), entry))
+}
+
+fn matches_rules(candidate: &RuleCandidate, conditions: &[RuleCondition], match_all: bool) -> bool {
+    if conditions.is_empty() {
+        return true;
+    }
+
+    let mut results = conditions.iter().map(|condition| matches_condition(candidate, condition));
+    if match_all {
+        results.all(|matched| matched)
+    } else {
+        results.any(|matched| matched)
+    }
+}
+
+fn matches_condition(candidate: &RuleCandidate, condition: &RuleCondition) -> bool {
+    let track = &candidate.track;
+    let value = condition.value.trim().to_lowercase();
+
+    match condition.field.kind() {
+        FieldKind::Text => {
+            let texts: Vec<String> = match condition.field {
+                RuleField::TrackName => vec![track.name.to_lowercase()],
+                RuleField::ArtistName => track.artists.iter().map(|a| a.name.to_lowercase()).collect(),
+                RuleField::AlbumName => vec![track.album.name.to_lowercase()],
+                _ => candidate.genres.iter().map(|g| g.to_lowercase()).collect(),
+            };
+
+            match condition.operator {
+                RuleOperator::Contains => texts.iter().any(|text| text.contains(&value)),
+                RuleOperator::NotContains => !texts.iter().any(|text| text.contains(&value)),
+                RuleOperator::NotEquals => !texts.contains(&value),
+                _ => texts.contains(&value),
+            }
+        }
+        FieldKind::Flag => {
+            let expected = matches!(value.as_str(), "true" | "yes" | "1");
+            track.explicit == expected
+        }
+        FieldKind::Number => {
+            let actual = match condition.field {
+                RuleField::ReleaseYear => year_of(&track.album.release_date),
+                RuleField::Popularity => track.popularity.map(f64::from),
+                RuleField::DurationSeconds => Some(f64::from(track.duration_ms) / 1000.0),
+                RuleField::AddedYear => candidate.added_at.as_deref().and_then(year_of),
+                RuleField::TopRank => candidate.top_rank.map(|rank| rank as f64),
+                _ => Some(candidate.play_count as f64),
+            };
+            let (Some(actual), Ok(expected)) = (actual, value.parse::<f64>()) else {
+                // Tracks without the value (e.g. no top rank) never match a numeric condition
+                return false;
+            };
+
+            match condition.operator {
+                RuleOperator::LessThan => actual < expected,
+                RuleOperator::GreaterThan => actual > expected,
+                RuleOperator::NotEquals => actual != expected,
+                _ => actual == expected,
+            }
+        }
+    }
+}
+
+fn year_of(date: &str) -> Option<f64> {
+    date.get(..4)?.parse().ok()
+}
\ No newline at end of file
@@ -0,0 +1,197 @@
+use crate::api::SpotifyClient;
+use crate::models::{PlayableUri, PlaylistId, PlaylistItem, PlaylistTrackItem};
+use anyhow::Result;
+use std::cmp::Ordering;
+
+/// Playlist field a sort can be applied on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortField {
+    Title,
+    Artist,
+    Album,
+    ReleaseDate,
+    AddedAt,
+    Duration,
+    Popularity,
+}
+
+impl SortField {
+    pub fn all() -> [SortField; 7] {
+        [
+            Self::Title,
+            Self::Artist,
+            Self::Album,
+            Self::ReleaseDate,
+            Self::AddedAt,
+            Self::Duration,
+            Self::Popularity,
+        ]
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Title => "title",
+            Self::Artist => "artist",
+            Self::Album => "album",
+            Self::ReleaseDate => "release_date",
+            Self::AddedAt => "added_at",
+            Self::Duration => "duration",
+            Self::Popularity => "popularity",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<SortField> {
+        Self::all().into_iter().find(|field| field.key() == key)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Title => "Title",
+            Self::Artist => "Artist",
+            Self::Album => "Album",
+            Self::ReleaseDate => "Release date",
+            Self::AddedAt => "Date added",
+            Self::Duration => "Duration",
+            Self::Popularity => "Popularity",
+        }
+    }
+
+    fn compare(&self, a: &PlaylistTrackItem, b: &PlaylistTrackItem) -> Ordering {
+        let artist = |item: &PlaylistTrackItem| item.track.first_artist_name().to_lowercase();
+        // Episodes, local files and unavailable entries have no release date or popularity
+        let release_date = |item: &PlaylistTrackItem| match &item.track {
+            PlaylistItem::Track(track) => track.album.release_date.clone(),
+            PlaylistItem::Episode(episode) => episode.release_date.clone(),
+            _ => String::new(),
+        };
+        let popularity = |item: &PlaylistTrackItem| item.as_track().and_then(|track| track.popularity).unwrap_or(0);
+
+        match self {
+            Self::Title => a.track.name().to_lowercase().cmp(&b.track.name().to_lowercase()),
+            Self::Artist => artist(a).cmp(&artist(b)),
+            Self::Album => a.track.album_name().to_lowercase().cmp(&b.track.album_name().to_lowercase()),
+            // "YYYY", "YYYY-MM" and "YYYY-MM-DD" all sort correctly as text
+            Self::ReleaseDate => release_date(a).cmp(&release_date(b)),
+            Self::AddedAt => a.added_at.cmp(&b.added_at),
+            Self::Duration => a.track.duration_ms().cmp(&b.track.duration_ms()),
+            Self::Popularity => popularity(a).cmp(&popularity(b)),
+        }
+    }
+}
+
+/// One level of a multi-key sort
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SortKey {
+    pub field: SortField,
+    pub descending: bool,
+}
+
+/// How a new order is written to Spotify
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ApplyMethod {
+    /// Move ranges of tracks, keeps "date added" but needs a request per moved range
+    Reorder,
+    /// Rewrite the playlist in chunks of 100, fast but resets "date added"
+    Replace,
+}
+
+/// New order of a playlist as indices into `items`; equal tracks keep their relative order
+pub fn sorted_order(items: &[PlaylistTrackItem], keys: &[SortKey]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by(|&a, &b| {
+        keys.iter()
+            .map(|key| {
+                let ordering = key.field.compare(&items[a], &items[b]);
+                if key.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            })
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    });
+    order
+}
+
+/// Order that turns `current` URIs back into `target` URIs, `None` if they hold different tracks
+pub fn order_from_uris(current: &[String], target: &[String]) -> Option<Vec<usize>> {
+    if current.len() != target.len() {
+        return None;
+    }
+
+    let mut used = vec![false; current.len()];
+    target
+        .iter()
+        .map(|uri| {
+            let index = (0..current.len()).find(|&i| !used[i] && current[i] == *uri)?;
+            used[index] = true;
+            Some(index)
+        })
+        .collect()
+}
+
+/// Reorder requests (range_start, range_length, insert_before) that produce `order`.
+/// Runs that are already in the right sequence are moved together.
+pub fn reorder_moves(order: &[usize]) -> Vec<(usize, usize, usize)> {
+    let mut current: Vec<usize> = (0..order.len()).collect();
+    let mut moves = Vec::new();
+    let mut index = 0;
+
+    while index < order.len() {
+        let position = current.iter().position(|&item| item == order[index]).unwrap_or(index);
+        if position == index {
+            index += 1;
+            continue;
+        }
+
+        let mut length = 1;
+        while position + length < current.len()
+            && index + length < order.len()
+            && current[position + length] == order[index + length]
+        {
+            length += 1;
+        }
+
+        let moved: Vec<usize> = current.drain(position..position + length).collect();
+        current.splice(index..index, moved);
+        moves.push((position, length, index));
+        index += length;
+    }
+
+    moves
+}
+
+/// Write `order` to the playlist, returning the new snapshot ID
+pub async fn apply_order(
+    client: &SpotifyClient,
+    playlist_id: &PlaylistId,
+    items: &[PlaylistTrackItem],
+    order: &[usize],
+    snapshot_id: Option<String>,
+    method: ApplyMethod,
+) -> Result<String> {
+    match method {
+        ApplyMethod::Reorder => {
+            let mut snapshot_id = snapshot_id;
+            for (range_start, range_length, insert_before) in reorder_moves(order) {
+                let new_snapshot_id = client
+                    .reorder_playlist_items(playlist_id, range_start, range_length, insert_before, snapshot_id.as_deref())
+                    .await?;
+                snapshot_id = Some(new_snapshot_id);
+            }
+            Ok(snapshot_id.unwrap_or_default())
+        }
+        ApplyMethod::Replace => {
+            // Local files and unavailable tracks can't be added back, rewriting would drop them
+            let uris: Vec<PlayableUri> = order.iter().filter_map(|&index| items[index].track.addable_uri()).collect();
+            if uris.len() < order.len() {
+                anyhow::bail!(
+                    "Rewriting would drop {} local or unavailable track(s), reorder the playlist instead",
+                    order.len() - uris.len()
+                );
+            }
+            client.replace_playlist_items(playlist_id, uris).await
+        }
+    }
+}
\ No newline at end of file
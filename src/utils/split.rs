@@ -0,0 +1,176 @@
+use crate::models::{PlaylistTrackItem, Track};
+use std::collections::HashMap;
+
+/// How a playlist is partitioned
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SplitMode {
+    /// First genre of the primary artist
+    Genre,
+    /// Release decade of the album
+    Decade,
+    PrimaryArtist,
+    /// Fixed number of tracks per playlist
+    ChunkSize(usize),
+    /// Maximum total duration per playlist, in minutes
+    Duration(u32),
+}
+
+impl SplitMode {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Genre => "genre",
+            Self::Decade => "decade",
+            Self::PrimaryArtist => "artist",
+            Self::ChunkSize(_) => "size",
+            Self::Duration(_) => "duration",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Genre => "Genre",
+            Self::Decade => "Decade",
+            Self::PrimaryArtist => "Artist",
+            Self::ChunkSize(_) => "Track count",
+            Self::Duration(_) => "Duration",
+        }
+    }
+
+    /// Text used in generated descriptions, e.g. "split by decade"
+    pub fn describe(&self) -> String {
+        match self {
+            Self::ChunkSize(size) => format!("split into parts of {} tracks", size),
+            Self::Duration(minutes) => format!("split into parts of up to {} minutes", minutes),
+            _ => format!("split by {}", self.label().to_lowercase()),
+        }
+    }
+}
+
+/// Tracks that end up in one new playlist
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitGroup {
+    pub label: String,
+    pub tracks: Vec<Track>,
+}
+
+impl SplitGroup {
+    pub fn duration_ms(&self) -> u64 {
+        self.tracks.iter().map(|t| u64::from(t.duration_ms)).sum()
+    }
+}
+
+/// Partition a playlist. `genres` maps artist IDs to their genres and is only used by
+/// `SplitMode::Genre`; groups smaller than `min_size` are collected in an "Other" group.
+pub fn split_playlist(
+    items: &[PlaylistTrackItem],
+    mode: SplitMode,
+    genres: &HashMap<String, Vec<String>>,
+    min_size: usize,
+) -> Vec<SplitGroup> {
+    // Only Spotify tracks can be added to the new playlists
+    let tracks = items.iter().filter_map(|item| item.as_track().cloned());
+
+    let mut groups = match mode {
+        SplitMode::ChunkSize(size) => {
+            let tracks: Vec<Track> = tracks.collect();
+            return tracks
+                .chunks(size.max(1))
+                .enumerate()
+                .map(|(index, chunk)| SplitGroup {
+                    label: format!("Part {}", index + 1),
+                    tracks: chunk.to_vec(),
+                })
+                .collect();
+        }
+        SplitMode::Duration(minutes) => {
+            let limit = u64::from(minutes.max(1)) * 60_000;
+            let mut parts: Vec<SplitGroup> = Vec::new();
+            for track in tracks {
+                let fits = parts
+                    .last()
+                    .map(|part| part.duration_ms() + u64::from(track.duration_ms) <= limit)
+                    .unwrap_or(false);
+                if !fits {
+                    parts.push(SplitGroup {
+                        label: format!("Part {}", parts.len() + 1),
+                        tracks: Vec::new(),
+                    });
+                }
+                if let Some(part) = parts.last_mut() {
+                    part.tracks.push(track);
+                }
+            }
+            return parts;
+        }
+        SplitMode::Genre => group_by(tracks, |track| {
+            track
+                .artists
+                .first()
+                .and_then(|artist| genres.get(&artist.id))
+                .and_then(|genres| genres.first())
+                .map(|genre| title_case(genre))
+                .unwrap_or_else(|| "Unknown Genre".to_string())
+        }),
+        SplitMode::Decade => {
+            let mut groups = group_by(tracks, |track| {
+                match track.album.release_date.get(..4).and_then(|year| year.parse::<u32>().ok()) {
+                    Some(year) => format!("{}s", year / 10 * 10),
+                    None => "Unknown Decade".to_string(),
+                }
+            });
+            groups.sort_by(|a, b| a.label.cmp(&b.label));
+            groups
+        }
+        SplitMode::PrimaryArtist => group_by(tracks, |track| {
+            track
+                .artists
+                .first()
+                .map(|artist| artist.name.clone())
+                .unwrap_or_else(|| "Unknown Artist".to_string())
+        }),
+    };
+
+    // Fold groups that are too small into a single "Other" group
+    if min_size > 1 {
+        let (kept, small): (Vec<SplitGroup>, Vec<SplitGroup>) =
+            groups.into_iter().partition(|group| group.tracks.len() >= min_size);
+        groups = kept;
+        if !small.is_empty() {
+            groups.push(SplitGroup {
+                label: "Other".to_string(),
+                tracks: small.into_iter().flat_map(|group| group.tracks).collect(),
+            });
+        }
+    }
+
+    groups
+}
+
+/// Group tracks by label, keeping groups in order of their first track
+fn group_by(tracks: impl Iterator<Item = Track>, label: impl Fn(&Track) -> String) -> Vec<SplitGroup> {
+    let mut groups: Vec<SplitGroup> = Vec::new();
+    for track in tracks {
+        let label = label(&track);
+        match groups.iter_mut().find(|group| group.label == label) {
+            Some(group) => group.tracks.push(track),
+            None => groups.push(SplitGroup {
+                label,
+                tracks: vec![track],
+            }),
+        }
+    }
+    groups
+}
+
+fn title_case(text: &str) -> String {
+    text.split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
\ No newline at end of file
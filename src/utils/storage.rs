@@ -0,0 +1,60 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Directory where InfoSpot keeps its local data (journals, snapshots, saved state)
+pub fn data_dir() -> PathBuf {
+    #[cfg(target_os = "windows")]
+    let base = std::env::var("APPDATA").map(PathBuf::from);
+
+    #[cfg(not(target_os = "windows"))]
+    let base = std::env::var("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| std::env::var("HOME").map(|home| PathBuf::from(home).join(".local").join("share")));
+
+    base.unwrap_or_else(|_| std::env::temp_dir()).join("info_spot")
+}
+
+/// Load a JSON file from the data directory, returning `None` if it is missing or unreadable
+pub fn load_data<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let content = std::fs::read_to_string(data_dir().join(name)).ok()?;
+
+    match serde_json::from_str(&content) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            eprintln!("Failed to parse stored {}: {}", name, e);
+            None
+        }
+    }
+}
+
+/// Save a value as JSON into the data directory
+pub fn save_data<T: Serialize + ?Sized>(name: &str, value: &T) -> Result<(), Box<dyn std::error::Error>> {
+    let path = data_dir().join(name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(path, serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+/// Delete a stored file, ignoring files that do not exist
+pub fn remove_data(name: &str) {
+    let _ = std::fs::remove_file(data_dir().join(name));
+}
+
+/// Names of the files in a subdirectory of the data directory, sorted
+pub fn list_data(dir: &str) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(data_dir().join(dir)) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    names.sort();
+    names
+}
\ No newline at end of file
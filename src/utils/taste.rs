@@ -0,0 +1,101 @@
+use crate::models::*;
+use crate::utils::RangeGenres;
+use std::collections::BTreeMap;
+
+/// Listening habits derived from the top lists of one time range
+#[derive(Debug, Clone, PartialEq)]
+pub struct TasteMetrics {
+    pub time_range: String,
+    /// Mean Spotify popularity of the top tracks and artists, from 0 (obscure) to 100 (mainstream)
+    pub mainstream_score: Option<f64>,
+    /// How evenly the top tracks spread over their artists, from 0 (one artist) to 1 (all different)
+    pub artist_diversity: f64,
+    /// How evenly the genre weight spreads over the genres, from 0 to 1
+    pub genre_diversity: f64,
+    pub average_release_year: Option<f64>,
+    /// Share of the top tracks per decade, oldest first
+    pub decades: Vec<(i32, f64)>,
+    /// Share of the top tracks marked explicit
+    pub explicit_share: f64,
+}
+
+impl TasteMetrics {
+    pub fn mainstream_label(&self) -> &'static str {
+        match self.mainstream_score {
+            Some(score) if score >= 70.0 => "Mainstream",
+            Some(score) if score >= 50.0 => "Balanced",
+            Some(score) if score >= 30.0 => "Off the beaten path",
+            Some(_) => "Obscure",
+            None => "Unknown",
+        }
+    }
+}
+
+/// Shannon entropy of the weights divided by its maximum for that many categories, so 1 means
+/// spread perfectly evenly
+fn normalized_entropy(weights: &[f64]) -> f64 {
+    let weights: Vec<f64> = weights.iter().copied().filter(|w| *w > 0.0).collect();
+    let total: f64 = weights.iter().sum();
+    if weights.len() < 2 || total <= 0.0 {
+        return 0.0;
+    }
+
+    let entropy: f64 = weights.iter().map(|w| w / total).map(|p| -p * p.ln()).sum();
+    entropy / (weights.len() as f64).ln()
+}
+
+fn release_year(track: &Track) -> Option<i32> {
+    track.album.release_date.get(..4).and_then(|year| year.parse().ok())
+}
+
+pub fn taste_metrics(range: &RangeGenres) -> TasteMetrics {
+    let tracks = &range.tracks;
+    let track_count = tracks.len().max(1) as f64;
+
+    let popularities: Vec<f64> = tracks
+        .iter()
+        .filter_map(|t| t.popularity)
+        .chain(range.artists.iter().filter_map(|a| a.popularity))
+        .map(f64::from)
+        .collect();
+    let mainstream_score =
+        (!popularities.is_empty()).then(|| popularities.iter().sum::<f64>() / popularities.len() as f64);
+
+    // Featured artists share the credit for a track
+    let mut artist_weights: BTreeMap<&str, f64> = BTreeMap::new();
+    for track in tracks {
+        let share = 1.0 / track.artists.len().max(1) as f64;
+        for artist in &track.artists {
+            *artist_weights.entry(artist.id.as_str()).or_insert(0.0) += share;
+        }
+    }
+    let artist_diversity = normalized_entropy(&artist_weights.into_values().collect::<Vec<_>>());
+
+    let genre_shares: Vec<f64> = range.profile.genres.iter().map(|g| g.share).collect();
+    let genre_diversity = normalized_entropy(&genre_shares);
+
+    let years: Vec<i32> = tracks.iter().filter_map(release_year).collect();
+    let average_release_year =
+        (!years.is_empty()).then(|| years.iter().map(|&y| f64::from(y)).sum::<f64>() / years.len() as f64);
+
+    let mut decade_counts: BTreeMap<i32, usize> = BTreeMap::new();
+    for year in &years {
+        *decade_counts.entry(year / 10 * 10).or_insert(0) += 1;
+    }
+    let decades = decade_counts
+        .into_iter()
+        .map(|(decade, count)| (decade, count as f64 / years.len() as f64))
+        .collect();
+
+    let explicit_share = tracks.iter().filter(|t| t.explicit).count() as f64 / track_count;
+
+    TasteMetrics {
+        time_range: range.profile.time_range.clone(),
+        mainstream_score,
+        artist_diversity,
+        genre_diversity,
+        average_release_year,
+        decades,
+        explicit_share,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,249 @@
+use crate::api::SpotifyClient;
+use crate::models::*;
+use crate::utils::{fetch_range_genres, RangeGenres};
+use std::collections::HashSet;
+
+/// Genres stored per time range in an exported profile
+const PROFILE_GENRES: usize = 20;
+
+/// Genres of an artist listed in its profile entry
+const ARTIST_DETAIL_GENRES: usize = 2;
+
+/// Summarize the top lists of every time range into a profile to share with others
+pub fn build_taste_profile(user: &User, ranges: &[RangeGenres]) -> TasteProfile {
+    let ranges = ranges
+        .iter()
+        .map(|range| TasteProfileRange {
+            time_range: range.profile.time_range.clone(),
+            artists: range
+                .artists
+                .iter()
+                .enumerate()
+                .map(|(index, artist)| TasteProfileItem {
+                    rank: index + 1,
+                    id: artist.id.clone(),
+                    name: artist.name.clone(),
+                    detail: artist
+                        .genres
+                        .as_deref()
+                        .unwrap_or_default()
+                        .iter()
+                        .take(ARTIST_DETAIL_GENRES)
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                })
+                .collect(),
+            tracks: range
+                .tracks
+                .iter()
+                .enumerate()
+                .map(|(index, track)| TasteProfileItem {
+                    rank: index + 1,
+                    id: track.id.clone(),
+                    name: track.name.clone(),
+                    detail: track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", "),
+                })
+                .collect(),
+            genres: range
+                .profile
+                .genres
+                .iter()
+                .take(PROFILE_GENRES)
+                .map(|g| TasteProfileGenre { genre: g.genre.clone(), share: g.share })
+                .collect(),
+        })
+        .collect();
+
+    TasteProfile {
+        version: TASTE_PROFILE_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        user_id: user.id.clone(),
+        display_name: user.display_name.clone().unwrap_or_else(|| user.id.clone()),
+        ranges,
+    }
+}
+
+/// Fetch the logged in user's top lists and summarize them into a profile
+pub async fn fetch_taste_profile(client: &SpotifyClient) -> anyhow::Result<TasteProfile> {
+    let user = client.get_current_user().await?;
+    let ranges = fetch_range_genres(client).await?;
+    Ok(build_taste_profile(&user, &ranges))
+}
+
+pub fn write_taste_profile(profile: &TasteProfile, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::write(path, serde_json::to_string_pretty(profile)?)?;
+    Ok(())
+}
+
+pub fn read_taste_profile(path: &str) -> Result<TasteProfile, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)?;
+    let profile: TasteProfile = serde_json::from_str(&content)?;
+    if profile.version > TASTE_PROFILE_VERSION {
+        return Err(format!(
+            "profile format {} was written by a newer version of InfoSpot, this one reads up to {}",
+            profile.version, TASTE_PROFILE_VERSION
+        )
+        .into());
+    }
+    // The IDs are sent to the API later, so a hand edited file must not slip malformed ones through
+    for range in profile.ranges.iter() {
+        for artist in range.artists.iter() {
+            ArtistId::from_id(&artist.id).map_err(|e| format!("artist \"{}\": {}", artist.name, e))?;
+        }
+        for track in range.tracks.iter() {
+            TrackId::from_id(&track.id).map_err(|e| format!("track \"{}\": {}", track.name, e))?;
+        }
+    }
+    Ok(profile)
+}
+
+/// Track or artist in both profiles, with its rank on each side
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedItem {
+    pub item: TasteProfileItem,
+    pub their_rank: usize,
+}
+
+impl SharedItem {
+    /// Sum of both ranks, lower means both like it more
+    pub fn combined_rank(&self) -> usize {
+        self.item.rank + self.their_rank
+    }
+}
+
+/// How two profiles overlap in one time range
+#[derive(Debug, Clone, PartialEq)]
+pub struct TasteComparison {
+    pub time_range: String,
+    /// Shared artists out of all distinct artists of both sides, between 0 and 1
+    pub artist_overlap: f64,
+    /// Shared tracks out of all distinct tracks of both sides, between 0 and 1
+    pub track_overlap: f64,
+    /// Genre share both sides have in common, between 0 and 1
+    pub genre_overlap: f64,
+    /// Ordered by combined rank
+    pub shared_artists: Vec<SharedItem>,
+    pub shared_tracks: Vec<SharedItem>,
+    /// Items only one side has, by rank
+    pub my_artists: Vec<TasteProfileItem>,
+    pub their_artists: Vec<TasteProfileItem>,
+    pub my_tracks: Vec<TasteProfileItem>,
+    pub their_tracks: Vec<TasteProfileItem>,
+}
+
+impl TasteComparison {
+    /// Mean of the artist, track and genre overlaps
+    pub fn overall(&self) -> f64 {
+        (self.artist_overlap + self.track_overlap + self.genre_overlap) / 3.0
+    }
+}
+
+/// Tracks match by name and artists, as the same song can have different IDs in different markets
+fn track_key(item: &TasteProfileItem) -> String {
+    format!("{}|{}", item.name.to_lowercase(), item.detail.to_lowercase())
+}
+
+fn artist_key(item: &TasteProfileItem) -> String {
+    item.id.clone()
+}
+
+/// Items of both lists and of each list alone, plus the Jaccard index of the two
+fn split_items(
+    mine: &[TasteProfileItem],
+    theirs: &[TasteProfileItem],
+    key: fn(&TasteProfileItem) -> String,
+) -> (f64, Vec<SharedItem>, Vec<TasteProfileItem>, Vec<TasteProfileItem>) {
+    let my_keys: HashSet<String> = mine.iter().map(key).collect();
+    let their_keys: HashSet<String> = theirs.iter().map(key).collect();
+
+    let mut shared: Vec<SharedItem> = mine
+        .iter()
+        .filter_map(|item| {
+            let their_rank = theirs.iter().find(|t| key(t) == key(item))?.rank;
+            Some(SharedItem { item: item.clone(), their_rank })
+        })
+        .collect();
+    shared.sort_by_key(SharedItem::combined_rank);
+
+    let my_unique = mine.iter().filter(|item| !their_keys.contains(&key(item))).cloned().collect();
+    let their_unique = theirs.iter().filter(|item| !my_keys.contains(&key(item))).cloned().collect();
+
+    let union = my_keys.union(&their_keys).count();
+    let overlap = if union > 0 { my_keys.intersection(&their_keys).count() as f64 / union as f64 } else { 0.0 };
+
+    (overlap, shared, my_unique, their_unique)
+}
+
+/// Compare one time range of two profiles. Ranges missing on either side compare as empty.
+pub fn compare_taste_profiles(mine: &TasteProfile, theirs: &TasteProfile, time_range: &str) -> TasteComparison {
+    let empty = TasteProfileRange {
+        time_range: time_range.to_string(),
+        artists: Vec::new(),
+        tracks: Vec::new(),
+        genres: Vec::new(),
+    };
+    let my_range = mine.range(time_range).unwrap_or(&empty);
+    let their_range = theirs.range(time_range).unwrap_or(&empty);
+
+    let (artist_overlap, shared_artists, my_artists, their_artists) =
+        split_items(&my_range.artists, &their_range.artists, artist_key);
+    let (track_overlap, shared_tracks, my_tracks, their_tracks) =
+        split_items(&my_range.tracks, &their_range.tracks, track_key);
+
+    // Both genre lists are shares of a whole, so the sum of the smaller share per genre is their overlap
+    let genre_overlap = my_range
+        .genres
+        .iter()
+        .filter_map(|mine| {
+            let theirs = their_range.genres.iter().find(|g| g.genre == mine.genre)?;
+            Some(mine.share.min(theirs.share))
+        })
+        .sum::<f64>()
+        .min(1.0);
+
+    TasteComparison {
+        time_range: time_range.to_string(),
+        artist_overlap,
+        track_overlap,
+        genre_overlap,
+        shared_artists,
+        shared_tracks,
+        my_artists,
+        their_artists,
+        my_tracks,
+        their_tracks,
+    }
+}
+
+/// Track IDs of a playlist mixing both tastes: the shared tracks first, then each side's own
+/// top tracks taking turns, at most `size` in total. IDs that don't validate are skipped.
+pub fn blend_track_ids(comparison: &TasteComparison, size: usize) -> Vec<TrackId> {
+    let mut ids: Vec<TrackId> = Vec::new();
+    let mut push = |id: &str| {
+        let Ok(id) = TrackId::from_id(id) else {
+            return;
+        };
+        if ids.len() < size && !ids.contains(&id) {
+            ids.push(id);
+        }
+    };
+
+    for shared in comparison.shared_tracks.iter() {
+        push(&shared.item.id);
+    }
+
+    let mut mine = comparison.my_tracks.iter();
+    let mut theirs = comparison.their_tracks.iter();
+    loop {
+        let (my_next, their_next) = (mine.next(), theirs.next());
+        if my_next.is_none() && their_next.is_none() {
+            break;
+        }
+        for item in [my_next, their_next].into_iter().flatten() {
+            push(&item.id);
+        }
+    }
+
+    ids
+}
\ No newline at end of file
@@ -0,0 +1,170 @@
+use crate::api::SpotifyClient;
+use crate::models::*;
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// Most items Spotify returns for a top list
+pub const TOP_LIST_LIMIT: u32 = 50;
+
+/// Time ranges from the most recent to the longest, in the order of [`RankedItem::ranks`]
+pub const TIME_RANGES: [&str; 3] = ["short_term", "medium_term", "long_term"];
+
+/// Which top list is being compared
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TopListKind {
+    Tracks,
+    Artists,
+}
+
+impl TopListKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Tracks => "Tracks",
+            Self::Artists => "Artists",
+        }
+    }
+}
+
+/// How an item's rank in the last 4 weeks compares to its all-time rank
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RankMovement {
+    Up(usize),
+    Down(usize),
+    Steady,
+    /// In the recent list but not the all-time one
+    New,
+    /// In the all-time list but not the recent one
+    Dropped,
+}
+
+impl RankMovement {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Up(places) => format!("▲ {}", places),
+            Self::Down(places) => format!("▼ {}", places),
+            Self::Steady => "=".to_string(),
+            Self::New => "New".to_string(),
+            Self::Dropped => "Dropped".to_string(),
+        }
+    }
+
+    /// CSS modifier for the movement badge
+    pub fn class(&self) -> &'static str {
+        match self {
+            Self::Up(_) | Self::New => "up",
+            Self::Down(_) | Self::Dropped => "down",
+            Self::Steady => "steady",
+        }
+    }
+}
+
+/// A track or artist with its rank in each time range
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedItem<T> {
+    pub item: T,
+    /// 1-based rank per entry of [`TIME_RANGES`], `None` when not in that list
+    pub ranks: [Option<usize>; 3],
+}
+
+impl<T> RankedItem<T> {
+    pub fn short_rank(&self) -> Option<usize> {
+        self.ranks[0]
+    }
+
+    pub fn long_rank(&self) -> Option<usize> {
+        self.ranks[2]
+    }
+
+    pub fn movement(&self) -> RankMovement {
+        match (self.short_rank(), self.long_rank()) {
+            (Some(recent), Some(all_time)) if recent < all_time => RankMovement::Up(all_time - recent),
+            (Some(recent), Some(all_time)) if recent > all_time => RankMovement::Down(recent - all_time),
+            (Some(_), Some(_)) => RankMovement::Steady,
+            (Some(_), None) => RankMovement::New,
+            _ => RankMovement::Dropped,
+        }
+    }
+
+    pub fn in_all_ranges(&self) -> bool {
+        self.ranks.iter().all(Option::is_some)
+    }
+
+    /// Mean rank over the ranges the item appears in
+    pub fn average_rank(&self) -> f64 {
+        let ranks: Vec<usize> = self.ranks.iter().flatten().copied().collect();
+        if ranks.is_empty() {
+            return f64::MAX;
+        }
+        ranks.iter().sum::<usize>() as f64 / ranks.len() as f64
+    }
+}
+
+/// The top lists of all three time ranges merged by item
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeComparison<T> {
+    pub items: Vec<RankedItem<T>>,
+}
+
+impl<T: Clone> RangeComparison<T> {
+    /// Merge one list per entry of [`TIME_RANGES`], matching items by `key`
+    pub fn new(lists: [Vec<T>; 3], key: impl Fn(&T) -> String) -> Self {
+        let mut items: Vec<RankedItem<T>> = Vec::new();
+        let mut index_by_key: HashMap<String, usize> = HashMap::new();
+
+        for (range, list) in lists.into_iter().enumerate() {
+            for (position, item) in list.into_iter().enumerate() {
+                let index = *index_by_key.entry(key(&item)).or_insert_with(|| {
+                    items.push(RankedItem { item, ranks: [None; 3] });
+                    items.len() - 1
+                });
+                // Keep the first rank if the API lists an item twice
+                items[index].ranks[range].get_or_insert(position + 1);
+            }
+        }
+
+        Self { items }
+    }
+
+    /// Items of the last 4 weeks by their current rank
+    pub fn current(&self) -> Vec<&RankedItem<T>> {
+        let mut current: Vec<_> = self.items.iter().filter(|i| i.short_rank().is_some()).collect();
+        current.sort_by_key(|i| i.short_rank());
+        current
+    }
+
+    /// Recent items that are not in the all-time list
+    pub fn entered(&self) -> Vec<&RankedItem<T>> {
+        self.current().into_iter().filter(|i| i.long_rank().is_none()).collect()
+    }
+
+    /// All-time items that are no longer in the recent list, by all-time rank
+    pub fn dropped(&self) -> Vec<&RankedItem<T>> {
+        let mut dropped: Vec<_> =
+            self.items.iter().filter(|i| i.long_rank().is_some() && i.short_rank().is_none()).collect();
+        dropped.sort_by_key(|i| i.long_rank());
+        dropped
+    }
+
+    /// Items in all three lists, best average rank first
+    pub fn staying(&self) -> Vec<&RankedItem<T>> {
+        let mut staying: Vec<_> = self.items.iter().filter(|i| i.in_all_ranges()).collect();
+        staying.sort_by(|a, b| a.average_rank().total_cmp(&b.average_rank()));
+        staying
+    }
+}
+
+pub async fn compare_top_tracks(client: &SpotifyClient) -> Result<RangeComparison<Track>> {
+    let mut lists: [Vec<Track>; 3] = Default::default();
+    for (list, range) in lists.iter_mut().zip(TIME_RANGES) {
+        *list = client.get_top_tracks(TOP_LIST_LIMIT, range).await?;
+    }
+    Ok(RangeComparison::new(lists, |track| track.id.clone()))
+}
+
+pub async fn compare_top_artists(client: &SpotifyClient) -> Result<RangeComparison<Artist>> {
+    let mut lists: [Vec<Artist>; 3] = Default::default();
+    for (list, range) in lists.iter_mut().zip(TIME_RANGES) {
+        *list = client.get_top_artists(TOP_LIST_LIMIT, range).await?;
+    }
+    Ok(RangeComparison::new(lists, |artist| artist.id.clone()))
+}
\ No newline at end of file
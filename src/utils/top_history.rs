@@ -0,0 +1,91 @@
+use crate::api::SpotifyClient;
+use crate::models::*;
+use crate::utils::{list_data, load_data, save_data, TopListKind, TIME_RANGES, TOP_LIST_LIMIT};
+use anyhow::Result;
+
+/// Directory with one snapshot file per day
+const TOP_HISTORY_DIR: &str = "top_history";
+
+fn snapshot_file(date: &str) -> String {
+    format!("{}/{}.json", TOP_HISTORY_DIR, date)
+}
+
+fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+/// Stored top list snapshots, oldest first
+pub fn load_top_history() -> Vec<TopSnapshot> {
+    list_data(TOP_HISTORY_DIR)
+        .iter()
+        .filter(|name| name.ends_with(".json"))
+        .filter_map(|name| load_data(&format!("{}/{}", TOP_HISTORY_DIR, name)))
+        .collect()
+}
+
+/// Fetch and store the top tracks and artists of every time range, unless that already happened
+/// today. Returns whether a snapshot was recorded.
+pub async fn record_top_snapshot(client: &SpotifyClient) -> Result<bool> {
+    let date = today();
+    if load_data::<TopSnapshot>(&snapshot_file(&date)).is_some() {
+        return Ok(false);
+    }
+
+    let mut ranges = Vec::new();
+    for time_range in TIME_RANGES {
+        let tracks = client.get_top_tracks(TOP_LIST_LIMIT, time_range).await?;
+        let artists = client.get_top_artists(TOP_LIST_LIMIT, time_range).await?;
+        ranges.push(TopRangeSnapshot {
+            time_range: time_range.to_string(),
+            tracks: tracks.iter().map(snapshot_track).collect(),
+            artists: artists.iter().map(snapshot_artist).collect(),
+        });
+    }
+
+    let snapshot = TopSnapshot { date: date.clone(), captured_at: chrono::Utc::now().to_rfc3339(), ranges };
+    if let Err(e) = save_data(&snapshot_file(&date), &snapshot) {
+        eprintln!("Failed to save top list snapshot: {}", e);
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+fn snapshot_track(track: &Track) -> TopSnapshotTrack {
+    TopSnapshotTrack {
+        id: track.id.clone(),
+        name: track.name.clone(),
+        artists: track.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", "),
+        artist_ids: track.artists.iter().map(|a| a.id.clone()).collect(),
+    }
+}
+
+fn snapshot_artist(artist: &Artist) -> TopSnapshotArtist {
+    TopSnapshotArtist {
+        id: artist.id.clone(),
+        name: artist.name.clone(),
+        genres: artist.genres.clone().unwrap_or_default(),
+    }
+}
+
+/// Rank of a track or artist in every stored snapshot
+pub fn rank_timeline(history: &[TopSnapshot], kind: TopListKind, id: &str) -> RankTimeline {
+    let points: Vec<RankPoint> = history
+        .iter()
+        .map(|snapshot| {
+            let mut ranks = [None; 3];
+            for (rank, time_range) in ranks.iter_mut().zip(TIME_RANGES) {
+                *rank = snapshot.range(time_range).and_then(|range| match kind {
+                    TopListKind::Tracks => range.track_rank(id),
+                    TopListKind::Artists => range.artist_rank(id),
+                });
+            }
+            RankPoint { date: snapshot.date.clone(), ranks }
+        })
+        .collect();
+
+    let appears = |p: &&RankPoint| p.ranks.iter().any(Option::is_some);
+    let first_appeared = points.iter().find(appears).map(|p| p.date.clone());
+    let last_seen = points.iter().rev().find(appears).map(|p| p.date.clone());
+
+    RankTimeline { points, first_appeared, last_seen }
+}
\ No newline at end of file
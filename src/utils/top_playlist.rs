@@ -0,0 +1,127 @@
+use crate::api::SpotifyClient;
+use crate::models::*;
+use anyhow::Result;
+use std::collections::HashSet;
+
+/// Market used for artist top tracks when the user's country is unknown
+const FALLBACK_MARKET: &str = "from_token";
+
+/// What a generated top playlist is built from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TopPlaylistSource {
+    /// The user's own top tracks
+    TopTracks,
+    /// The most popular tracks of the user's top artists
+    TopArtistsMix,
+}
+
+impl TopPlaylistSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::TopTracks => "My Top Tracks",
+            Self::TopArtistsMix => "Top Artists Mix",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopPlaylistOptions {
+    pub source: TopPlaylistSource,
+    pub time_range: String,
+    /// Number of top tracks, or of top artists for the mix
+    pub limit: u32,
+    pub tracks_per_artist: usize,
+    /// Leave out tracks that are already saved in the library
+    pub exclude_library: bool,
+}
+
+impl TopPlaylistOptions {
+    pub fn new(time_range: &str) -> Self {
+        Self {
+            source: TopPlaylistSource::TopTracks,
+            time_range: time_range.to_string(),
+            limit: 50,
+            tracks_per_artist: 3,
+            exclude_library: false,
+        }
+    }
+
+    /// Name with today's date, so saving periodically keeps older snapshots apart
+    pub fn dated_name(&self) -> String {
+        format!(
+            "{} ({}) · {}",
+            self.source.label(),
+            time_range_label(&self.time_range),
+            chrono::Local::now().format("%Y-%m-%d")
+        )
+    }
+
+    pub fn description(&self) -> String {
+        let mut description = match self.source {
+            TopPlaylistSource::TopTracks => {
+                format!("My top {} tracks of the {}", self.limit, time_range_label(&self.time_range))
+            }
+            TopPlaylistSource::TopArtistsMix => format!(
+                "Top {} tracks of each of my top {} artists of the {}",
+                self.tracks_per_artist,
+                self.limit,
+                time_range_label(&self.time_range)
+            ),
+        };
+        if self.exclude_library {
+            description.push_str(", without tracks from my library");
+        }
+        description
+    }
+}
+
+/// Collect the tracks of a top playlist without creating it
+pub async fn build_top_playlist(
+    client: &SpotifyClient,
+    options: &TopPlaylistOptions,
+    market: Option<&str>,
+) -> Result<Vec<Track>> {
+    let limit = options.limit.clamp(1, 50);
+
+    let tracks = match options.source {
+        TopPlaylistSource::TopTracks => client.get_top_tracks(limit, &options.time_range).await?,
+        TopPlaylistSource::TopArtistsMix => {
+            let market = market.unwrap_or(FALLBACK_MARKET);
+            let mut tracks = Vec::new();
+            for artist in client.get_top_artists(limit, &options.time_range).await? {
+                match client.get_artist_top_tracks(&artist.artist_id(), market).await {
+                    Ok(top) => tracks.extend(top.into_iter().take(options.tracks_per_artist)),
+                    Err(e) => eprintln!("Failed to fetch top tracks of {}: {}", artist.name, e),
+                }
+            }
+            tracks
+        }
+    };
+
+    // Artists share tracks (features), so keep only the first copy
+    let mut seen = HashSet::new();
+    let mut tracks: Vec<Track> = tracks.into_iter().filter(|t| seen.insert(t.id.clone())).collect();
+
+    if options.exclude_library {
+        let saved = client.check_saved_tracks(tracks.iter().map(|t| t.track_id()).collect()).await?;
+        let mut saved = saved.into_iter();
+        tracks.retain(|_| !saved.next().unwrap_or(false));
+    }
+
+    Ok(tracks)
+}
+
+/// Create a playlist holding the given tracks
+pub async fn save_tracks_as_playlist(
+    client: &SpotifyClient,
+    name: &str,
+    description: &str,
+    tracks: &[Track],
+) -> Result<Playlist> {
+    let playlist = client.create_playlist(name, description, false, false).await?;
+    let uris: Vec<PlayableUri> = tracks.iter().map(|t| t.playable_uri()).collect();
+    if !uris.is_empty() {
+        client.add_tracks_to_playlist(&playlist.playlist_id(), uris).await?;
+    }
+    Ok(playlist)
+}
\ No newline at end of file
@@ -0,0 +1,167 @@
+use crate::api::SpotifyClient;
+use crate::models::*;
+use crate::utils::{load_data, remove_data, save_data};
+use anyhow::Result;
+
+/// File in the data directory holding the last unfinished transfer
+const TRANSFER_FILE: &str = "transfer.json";
+
+pub fn load_transfer_state() -> Option<TransferState> {
+    load_data(TRANSFER_FILE)
+}
+
+pub fn save_transfer_state(state: &TransferState) {
+    if let Err(e) = save_data(TRANSFER_FILE, state) {
+        eprintln!("Failed to save transfer state: {}", e);
+    }
+}
+
+pub fn clear_transfer_state() {
+    remove_data(TRANSFER_FILE);
+}
+
+/// Capture the playlists to copy and find tracks that are unavailable in the target market
+pub async fn plan_transfer(
+    source: &SpotifyClient,
+    target: &SpotifyClient,
+    playlists: Vec<Playlist>,
+    copy_saved_tracks: bool,
+    copy_followed_artists: bool,
+) -> Result<TransferState> {
+    let source_user = source.get_current_user().await?;
+    let target_user = target.get_current_user().await?;
+
+    let mut jobs = Vec::new();
+    let mut unavailable = Vec::new();
+
+    for playlist in playlists {
+        let items = source.get_playlist_tracks(&playlist.playlist_id(), None).await?;
+
+        // Ask the target account which tracks it can actually play
+        if let Some(market) = target_user.country.as_deref() {
+            let ids: Vec<TrackId> = items.iter().filter_map(|item| Some(item.as_track()?.track_id())).collect();
+            let tracks = target.get_tracks(ids, Some(market)).await?;
+            unavailable.extend(tracks.iter().filter(|t| t.is_playable == Some(false)).map(|t| UnavailableTrack {
+                playlist_name: playlist.name.clone(),
+                track_name: t.name.clone(),
+                artists: t.artists.iter().map(|a| a.name.clone()).collect::<Vec<_>>().join(", "),
+                uri: t.spotify_uri(),
+            }));
+        }
+
+        let collaborative = playlist.collaborative.unwrap_or(false);
+        jobs.push(TransferJob {
+            source_playlist_id: playlist.id.clone(),
+            name: playlist.name.clone(),
+            description: playlist.description.clone().unwrap_or_default(),
+            public: playlist.public.unwrap_or(false) && !collaborative,
+            collaborative,
+            uris: items.iter().filter_map(|item| item.track.addable_uri().map(|uri| uri.uri())).collect(),
+            target_playlist_id: None,
+            tracks_added: 0,
+            status: TransferStatus::Pending,
+            error: None,
+        });
+    }
+
+    Ok(TransferState {
+        source_user_id: source_user.id.clone(),
+        source_user_name: source_user.display_name.unwrap_or(source_user.id),
+        target_user_id: target_user.id.clone(),
+        target_user_name: target_user.display_name.unwrap_or(target_user.id),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        jobs,
+        copy_saved_tracks,
+        copy_followed_artists,
+        saved_tracks_done: false,
+        followed_artists_done: false,
+        unavailable,
+    })
+}
+
+/// Run (or resume) a transfer. `on_progress` is called after every step so the state can be
+/// shown and persisted; the transfer stops at the first failure and can be resumed later.
+pub async fn run_transfer(
+    source: &SpotifyClient,
+    target: &SpotifyClient,
+    state: &mut TransferState,
+    on_progress: &mut dyn FnMut(&TransferState),
+) -> Result<()> {
+    for index in 0..state.jobs.len() {
+        if state.jobs[index].status == TransferStatus::Done {
+            continue;
+        }
+
+        state.jobs[index].status = TransferStatus::InProgress;
+        state.jobs[index].error = None;
+        on_progress(state);
+
+        if let Err(e) = transfer_playlist(target, state, index, on_progress).await {
+            state.jobs[index].status = TransferStatus::Failed;
+            state.jobs[index].error = Some(e.to_string());
+            on_progress(state);
+            return Err(e);
+        }
+
+        state.jobs[index].status = TransferStatus::Done;
+        on_progress(state);
+    }
+
+    if state.copy_saved_tracks && !state.saved_tracks_done {
+        let saved: Vec<(TrackId, String)> = source
+            .get_saved_tracks()
+            .await?
+            .into_iter()
+            .map(|s| (s.track.track_id(), s.added_at))
+            .collect();
+        target.save_tracks(saved).await?;
+        state.saved_tracks_done = true;
+        on_progress(state);
+    }
+
+    if state.copy_followed_artists && !state.followed_artists_done {
+        let artist_ids: Vec<ArtistId> = source.get_followed_artists().await?.into_iter().map(|a| a.artist_id()).collect();
+        target.follow_artists(artist_ids).await?;
+        state.followed_artists_done = true;
+        on_progress(state);
+    }
+
+    Ok(())
+}
+
+async fn transfer_playlist(
+    target: &SpotifyClient,
+    state: &mut TransferState,
+    index: usize,
+    on_progress: &mut dyn FnMut(&TransferState),
+) -> Result<()> {
+    let target_id = match state.jobs[index].target_playlist_id.as_deref() {
+        Some(id) => PlaylistId::from_id(id)?,
+        None => {
+            let job = &state.jobs[index];
+            let created = target
+                .create_playlist(&job.name, &job.description, job.public, job.collaborative)
+                .await?;
+            state.jobs[index].target_playlist_id = Some(created.id.clone());
+            on_progress(state);
+            created.playlist_id()
+        }
+    };
+
+    // Add in chunks so progress is saved and a resume continues where it stopped
+    while state.jobs[index].tracks_added < state.jobs[index].uris.len() {
+        let job = &state.jobs[index];
+        let end = (job.tracks_added + 100).min(job.uris.len());
+        // The job is resumed from a file, so its URIs are validated again
+        let chunk = job.uris[job.tracks_added..end]
+            .iter()
+            .map(|uri| PlayableUri::parse(uri))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        target.add_tracks_to_playlist(&target_id, chunk).await?;
+        state.jobs[index].tracks_added = end;
+        on_progress(state);
+    }
+
+    Ok(())
+}